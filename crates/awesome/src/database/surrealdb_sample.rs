@@ -32,7 +32,6 @@ struct Record {
     id: RecordId,
 }
 
-#[tokio::main]
 async fn surreal_mem_sample() -> surrealdb::Result<()> {
     // Create database connection in memory
     let db = Surreal::new::<Mem>(()).await?;
@@ -105,8 +104,8 @@ async fn surreal_mem_sample() -> surrealdb::Result<()> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_surreal_mem_sample() {
-        surreal_mem_sample().unwrap();
+    #[tokio::test]
+    async fn test_surreal_mem_sample() {
+        surreal_mem_sample().await.unwrap();
     }
 }
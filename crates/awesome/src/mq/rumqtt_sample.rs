@@ -10,10 +10,23 @@ use tracing_subscriber::prelude::*;
 use tracing_subscriber::FmtSubscriber;
 use tracing_subscriber::{filter::LevelFilter, EnvFilter}; // For `with_filter` and `EnvFilter` // For setting log level
 
-pub fn mqtt_sync_sample() {
+/// Default MQTT port, used when the broker address has none
+const DEFAULT_PORT: u16 = 1883;
+
+/// Connection options for the broker at `broker` (`host:port`, e.g. "127.0.0.1:1883")
+fn broker_options(client_id: &str, broker: &str) -> MqttOptions {
+    let (host, port) = match broker.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse().unwrap_or(DEFAULT_PORT)),
+        None => (broker, DEFAULT_PORT),
+    };
+    MqttOptions::new(client_id, host, port)
+}
+
+/// Publish to and subscribe from `hello/rumqtt` on the broker at `broker` (`host:port`)
+pub fn mqtt_sync_sample(broker: &str) {
     info!("mqtt sync sample start ...");
     // let mut mqttoptions = MqttOptions::new("rumqtt-sync", "test.mosquitto.org", 1883);
-    let mut mqttoptions = MqttOptions::new("rumqtt-sync", "127.0.0.1", 1883);
+    let mut mqttoptions = broker_options("rumqtt-sync", broker);
 
     mqttoptions.set_keep_alive(Duration::from_secs(5));
 
@@ -45,12 +58,10 @@ pub fn mqtt_sync_sample() {
         }
     }
 }
-#[tokio::main]
-
-async fn mqtt_async_sample() {
+async fn mqtt_async_sample(broker: &str) {
     info!("mqtt_async_sample 'hello/rumqtt'");
 
-    let mut mqttoptions = MqttOptions::new("rumqtt-async", "127.0.0.1", 1883);
+    let mut mqttoptions = broker_options("rumqtt-async", broker);
     mqttoptions.set_keep_alive(Duration::from_secs(5));
 
     let (mut client, mut eventloop) = AsyncClient::new(mqttoptions, 15);
@@ -143,24 +154,32 @@ mod tests {
             )
             .init(); // Initialize the global default subscribe
     }
+    #[test]
+    fn test_broker_options() {
+        let options = broker_options("id", "broker.local:1884");
+        assert_eq!(options.broker_address(), ("broker.local".to_string(), 1884));
+        let options = broker_options("id", "broker.local");
+        assert_eq!(options.broker_address(), ("broker.local".to_string(), 1883));
+    }
+
     #[ignore = "mq test"]
     #[test]
     fn test_mqtt_sync_sample() {
         setup();
         // This test will run the MQTT publish/subscribe sample
         println!("Running MQTT subscription test...");
-        mqtt_sync_sample();
+        mqtt_sync_sample("127.0.0.1:1883");
         assert!(true, "MQTT subscription test passed");
     }
 
     // #[test_log::test]
     #[ignore = "mq test"]
-    #[test]
-    fn test_mqtt_async_sample() {
+    #[tokio::test]
+    async fn test_mqtt_async_sample() {
         setup();
         // This test will run the MQTT publish/subscribe sample
         info!("Running MQTT async sample  test...");
-        mqtt_async_sample();
+        mqtt_async_sample("127.0.0.1:1883").await;
         assert!(true, "MQTT async sample test passed");
         info!("MQTT async sample test passed");
     }
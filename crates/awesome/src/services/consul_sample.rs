@@ -5,14 +5,20 @@ use rs_consul::{
 use std::collections::HashMap;
 use tokio::time::{sleep, Duration};
 
-#[tokio::main]
-pub async fn main_consul() -> Result<(), Box<dyn std::error::Error>> {
+pub async fn main_consul() -> anyhow::Result<()> {
     // 1. 配置 Consul 客户端
-    // 默认连接到 localhost:8500。如果你有不同的Consul地址，可以在这里配置。
-    // 例如：.address("http://your-consul-host:8500")
+    // 默认连接到 localhost:8500。如果你有不同的Consul地址，可以通过环境变量配置。
+    // 例如：CONSUL_HTTP_ADDR=http://your-consul-host:8500
 
+    let address =
+        std::env::var("CONSUL_HTTP_ADDR").unwrap_or_else(|_| "http://127.0.0.1:8500".to_string());
+    consul_demo(address).await
+}
+
+/// 对 `address` 处的 Consul 演示服务注册、发现、KV 存储和注销
+pub async fn consul_demo(address: String) -> anyhow::Result<()> {
     let var_name = Config {
-        address: "http://192.168.2.6:8500".to_string(),
+        address,
         token: None,          // No token required in development mode
         ..Default::default()  // Uses default values for other settings
     };
@@ -123,10 +129,12 @@ pub async fn main_consul() -> Result<(), Box<dyn std::error::Error>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::fake_consul::FakeConsul;
 
-    #[ignore = "consul client requires a running Consul instance"]
-    #[test]
-    fn test_hello_client() {
-        main_consul().unwrap();
+    #[tokio::test]
+    async fn test_hello_client() {
+        // 用本地的 Consul 替身代替真实的 Consul
+        let consul = FakeConsul::start().await.unwrap();
+        consul_demo(consul.http_addr()).await.unwrap();
     }
 }
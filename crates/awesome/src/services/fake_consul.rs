@@ -0,0 +1,1200 @@
+//! A stand-in Consul agent for tests and local development.
+//!
+//! `FakeConsul` serves the part of the Consul HTTP API the samples use, on any port:
+//! - agent: `service/register`, `service/deregister/{id}`, `services`, `checks`
+//! - catalog: `register`, `deregister`, `services`, `service/{name}`
+//! - health: `service/{name}` (with `?passing`), `checks/{name}`, `state/{state}`
+//! - KV: `GET`/`PUT`/`DELETE kv/{key}` with `recurse`, `keys`, `raw`, `flags` and `cas`
+//! - `status/leader`
+//!
+//! HTTP and TCP checks registered with a service really run at their interval, and a
+//! service critical for longer than `DeregisterCriticalServiceAfter` is removed.
+//! Catalog and health reads are blocking queries: with `?index=N` they answer once the
+//! `X-Consul-Index` moves past N or `wait` elapses.
+
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::time::{Duration, Instant};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio::task::{AbortHandle, JoinHandle};
+use tracing::{info, warn};
+
+/// Node name of the services registered through the agent endpoints
+const AGENT_NODE: &str = "fake-consul";
+const AGENT_NODE_ID: &str = "00000000-0000-0000-0000-000000000001";
+const DATACENTER: &str = "dc1";
+
+/// Wait of a blocking query without `wait`, and the longest one allowed (as in Consul)
+const DEFAULT_WAIT: Duration = Duration::from_secs(300);
+const MAX_WAIT: Duration = Duration::from_secs(600);
+/// Interval and timeout of a check that does not set them
+const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+const PASSING: &str = "passing";
+const WARNING: &str = "warning";
+const CRITICAL: &str = "critical";
+
+// --- Request bodies (field names are matched like Consul does, ignoring case) ---
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct ServiceRegistration {
+    #[serde(rename = "ID", alias = "Id", default)]
+    id: Option<String>,
+    name: String,
+    #[serde(default)]
+    tags: Option<Vec<String>>,
+    #[serde(default)]
+    address: Option<String>,
+    #[serde(default)]
+    port: Option<u16>,
+    #[serde(default)]
+    meta: Option<HashMap<String, String>>,
+    #[serde(default)]
+    check: Option<CheckDefinition>,
+    #[serde(default)]
+    checks: Option<Vec<CheckDefinition>>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "PascalCase")]
+struct CheckDefinition {
+    #[serde(rename = "CheckID", alias = "CheckId", default)]
+    check_id: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(rename = "HTTP", alias = "Http", default)]
+    http: Option<String>,
+    #[serde(rename = "TCP", alias = "Tcp", default)]
+    tcp: Option<String>,
+    #[serde(default)]
+    interval: Option<String>,
+    #[serde(default)]
+    timeout: Option<String>,
+    #[serde(default)]
+    deregister_critical_service_after: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct CatalogRegistration {
+    node: String,
+    address: String,
+    #[serde(default)]
+    service: Option<CatalogService>,
+    #[serde(default)]
+    checks: Option<Vec<CatalogCheck>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct CatalogService {
+    #[serde(rename = "ID", default)]
+    id: Option<String>,
+    service: String,
+    #[serde(default)]
+    tags: Option<Vec<String>>,
+    #[serde(default)]
+    address: Option<String>,
+    #[serde(default)]
+    port: Option<u16>,
+    #[serde(default)]
+    meta: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct CatalogCheck {
+    #[serde(rename = "CheckID", default)]
+    check_id: Option<String>,
+    name: String,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(rename = "ServiceID", default)]
+    service_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct CatalogDeregistration {
+    #[serde(default)]
+    node: Option<String>,
+    #[serde(rename = "ServiceID", default)]
+    service_id: Option<String>,
+    #[serde(rename = "CheckID", default)]
+    check_id: Option<String>,
+}
+
+// --- Agent state ---
+
+#[derive(Debug, Clone)]
+struct ServiceEntry {
+    node: String,
+    node_address: String,
+    id: String,
+    name: String,
+    tags: Vec<String>,
+    address: String,
+    port: u16,
+    meta: HashMap<String, String>,
+}
+
+impl ServiceEntry {
+    /// The service as `agent/services` and `health/service` report it
+    fn to_json(&self) -> Value {
+        json!({
+            "ID": self.id,
+            "Service": self.name,
+            "Tags": self.tags,
+            "Address": self.address,
+            "Port": self.port,
+            "Meta": self.meta,
+        })
+    }
+
+    /// The node the service runs on
+    fn node_json(&self) -> Value {
+        json!({
+            "ID": if self.node == AGENT_NODE { AGENT_NODE_ID } else { "" },
+            "Node": self.node,
+            "Address": self.node_address,
+            "Datacenter": DATACENTER,
+        })
+    }
+
+    /// The entry `catalog/service/{name}` reports
+    fn catalog_json(&self) -> Value {
+        json!({
+            "ID": if self.node == AGENT_NODE { AGENT_NODE_ID } else { "" },
+            "Node": self.node,
+            "Address": self.node_address,
+            "Datacenter": DATACENTER,
+            "ServiceID": self.id,
+            "ServiceName": self.name,
+            "ServiceAddress": self.address,
+            "ServicePort": self.port,
+            "ServiceTags": self.tags,
+            "ServiceMeta": self.meta,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct CheckEntry {
+    node: String,
+    id: String,
+    name: String,
+    service_id: String,
+    service_name: String,
+    kind: &'static str,
+    status: String,
+    output: String,
+    critical_since: Option<Instant>,
+    task: Option<AbortHandle>,
+}
+
+impl CheckEntry {
+    fn to_json(&self) -> Value {
+        json!({
+            "Node": self.node,
+            "CheckID": self.id,
+            "Name": self.name,
+            "Status": self.status,
+            "Notes": "",
+            "Output": self.output,
+            "ServiceID": self.service_id,
+            "ServiceName": self.service_name,
+            "Type": self.kind,
+        })
+    }
+}
+
+impl Drop for CheckEntry {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
+
+#[derive(Debug)]
+struct KvEntry {
+    value: Vec<u8>,
+    flags: u64,
+    create_index: u64,
+    modify_index: u64,
+}
+
+#[derive(Debug, Default)]
+struct Agent {
+    services: BTreeMap<String, ServiceEntry>,
+    checks: BTreeMap<String, CheckEntry>,
+    kv: BTreeMap<String, KvEntry>,
+    index: u64,
+}
+
+impl Agent {
+    /// Removes a service with its checks; `false` if it was not registered
+    fn remove_service(&mut self, service_id: &str) -> bool {
+        self.checks
+            .retain(|_, check| check.service_id != service_id);
+        self.services.remove(service_id).is_some()
+    }
+
+    /// Whether every check of the service and its node passes (no checks pass too)
+    fn is_passing(&self, service: &ServiceEntry) -> bool {
+        self.checks
+            .values()
+            .filter(|check| check.node == service.node)
+            .filter(|check| check.service_id == service.id || check.service_id.is_empty())
+            .all(|check| check.status == PASSING)
+    }
+
+    fn service_checks(&self, service_id: &str) -> Vec<Value> {
+        self.checks
+            .values()
+            .filter(|check| check.service_id == service_id)
+            .map(CheckEntry::to_json)
+            .collect()
+    }
+}
+
+/// State shared by the HTTP handlers and the check tasks
+#[derive(Debug)]
+struct Shared {
+    agent: Mutex<Agent>,
+    /// Latest index, for blocking queries
+    changes: watch::Sender<u64>,
+    http_client: reqwest::Client,
+}
+
+impl Shared {
+    fn agent(&self) -> MutexGuard<'_, Agent> {
+        self.agent.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Applies a change to the agent and moves the index past it
+    fn update<R>(&self, change: impl FnOnce(&mut Agent, u64) -> R) -> R {
+        let mut agent = self.agent();
+        let index = agent.index + 1;
+        let result = change(&mut agent, index);
+        agent.index = index;
+        drop(agent);
+        self.changes.send_replace(index);
+        result
+    }
+
+    /// Records the result of a check run
+    /// Returns `false` once the check is gone and its task should stop.
+    fn record_check(
+        &self,
+        check_id: &str,
+        status: &str,
+        output: String,
+        deregister_after: Option<Duration>,
+    ) -> bool {
+        let mut agent = self.agent();
+        let Some(check) = agent.checks.get_mut(check_id) else {
+            return false;
+        };
+        check.output = output;
+        let changed = check.status != status;
+        if changed {
+            check.status = status.to_string();
+        }
+        if status != CRITICAL {
+            check.critical_since = None;
+        } else if check.critical_since.is_none() {
+            check.critical_since = Some(Instant::now());
+        }
+
+        let expired = match (check.critical_since, deregister_after) {
+            (Some(since), Some(after)) => since.elapsed() >= after,
+            _ => false,
+        };
+        let service_id = check.service_id.clone();
+        if expired {
+            // Removing the check drops its own abort handle: detach it first
+            check.task = None;
+            warn!(
+                "Service '{}' critical for longer than {:?}: deregistering it.",
+                service_id,
+                deregister_after.unwrap_or_default()
+            );
+            agent.remove_service(&service_id);
+        }
+        if changed || expired {
+            agent.index += 1;
+            let index = agent.index;
+            drop(agent);
+            self.changes.send_replace(index);
+        }
+        !expired
+    }
+}
+
+/// What a check runs
+#[derive(Debug, Clone)]
+enum Probe {
+    Http(String),
+    Tcp(String),
+}
+
+impl CheckDefinition {
+    /// Rejects durations the agent could not run the check with, as Consul answers 400
+    fn validate(&self) -> Result<(), String> {
+        let fields = [
+            ("Interval", &self.interval, false),
+            ("Timeout", &self.timeout, false),
+            (
+                "DeregisterCriticalServiceAfter",
+                &self.deregister_critical_service_after,
+                true,
+            ),
+        ];
+        for (name, value, zero_allowed) in fields {
+            let Some(value) = value else {
+                continue;
+            };
+            match parse_duration(value) {
+                Some(duration) if zero_allowed || !duration.is_zero() => {}
+                Some(_) => return Err(format!("{} must be positive, got {:?}", name, value)),
+                None => return Err(format!("Invalid {} {:?}", name, value)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Probe {
+    fn from_definition(definition: &CheckDefinition) -> Option<Self> {
+        match (&definition.http, &definition.tcp) {
+            (Some(url), _) => Some(Probe::Http(url.clone())),
+            (None, Some(addr)) => Some(Probe::Tcp(addr.clone())),
+            (None, None) => None,
+        }
+    }
+
+    fn kind(probe: Option<&Self>) -> &'static str {
+        match probe {
+            Some(Probe::Http(_)) => "http",
+            Some(Probe::Tcp(_)) => "tcp",
+            None => "",
+        }
+    }
+
+    /// Runs the check once: 2xx passes and 429 warns (as in Consul), a TCP connect passes
+    async fn run(&self, client: &reqwest::Client, timeout: Duration) -> (&'static str, String) {
+        match self {
+            Probe::Http(url) => match client.get(url).timeout(timeout).send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    let result = if status.is_success() {
+                        PASSING
+                    } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                        WARNING
+                    } else {
+                        CRITICAL
+                    };
+                    (result, format!("HTTP GET {}: {}", url, status))
+                }
+                Err(e) => (CRITICAL, format!("HTTP GET {}: {}", url, e)),
+            },
+            Probe::Tcp(addr) => match tokio::time::timeout(timeout, TcpStream::connect(addr)).await
+            {
+                Ok(Ok(_)) => (PASSING, format!("TCP connect {}: Success", addr)),
+                Ok(Err(e)) => (CRITICAL, format!("TCP connect {}: {}", addr, e)),
+                Err(_) => (CRITICAL, format!("TCP connect {}: timed out", addr)),
+            },
+        }
+    }
+}
+
+/// Runs `probe` every interval until the check is removed or the agent is gone
+fn spawn_check(
+    shared: &Arc<Shared>,
+    check_id: String,
+    probe: Probe,
+    definition: &CheckDefinition,
+) -> AbortHandle {
+    let interval = parse_duration_or(&definition.interval, DEFAULT_CHECK_INTERVAL);
+    let timeout = parse_duration_or(&definition.timeout, DEFAULT_CHECK_TIMEOUT);
+    let deregister_after = definition
+        .deregister_critical_service_after
+        .as_deref()
+        .and_then(parse_duration);
+    let shared: Weak<Shared> = Arc::downgrade(shared);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let Some(client) = shared.upgrade().map(|s| s.http_client.clone()) else {
+                break;
+            };
+            let (status, output) = probe.run(&client, timeout).await;
+            let Some(shared) = shared.upgrade() else {
+                break;
+            };
+            if !shared.record_check(&check_id, status, output, deregister_after) {
+                break;
+            }
+        }
+    })
+    .abort_handle()
+}
+
+/// Parses a Go duration such as "10s", "1m30s" or "500ms"
+fn parse_duration(text: &str) -> Option<Duration> {
+    let mut total = Duration::ZERO;
+    let mut rest = text.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let seconds = match &rest[..unit_len] {
+            "ns" => 1e-9,
+            "us" | "µs" => 1e-6,
+            "ms" => 1e-3,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        // Huge or non-finite values are invalid rather than a panic
+        let part = Duration::try_from_secs_f64(number * seconds).ok()?;
+        total = total.checked_add(part)?;
+    }
+    Some(total)
+}
+
+fn parse_duration_or(text: &Option<String>, default: Duration) -> Duration {
+    text.as_deref().and_then(parse_duration).unwrap_or(default)
+}
+
+/// Whether a boolean query flag is set (`?passing` and `?passing=true` both are)
+fn flag(params: &HashMap<String, String>, name: &str) -> bool {
+    params
+        .get(name)
+        .is_some_and(|value| value.is_empty() || value == "true" || value == "1")
+}
+
+/// Waits for a blocking query: until the index moves past `?index` or `?wait` elapses
+async fn block(shared: &Shared, params: &HashMap<String, String>) {
+    let Some(index) = params.get("index").and_then(|i| i.parse::<u64>().ok()) else {
+        return;
+    };
+    let wait = params
+        .get("wait")
+        .and_then(|wait| parse_duration(wait))
+        .unwrap_or(DEFAULT_WAIT)
+        .min(MAX_WAIT);
+    let mut changes = shared.changes.subscribe();
+    let _ = tokio::time::timeout(wait, changes.wait_for(|current| *current > index)).await;
+}
+
+/// A JSON response carrying the index it was read at
+fn indexed(index: u64, body: Value) -> Response {
+    ([("X-Consul-Index", index.to_string())], Json(body)).into_response()
+}
+
+fn not_found(message: String) -> Response {
+    (StatusCode::NOT_FOUND, message).into_response()
+}
+
+fn bad_request(message: String) -> Response {
+    (StatusCode::BAD_REQUEST, message).into_response()
+}
+
+// --- Agent endpoints ---
+
+async fn agent_register(State(shared): State<Arc<Shared>>, body: Bytes) -> Response {
+    let registration: ServiceRegistration = match serde_json::from_slice(&body) {
+        Ok(registration) => registration,
+        Err(e) => return bad_request(format!("Request decode failed: {}", e)),
+    };
+    let id = registration
+        .id
+        .clone()
+        .unwrap_or_else(|| registration.name.clone());
+    let service = ServiceEntry {
+        node: AGENT_NODE.to_string(),
+        node_address: "127.0.0.1".to_string(),
+        id: id.clone(),
+        name: registration.name.clone(),
+        tags: registration.tags.clone().unwrap_or_default(),
+        address: registration.address.clone().unwrap_or_default(),
+        port: registration.port.unwrap_or_default(),
+        meta: registration.meta.clone().unwrap_or_default(),
+    };
+    let definitions: Vec<CheckDefinition> = registration
+        .check
+        .into_iter()
+        .chain(registration.checks.into_iter().flatten())
+        .collect();
+    if let Err(e) = definitions.iter().try_for_each(CheckDefinition::validate) {
+        return bad_request(e);
+    }
+
+    shared.update(|agent, _| {
+        agent.remove_service(&id);
+        let count = definitions.len();
+        for (n, definition) in definitions.iter().enumerate() {
+            let check_id = definition.check_id.clone().unwrap_or_else(|| {
+                if count == 1 {
+                    format!("service:{}", id)
+                } else {
+                    format!("service:{}:{}", id, n + 1)
+                }
+            });
+            let probe = Probe::from_definition(definition);
+            let kind = Probe::kind(probe.as_ref());
+            let task = probe.map(|probe| spawn_check(&shared, check_id.clone(), probe, definition));
+            let check = CheckEntry {
+                node: AGENT_NODE.to_string(),
+                id: check_id.clone(),
+                name: definition
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("Service '{}' check", service.name)),
+                service_id: id.clone(),
+                service_name: service.name.clone(),
+                kind,
+                status: definition
+                    .status
+                    .clone()
+                    .unwrap_or_else(|| CRITICAL.to_string()),
+                output: String::new(),
+                critical_since: None,
+                task,
+            };
+            agent.checks.insert(check_id, check);
+        }
+        agent.services.insert(id.clone(), service);
+    });
+    info!("Registered service '{}'.", id);
+    StatusCode::OK.into_response()
+}
+
+async fn agent_deregister(
+    State(shared): State<Arc<Shared>>,
+    Path(service_id): Path<String>,
+) -> Response {
+    if !shared.agent().services.contains_key(&service_id) {
+        return not_found(format!("Unknown service ID {:?}", service_id));
+    }
+    shared.update(|agent, _| agent.remove_service(&service_id));
+    info!("Deregistered service '{}'.", service_id);
+    StatusCode::OK.into_response()
+}
+
+async fn agent_services(State(shared): State<Arc<Shared>>) -> Response {
+    let agent = shared.agent();
+    let services: serde_json::Map<String, Value> = agent
+        .services
+        .values()
+        .filter(|service| service.node == AGENT_NODE)
+        .map(|service| (service.id.clone(), service.to_json()))
+        .collect();
+    Json(Value::Object(services)).into_response()
+}
+
+async fn agent_checks(State(shared): State<Arc<Shared>>) -> Response {
+    let agent = shared.agent();
+    let checks: serde_json::Map<String, Value> = agent
+        .checks
+        .values()
+        .filter(|check| check.node == AGENT_NODE)
+        .map(|check| (check.id.clone(), check.to_json()))
+        .collect();
+    Json(Value::Object(checks)).into_response()
+}
+
+// --- Catalog endpoints ---
+
+async fn catalog_register(State(shared): State<Arc<Shared>>, body: Bytes) -> Response {
+    let registration: CatalogRegistration = match serde_json::from_slice(&body) {
+        Ok(registration) => registration,
+        Err(e) => return bad_request(format!("Request decode failed: {}", e)),
+    };
+    shared.update(|agent, _| {
+        let mut service_name = String::new();
+        if let Some(service) = &registration.service {
+            let id = service
+                .id
+                .clone()
+                .unwrap_or_else(|| service.service.clone());
+            service_name = service.service.clone();
+            agent.services.insert(
+                id.clone(),
+                ServiceEntry {
+                    node: registration.node.clone(),
+                    node_address: registration.address.clone(),
+                    id,
+                    name: service.service.clone(),
+                    tags: service.tags.clone().unwrap_or_default(),
+                    address: service.address.clone().unwrap_or_default(),
+                    port: service.port.unwrap_or_default(),
+                    meta: service.meta.clone().unwrap_or_default(),
+                },
+            );
+        }
+        for check in registration.checks.iter().flatten() {
+            let id = check.check_id.clone().unwrap_or_else(|| check.name.clone());
+            let service_id = check.service_id.clone().unwrap_or_default();
+            agent.checks.insert(
+                id.clone(),
+                CheckEntry {
+                    node: registration.node.clone(),
+                    id,
+                    name: check.name.clone(),
+                    service_name: if service_id.is_empty() {
+                        String::new()
+                    } else {
+                        service_name.clone()
+                    },
+                    service_id,
+                    kind: "",
+                    status: check.status.clone().unwrap_or_else(|| CRITICAL.to_string()),
+                    output: String::new(),
+                    critical_since: None,
+                    task: None,
+                },
+            );
+        }
+    });
+    Json(true).into_response()
+}
+
+async fn catalog_deregister(State(shared): State<Arc<Shared>>, body: Bytes) -> Response {
+    let deregistration: CatalogDeregistration = match serde_json::from_slice(&body) {
+        Ok(deregistration) => deregistration,
+        Err(e) => return bad_request(format!("Request decode failed: {}", e)),
+    };
+    shared.update(
+        |agent, _| match (&deregistration.service_id, &deregistration.check_id) {
+            (Some(service_id), _) => {
+                agent.remove_service(service_id);
+            }
+            (None, Some(check_id)) => {
+                agent.checks.remove(check_id);
+            }
+            (None, None) => {
+                if let Some(node) = &deregistration.node {
+                    agent.services.retain(|_, service| &service.node != node);
+                    agent.checks.retain(|_, check| &check.node != node);
+                }
+            }
+        },
+    );
+    Json(true).into_response()
+}
+
+async fn catalog_services(
+    State(shared): State<Arc<Shared>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    block(&shared, &params).await;
+    let agent = shared.agent();
+    let mut services: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for service in agent.services.values() {
+        let tags = services.entry(&service.name).or_default();
+        for tag in &service.tags {
+            if !tags.contains(&tag.as_str()) {
+                tags.push(tag);
+            }
+        }
+    }
+    indexed(agent.index, json!(services))
+}
+
+async fn catalog_service(
+    State(shared): State<Arc<Shared>>,
+    Path(name): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    block(&shared, &params).await;
+    let agent = shared.agent();
+    let nodes: Vec<Value> = agent
+        .services
+        .values()
+        .filter(|service| service.name == name)
+        .filter(|service| {
+            params
+                .get("tag")
+                .is_none_or(|tag| service.tags.contains(tag))
+        })
+        .map(ServiceEntry::catalog_json)
+        .collect();
+    indexed(agent.index, Value::Array(nodes))
+}
+
+// --- Health endpoints ---
+
+async fn health_service(
+    State(shared): State<Arc<Shared>>,
+    Path(name): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    block(&shared, &params).await;
+    let passing_only = flag(&params, "passing");
+    let agent = shared.agent();
+    let entries: Vec<Value> = agent
+        .services
+        .values()
+        .filter(|service| service.name == name)
+        .filter(|service| !passing_only || agent.is_passing(service))
+        .map(|service| {
+            json!({
+                "Node": service.node_json(),
+                "Service": service.to_json(),
+                "Checks": agent.service_checks(&service.id),
+            })
+        })
+        .collect();
+    indexed(agent.index, Value::Array(entries))
+}
+
+async fn health_checks(
+    State(shared): State<Arc<Shared>>,
+    Path(name): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    block(&shared, &params).await;
+    let agent = shared.agent();
+    let checks: Vec<Value> = agent
+        .checks
+        .values()
+        .filter(|check| check.service_name == name)
+        .map(CheckEntry::to_json)
+        .collect();
+    indexed(agent.index, Value::Array(checks))
+}
+
+async fn health_state(
+    State(shared): State<Arc<Shared>>,
+    Path(state): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    if ![PASSING, WARNING, CRITICAL, "any"].contains(&state.as_str()) {
+        return bad_request(format!("Invalid check state {:?}", state));
+    }
+    block(&shared, &params).await;
+    let agent = shared.agent();
+    let checks: Vec<Value> = agent
+        .checks
+        .values()
+        .filter(|check| state == "any" || check.status == state)
+        .map(CheckEntry::to_json)
+        .collect();
+    indexed(agent.index, Value::Array(checks))
+}
+
+// --- KV endpoints ---
+
+async fn kv_get(
+    State(shared): State<Arc<Shared>>,
+    Path(key): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    block(&shared, &params).await;
+    let agent = shared.agent();
+    let index = agent.index;
+
+    if flag(&params, "keys") {
+        let keys: Vec<&String> = agent.kv.keys().filter(|k| k.starts_with(&key)).collect();
+        if keys.is_empty() {
+            return not_found(String::new());
+        }
+        return indexed(index, json!(keys));
+    }
+
+    let entries: Vec<(&String, &KvEntry)> = if flag(&params, "recurse") {
+        agent
+            .kv
+            .iter()
+            .filter(|(k, _)| k.starts_with(&key))
+            .collect()
+    } else {
+        agent.kv.get_key_value(&key).into_iter().collect()
+    };
+    let Some((_, first)) = entries.first() else {
+        return not_found(String::new());
+    };
+    if flag(&params, "raw") {
+        return ([("X-Consul-Index", index.to_string())], first.value.clone()).into_response();
+    }
+    let body: Vec<Value> = entries
+        .iter()
+        .map(|(key, entry)| {
+            json!({
+                "LockIndex": 0,
+                "Key": key,
+                "Flags": entry.flags,
+                "Value": base64::engine::general_purpose::STANDARD.encode(&entry.value),
+                "CreateIndex": entry.create_index,
+                "ModifyIndex": entry.modify_index,
+            })
+        })
+        .collect();
+    indexed(index, Value::Array(body))
+}
+
+async fn kv_put(
+    State(shared): State<Arc<Shared>>,
+    Path(key): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+    body: Bytes,
+) -> Response {
+    let flags = params
+        .get("flags")
+        .and_then(|flags| flags.parse().ok())
+        .unwrap_or(0);
+    let cas: Option<u64> = params.get("cas").and_then(|cas| cas.parse().ok());
+    let written = shared.update(|agent, index| {
+        let current = agent.kv.get(&key).map(|entry| entry.modify_index);
+        // cas=0 only creates; cas=N only replaces the entry last modified at N
+        match (cas, current) {
+            (Some(0), Some(_)) => return false,
+            (Some(cas), Some(current)) if cas != 0 && cas != current => return false,
+            (Some(cas), None) if cas != 0 => return false,
+            _ => {}
+        }
+        let create_index = agent.kv.get(&key).map_or(index, |entry| entry.create_index);
+        agent.kv.insert(
+            key.clone(),
+            KvEntry {
+                value: body.to_vec(),
+                flags,
+                create_index,
+                modify_index: index,
+            },
+        );
+        true
+    });
+    Json(written).into_response()
+}
+
+async fn kv_delete(
+    State(shared): State<Arc<Shared>>,
+    Path(key): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let cas: Option<u64> = params.get("cas").and_then(|cas| cas.parse().ok());
+    let recurse = flag(&params, "recurse");
+    let deleted = shared.update(|agent, _| {
+        if recurse {
+            agent.kv.retain(|k, _| !k.starts_with(&key));
+            return true;
+        }
+        if let Some(cas) = cas
+            && agent
+                .kv
+                .get(&key)
+                .is_some_and(|entry| entry.modify_index != cas)
+        {
+            return false;
+        }
+        agent.kv.remove(&key);
+        true
+    });
+    Json(deleted).into_response()
+}
+
+async fn status_leader() -> Json<&'static str> {
+    Json("127.0.0.1:8300")
+}
+
+fn router(shared: Arc<Shared>) -> Router {
+    Router::new()
+        .route("/v1/agent/service/register", put(agent_register))
+        .route("/v1/agent/service/deregister/{id}", put(agent_deregister))
+        .route("/v1/agent/services", get(agent_services))
+        .route("/v1/agent/checks", get(agent_checks))
+        .route("/v1/catalog/register", put(catalog_register))
+        .route("/v1/catalog/deregister", put(catalog_deregister))
+        .route("/v1/catalog/services", get(catalog_services))
+        .route("/v1/catalog/service/{name}", get(catalog_service))
+        .route("/v1/health/service/{name}", get(health_service))
+        .route("/v1/health/checks/{name}", get(health_checks))
+        .route("/v1/health/state/{state}", get(health_state))
+        .route("/v1/kv/{*key}", get(kv_get).put(kv_put).delete(kv_delete))
+        .route("/v1/status/leader", get(status_leader))
+        .with_state(shared)
+}
+
+/// A running stand-in Consul agent; it stops when dropped
+#[derive(Debug)]
+pub struct FakeConsul {
+    addr: SocketAddr,
+    server: JoinHandle<()>,
+}
+
+impl FakeConsul {
+    /// Starts an agent on an ephemeral port of 127.0.0.1.
+    pub async fn start() -> std::io::Result<Self> {
+        Self::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await
+    }
+
+    /// Starts an agent listening on `addr` (e.g. `127.0.0.1:8500` to replace a real one).
+    pub async fn bind(addr: SocketAddr) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        // Consul never reports index 0, which would make blocking queries return at once
+        let shared = Arc::new(Shared {
+            agent: Mutex::new(Agent {
+                index: 1,
+                ..Agent::default()
+            }),
+            changes: watch::Sender::new(1),
+            http_client: reqwest::Client::new(),
+        });
+        let app = router(shared);
+        let server = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                warn!("Fake Consul agent stopped: {}", e);
+            }
+        });
+        info!("Fake Consul agent listening on {}", addr);
+        Ok(Self { addr, server })
+    }
+
+    /// Address the agent listens on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Agent address as `CONSUL_HTTP_ADDR` holds it, e.g. `http://127.0.0.1:8500`
+    pub fn http_addr(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Base URL of the HTTP API, as `RegistryConfig::registry_url` holds it
+    pub fn api_url(&self) -> String {
+        format!("http://{}/v1/", self.addr)
+    }
+}
+
+impl Drop for FakeConsul {
+    fn drop(&mut self) {
+        // Dropping the router state also ends the check tasks
+        self.server.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::framework::registry::{
+        AgentServiceCheck, AgentServiceRegistration, ConsulClient, ServiceRegistry,
+    };
+
+    /// A `/health` endpoint answering `status`, on an ephemeral port
+    async fn health_server(status: StatusCode) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().route("/health", get(move || async move { status }));
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        addr
+    }
+
+    fn registration(
+        id: &str,
+        health: SocketAddr,
+        deregister_after: &str,
+    ) -> AgentServiceRegistration {
+        AgentServiceRegistration {
+            id: Some(id.to_string()),
+            name: "greeter".to_string(),
+            tags: Some(vec!["grpc".to_string()]),
+            address: Some("127.0.0.1".to_string()),
+            port: Some(50051),
+            meta: None,
+            check: Some(AgentServiceCheck {
+                check_id: Some(format!("{}-health", id)),
+                name: Some("HTTP Health Check".to_string()),
+                http: Some(format!("http://{}/health", health)),
+                tcp: None,
+                interval: Some("100ms".to_string()),
+                timeout: Some("1s".to_string()),
+                deregister_critical_service_after: Some(deregister_after.to_string()),
+            }),
+        }
+    }
+
+    async fn passing_instances(consul: &FakeConsul, name: &str) -> Vec<Value> {
+        let url = format!("{}health/service/{}?passing", consul.api_url(), name);
+        reqwest::get(url).await.unwrap().json().await.unwrap()
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("10s"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_duration("1m30s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5h"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("10"), None);
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("1e400s"), None);
+        assert_eq!(parse_duration("99999999999999999999h"), None);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_check_durations_are_rejected() {
+        let consul = FakeConsul::start().await.unwrap();
+        let client = ConsulClient::new(&consul.api_url()).unwrap();
+        let health = health_server(StatusCode::OK).await;
+
+        for (interval, deregister_after) in [("0s", "1m"), ("1e400s", "1m"), ("10s", "forever")] {
+            let mut bad = registration("greeter-bad", health, deregister_after);
+            bad.check.as_mut().unwrap().interval = Some(interval.to_string());
+            assert!(client.register_service(&bad).await.is_err());
+        }
+        // 代理没有崩溃，仍然接受合法的注册
+        client
+            .register_service(&registration("greeter-ok", health, "1m"))
+            .await
+            .unwrap();
+        assert_eq!(client.discover_service("greeter").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_register_discover_deregister() {
+        let consul = FakeConsul::start().await.unwrap();
+        let client = ConsulClient::new(&consul.api_url()).unwrap();
+        let health = health_server(StatusCode::OK).await;
+
+        client
+            .register_service(&registration("greeter-1", health, "1m"))
+            .await
+            .unwrap();
+        let nodes = client.discover_service("greeter").await.unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].service_id, "greeter-1");
+        assert_eq!(nodes[0].service_port, 50051);
+
+        // The health check really runs against /health
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while passing_instances(&consul, "greeter").await.is_empty() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        client.deregister_service("greeter-1").await.unwrap();
+        assert!(client.discover_service("greeter").await.unwrap().is_empty());
+        assert!(client.deregister_service("greeter-1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_critical_service_is_deregistered() {
+        let consul = FakeConsul::start().await.unwrap();
+        let client = ConsulClient::new(&consul.api_url()).unwrap();
+        let health = health_server(StatusCode::SERVICE_UNAVAILABLE).await;
+
+        client
+            .register_service(&registration("greeter-2", health, "300ms"))
+            .await
+            .unwrap();
+        assert!(passing_instances(&consul, "greeter").await.is_empty());
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !client.discover_service("greeter").await.unwrap().is_empty() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watch_uses_blocking_queries() {
+        let consul = FakeConsul::start().await.unwrap();
+        let client = ConsulClient::new(&consul.api_url()).unwrap();
+        let health = health_server(StatusCode::OK).await;
+
+        let mut instances = client.watch_service("greeter").await.unwrap();
+        assert!(instances.borrow_and_update().is_empty());
+        client
+            .register_service(&registration("greeter-3", health, "1m"))
+            .await
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(5), instances.wait_for(|n| n.len() == 1))
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_kv() {
+        let consul = FakeConsul::start().await.unwrap();
+        let http = reqwest::Client::new();
+        let url = format!("{}kv/app/config/enabled", consul.api_url());
+
+        let created: bool = http
+            .put(&url)
+            .body("true")
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(created);
+        let raw = http.get(format!("{}?raw", url)).send().await.unwrap();
+        assert_eq!(raw.text().await.unwrap(), "true");
+
+        let entries: Vec<Value> = http.get(&url).send().await.unwrap().json().await.unwrap();
+        assert_eq!(entries[0]["Value"], "dHJ1ZQ==");
+        // cas=0 only creates
+        let replaced: bool = http
+            .put(format!("{}?cas=0", url))
+            .body("false")
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(!replaced);
+
+        let keys: Vec<String> = http
+            .get(format!("{}kv/app?keys", consul.api_url()))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(keys, ["app/config/enabled"]);
+
+        http.delete(&url).send().await.unwrap();
+        assert_eq!(
+            http.get(&url).send().await.unwrap().status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
+use std::path::PathBuf;
 
 /// Configuration for the Consul service registry
 /// This struct defines the necessary parameters to connect to a Consul instance
@@ -28,12 +29,32 @@ impl Default for RegistryConfig {
     }
 }
 
+/// Which service registry the framework registers services with
+/// Consul is the default; the local backends need no external process
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "Kind", rename_all = "PascalCase")]
+pub enum RegistryBackend {
+    // The Consul agent at `RegistryConfig::registry_url`
+    #[default]
+    Consul,
+    // A registry living in the process, e.g. for unit tests
+    Memory,
+    // A JSON file shared by the services of a machine, for local development
+    File {
+        #[serde(rename = "Path")]
+        path: PathBuf,
+    },
+}
+
 // Base configuration common to all services managed by the framework
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BaseServiceConfig {
     pub service_id_prefix: String,
     pub service_name: String,
     pub consul: RegistryConfig,
+    // Registry backend; `consul` is only used by the Consul backend
+    #[serde(default)]
+    pub registry: RegistryBackend,
     // Add other common config here, e.g., logging levels, metrics endpoints
 }
 
@@ -43,6 +64,7 @@ impl Default for BaseServiceConfig {
             service_id_prefix: "default-service".to_string(),
             service_name: "default-app-service".to_string(),
             consul: RegistryConfig::default(),
+            registry: RegistryBackend::default(),
         }
     }
 }
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use super::signals::Signal;
+
 #[derive(Debug, Error)]
 pub enum FrameworkError {
     #[error("Service initialization failed: {0}")]
@@ -8,8 +10,14 @@ pub enum FrameworkError {
     Startup(String),
     #[error("Service shutdown failed: {0}")]
     Shutdown(String),
+    #[error("Service not ready within {0:?}")]
+    NotReady(std::time::Duration),
+    #[error("Shutdown forced by a second {0}")]
+    ForcedShutdown(Signal),
     #[error("Consul interaction failed: {0}")]
     Consul(String),
+    #[error("Service registry interaction failed: {0}")]
+    Registry(String),
     #[error("Configuration error: {0}")]
     Config(String),
     #[error("Internal framework error: {0}")]
@@ -0,0 +1,836 @@
+//! Hosts several `RunnableService`s in one process.
+//!
+//! `ApplicationHost` starts the services in the order given by their declared
+//! dependencies, waiting for each one to be ready before starting its dependents,
+//! restarts crashed services according to their `RestartPolicy`, and stops them
+//! in reverse order.
+
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{oneshot, watch};
+use tokio::task::JoinHandle;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+use super::{
+    config::ServiceConfig as TraitServiceConfig,
+    error::FrameworkError,
+    lifecycle::{ReadyHandle, RunnableService, ServiceStatus, StatusHandle},
+    registry::{self, ServiceRegistry},
+};
+
+/// What the host does when a service's logic returns on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Leave the service `Stopped` or `Failed`.
+    Never,
+    /// Restart the service after `delay`, whether it failed or returned `Ok`.
+    Always { delay: Duration },
+    /// Restart a failed service, doubling the wait from `initial_backoff` up to
+    /// `max_backoff`, at most `max_restarts` times in a row. A run lasting at least
+    /// `reset_after` counts as healthy and resets the backoff and the budget.
+    OnFailure {
+        initial_backoff: Duration,
+        max_backoff: Duration,
+        max_restarts: u32,
+        reset_after: Duration,
+    },
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        RestartPolicy::OnFailure {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_restarts: 5,
+            reset_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// The wait before restart number `restarts + 1`, or `None` if the service stays down.
+    fn next_restart(&self, restarts: u32, failed: bool) -> Option<Duration> {
+        match self {
+            RestartPolicy::Never => None,
+            RestartPolicy::Always { delay } => Some(*delay),
+            RestartPolicy::OnFailure {
+                initial_backoff,
+                max_backoff,
+                max_restarts,
+                ..
+            } => {
+                if !failed || restarts >= *max_restarts {
+                    return None;
+                }
+                let backoff = initial_backoff.saturating_mul(2u32.saturating_pow(restarts));
+                Some(backoff.min(*max_backoff))
+            }
+        }
+    }
+
+    /// Whether a run of `duration` was healthy enough to start counting restarts from zero.
+    fn resets_after(&self, duration: Duration) -> bool {
+        match self {
+            RestartPolicy::OnFailure { reset_after, .. } => duration >= *reset_after,
+            _ => false,
+        }
+    }
+}
+
+/// How the host runs one service.
+#[derive(Debug, Clone)]
+pub struct ServiceOptions {
+    /// Unique name of the service within the host
+    pub name: String,
+    /// Names of the services that must be ready before this one starts
+    pub depends_on: Vec<String>,
+    pub restart: RestartPolicy,
+    /// How long `start` waits for the service to report itself ready
+    pub ready_timeout: Duration,
+}
+
+impl ServiceOptions {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            depends_on: Vec::new(),
+            restart: RestartPolicy::default(),
+            ready_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// The status of one hosted service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceReport {
+    pub name: String,
+    pub instance_id: String,
+    pub status: ServiceStatus,
+    /// Restarts since the host started the service, including those before a reset
+    pub restarts: u32,
+}
+
+/// The status of the whole host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostStatus {
+    /// `Failed` if any service failed, else `Stopping` or `Starting` if any service is,
+    /// `Stopped` once all stopped, and `Running` otherwise
+    pub overall: ServiceStatus,
+    /// One report per service, in start order
+    pub services: Vec<ServiceReport>,
+}
+
+/// Runs one incarnation of a service until it returns.
+type Runner = Arc<
+    dyn Fn(
+            StatusHandle,
+            oneshot::Receiver<()>,
+            ReadyHandle,
+        ) -> BoxFuture<'static, Result<(), FrameworkError>>
+        + Send
+        + Sync,
+>;
+
+struct HostedService {
+    options: ServiceOptions,
+    instance_id: String,
+    status: StatusHandle,
+    restarts: Arc<AtomicU32>,
+    runner: Runner,
+    stop_tx: Option<watch::Sender<bool>>,
+    supervisor: Option<JoinHandle<()>>,
+}
+
+/// Supervises several services of possibly different types.
+#[derive(Default)]
+pub struct ApplicationHost {
+    /// Services in the order they were added
+    services: Vec<HostedService>,
+    /// Indexes into `services`, in start order; set by `start`
+    start_order: Vec<usize>,
+}
+
+impl ApplicationHost {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a service whose registry is created from its configuration.
+    pub fn add_service<S: RunnableService>(
+        &mut self,
+        config: S::Config,
+        options: ServiceOptions,
+    ) -> Result<&mut Self, FrameworkError> {
+        let registry = registry::from_config(config.base_config())
+            .map_err(|e| FrameworkError::Config(e.to_string()))?;
+        self.add_service_with_registry::<S>(config, options, registry)
+    }
+
+    /// Adds a service that announces itself in `registry`.
+    pub fn add_service_with_registry<S: RunnableService>(
+        &mut self,
+        config: S::Config,
+        options: ServiceOptions,
+        registry: Arc<dyn ServiceRegistry>,
+    ) -> Result<&mut Self, FrameworkError> {
+        if self.services.iter().any(|s| s.options.name == options.name) {
+            return Err(FrameworkError::Config(format!(
+                "Service '{}' is added twice",
+                options.name
+            )));
+        }
+        let instance_id = format!(
+            "{}-{}",
+            config.base_config().service_id_prefix,
+            Uuid::new_v4()
+        );
+
+        // Every (re)start builds a fresh service with the same instance ID
+        let id = instance_id.clone();
+        let runner: Runner = Arc::new(move |status, shutdown_rx, ready| {
+            let service = S::new(config.clone(), id.clone(), status, Arc::clone(&registry));
+            Box::pin(async move { service.start_service_logic(shutdown_rx, ready).await })
+        });
+
+        self.services.push(HostedService {
+            options,
+            instance_id,
+            status: StatusHandle::new(ServiceStatus::Initializing),
+            restarts: Arc::new(AtomicU32::new(0)),
+            runner,
+            stop_tx: None,
+            supervisor: None,
+        });
+        Ok(self)
+    }
+
+    /// Orders the services so that each comes after its dependencies,
+    /// keeping the order they were added in where the dependencies allow.
+    fn resolve_order(&self) -> Result<Vec<usize>, FrameworkError> {
+        let index: HashMap<&str, usize> = self
+            .services
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.options.name.as_str(), i))
+            .collect();
+
+        let mut dependencies = Vec::with_capacity(self.services.len());
+        for service in &self.services {
+            let mut deps = Vec::new();
+            for dep in &service.options.depends_on {
+                let dep_index = index.get(dep.as_str()).ok_or_else(|| {
+                    FrameworkError::Config(format!(
+                        "Service '{}' depends on unknown service '{}'",
+                        service.options.name, dep
+                    ))
+                })?;
+                deps.push(*dep_index);
+            }
+            dependencies.push(deps);
+        }
+
+        let mut order = Vec::with_capacity(self.services.len());
+        let mut started = vec![false; self.services.len()];
+        while order.len() < self.services.len() {
+            let next = (0..self.services.len())
+                .find(|&i| !started[i] && dependencies[i].iter().all(|&d| started[d]))
+                .ok_or_else(|| {
+                    let cycle: Vec<&str> = (0..self.services.len())
+                        .filter(|&i| !started[i])
+                        .map(|i| self.services[i].options.name.as_str())
+                        .collect();
+                    FrameworkError::Config(format!(
+                        "Dependency cycle between services: {}",
+                        cycle.join(", ")
+                    ))
+                })?;
+            started[next] = true;
+            order.push(next);
+        }
+        Ok(order)
+    }
+
+    /// Starts every service in dependency order; each must be ready within its
+    /// `ready_timeout` before its dependents start. If one does not become ready,
+    /// the services already started are stopped again and its error is returned.
+    #[instrument(name = "host_start", skip(self))]
+    pub async fn start(&mut self) -> Result<(), FrameworkError> {
+        if !self.start_order.is_empty() {
+            return Err(FrameworkError::Startup(
+                "Application host is already started".to_string(),
+            ));
+        }
+        let order = self.resolve_order()?;
+
+        for (position, &i) in order.iter().enumerate() {
+            self.start_order.push(i);
+            let service = &mut self.services[i];
+            info!(
+                "Starting service '{}' ({}).",
+                service.options.name, service.instance_id
+            );
+            let (stop_tx, stop_rx) = watch::channel(false);
+            let mut supervisor = tokio::spawn(supervise(
+                service.options.clone(),
+                service.status.clone(),
+                Arc::clone(&service.restarts),
+                Arc::clone(&service.runner),
+                stop_rx,
+            ));
+
+            let ready = wait_running(
+                &service.status,
+                &mut supervisor,
+                service.options.ready_timeout,
+            )
+            .await;
+            service.stop_tx = Some(stop_tx);
+            // A finished supervisor has nothing left to stop and must not be awaited again
+            service.supervisor = (!supervisor.is_finished()).then_some(supervisor);
+
+            if let Err(e) = ready {
+                error!(
+                    "Service '{}' did not become ready ({}/{}): {}",
+                    service.options.name,
+                    position + 1,
+                    order.len(),
+                    e
+                );
+                self.stop().await;
+                return Err(e);
+            }
+        }
+        info!("All {} services are running.", order.len());
+        Ok(())
+    }
+
+    /// Stops the services in reverse start order, waiting for each one to finish.
+    #[instrument(name = "host_stop", skip(self))]
+    pub async fn stop(&mut self) {
+        for i in std::mem::take(&mut self.start_order).into_iter().rev() {
+            let service = &mut self.services[i];
+            if let Some(stop_tx) = service.stop_tx.take() {
+                info!("Stopping service '{}'.", service.options.name);
+                let _ = stop_tx.send(true);
+            }
+            if let Some(supervisor) = service.supervisor.take()
+                && let Err(e) = supervisor.await
+            {
+                error!(
+                    "Supervisor of service '{}' panicked: {}",
+                    service.options.name, e
+                );
+                service.status.set(ServiceStatus::Failed(e.to_string()));
+            }
+        }
+    }
+
+    /// Subscribes to the status transitions of the service called `name`.
+    pub fn subscribe_status(&self, name: &str) -> Option<watch::Receiver<ServiceStatus>> {
+        self.services
+            .iter()
+            .find(|s| s.options.name == name)
+            .map(|s| s.status.subscribe())
+    }
+
+    /// Reports the status of every service and of the host as a whole.
+    pub fn status(&self) -> HostStatus {
+        let order = if self.start_order.len() == self.services.len() {
+            self.start_order.clone()
+        } else {
+            self.resolve_order()
+                .unwrap_or_else(|_| (0..self.services.len()).collect())
+        };
+        let services: Vec<ServiceReport> = order
+            .into_iter()
+            .map(|i| {
+                let service = &self.services[i];
+                ServiceReport {
+                    name: service.options.name.clone(),
+                    instance_id: service.instance_id.clone(),
+                    status: service.status.get(),
+                    restarts: service.restarts.load(Ordering::Relaxed),
+                }
+            })
+            .collect();
+
+        let failed: Vec<&str> = services
+            .iter()
+            .filter(|s| matches!(s.status, ServiceStatus::Failed(_)))
+            .map(|s| s.name.as_str())
+            .collect();
+        let any =
+            |statuses: &[ServiceStatus]| services.iter().any(|s| statuses.contains(&s.status));
+        let overall = if !failed.is_empty() {
+            ServiceStatus::Failed(format!("Failed services: {}", failed.join(", ")))
+        } else if any(&[ServiceStatus::Stopping]) {
+            ServiceStatus::Stopping
+        } else if any(&[ServiceStatus::Initializing, ServiceStatus::Starting]) {
+            ServiceStatus::Starting
+        } else if services.iter().all(|s| s.status == ServiceStatus::Stopped) {
+            ServiceStatus::Stopped
+        } else {
+            // Services with `RestartPolicy::Never` may have returned while the others run
+            ServiceStatus::Running
+        };
+        HostStatus { overall, services }
+    }
+}
+
+/// Waits until the service is `Running`, or `Stopped` after returning `Ok` (a short job
+/// may finish before the `Running` status is observed). Fails if its supervisor gives up
+/// first or `timeout` elapses.
+async fn wait_running(
+    status: &StatusHandle,
+    supervisor: &mut JoinHandle<()>,
+    timeout: Duration,
+) -> Result<(), FrameworkError> {
+    let mut status_rx = status.subscribe();
+    let started = |s: &ServiceStatus| matches!(s, ServiceStatus::Running | ServiceStatus::Stopped);
+    tokio::select! {
+        biased;
+        started = status_rx.wait_for(started) => {
+            started.map(|_| ()).map_err(|e| FrameworkError::Internal(e.to_string()))
+        }
+        _ = supervisor => match status.get() {
+            ServiceStatus::Failed(reason) => Err(FrameworkError::Startup(reason)),
+            other => Err(FrameworkError::Startup(format!(
+                "Service is {:?} instead of ready",
+                other
+            ))),
+        },
+        _ = tokio::time::sleep(timeout) => Err(FrameworkError::NotReady(timeout)),
+    }
+}
+
+/// Resolves once the host asks the service to stop, or drops its sender.
+async fn stop_requested(stop_rx: &mut watch::Receiver<bool>) {
+    let _ = stop_rx.wait_for(|stop| *stop).await;
+}
+
+/// Runs a service, restarting it according to its policy, until the host asks it
+/// to stop or the policy gives up.
+async fn supervise(
+    options: ServiceOptions,
+    status: StatusHandle,
+    restarts: Arc<AtomicU32>,
+    runner: Runner,
+    mut stop_rx: watch::Receiver<bool>,
+) {
+    // Restarts since the last healthy run, which the policy's backoff and budget apply to
+    let mut attempt = 0;
+    loop {
+        status.set(ServiceStatus::Starting);
+        let started = Instant::now();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        // A task of its own, so a panicking service shows up as a failure
+        let mut run = tokio::spawn(runner(
+            status.clone(),
+            shutdown_rx,
+            ReadyHandle::new(status.clone()),
+        ));
+
+        let result = tokio::select! {
+            result = &mut run => result,
+            () = stop_requested(&mut stop_rx) => {
+                status.set(ServiceStatus::Stopping);
+                let _ = shutdown_tx.send(());
+                match run.await {
+                    Ok(Ok(())) => status.set(ServiceStatus::Stopped),
+                    Ok(Err(e)) => status.set(ServiceStatus::Failed(e.to_string())),
+                    Err(e) => status.set(ServiceStatus::Failed(format!("Service panicked: {}", e))),
+                }
+                info!("Service '{}' stopped: {:?}", options.name, status.get());
+                return;
+            }
+        };
+
+        let failed = match result {
+            Ok(Ok(())) => {
+                status.set(ServiceStatus::Stopped);
+                info!("Service '{}' returned on its own.", options.name);
+                false
+            }
+            Ok(Err(e)) => {
+                status.set(ServiceStatus::Failed(e.to_string()));
+                error!("Service '{}' failed: {}", options.name, e);
+                true
+            }
+            Err(e) => {
+                status.set(ServiceStatus::Failed(format!("Service panicked: {}", e)));
+                error!("Service '{}' panicked: {}", options.name, e);
+                true
+            }
+        };
+
+        if options.restart.resets_after(started.elapsed()) {
+            attempt = 0;
+        }
+        let Some(delay) = options.restart.next_restart(attempt, failed) else {
+            if failed {
+                warn!(
+                    "Service '{}' is not restarted after {} restarts in a row.",
+                    options.name, attempt
+                );
+            }
+            return;
+        };
+        info!(
+            "Restarting service '{}' in {:?} (restart {} in a row).",
+            options.name,
+            delay,
+            attempt + 1
+        );
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            () = stop_requested(&mut stop_rx) => {
+                if !failed {
+                    status.set(ServiceStatus::Stopped);
+                }
+                return;
+            }
+        }
+        attempt += 1;
+        restarts.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::framework::{config::BaseServiceConfig, local_registry::InMemoryRegistry};
+    use std::sync::Mutex;
+
+    /// What the test service does in `start_service_logic`
+    #[derive(Debug, Clone, Copy)]
+    enum Behavior {
+        /// Fails this many attempts before becoming ready
+        FailFirst(u32),
+        /// Becomes ready, then returns at once
+        Exit,
+        /// Becomes ready, then fails after running this long
+        FailAfter(Duration),
+    }
+
+    #[derive(Debug, Clone)]
+    struct TestConfig {
+        base_config: BaseServiceConfig,
+        name: &'static str,
+        behavior: Behavior,
+        attempts: Arc<AtomicU32>,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl TraitServiceConfig for TestConfig {
+        fn base_config(&self) -> &BaseServiceConfig {
+            &self.base_config
+        }
+    }
+
+    #[derive(Debug)]
+    struct TestService {
+        config: TestConfig,
+        instance_id: String,
+        status: StatusHandle,
+    }
+
+    #[async_trait::async_trait]
+    impl RunnableService for TestService {
+        type Config = TestConfig;
+
+        fn new(
+            config: Self::Config,
+            instance_id: String,
+            status: StatusHandle,
+            _registry: Arc<dyn ServiceRegistry>,
+        ) -> Self {
+            Self {
+                config,
+                instance_id,
+                status,
+            }
+        }
+
+        fn instance_id(&self) -> &str {
+            &self.instance_id
+        }
+
+        fn get_status(&self) -> StatusHandle {
+            self.status.clone()
+        }
+
+        async fn start_service_logic(
+            &self,
+            shutdown_rx: oneshot::Receiver<()>,
+            ready: ReadyHandle,
+        ) -> Result<(), FrameworkError> {
+            let attempt = self.config.attempts.fetch_add(1, Ordering::Relaxed);
+            match self.config.behavior {
+                Behavior::FailFirst(n) if attempt < n => {
+                    return Err(FrameworkError::Startup(format!("attempt {}", attempt)));
+                }
+                Behavior::FailFirst(_) => {}
+                Behavior::Exit => {
+                    ready.ready();
+                    return Ok(());
+                }
+                Behavior::FailAfter(uptime) => {
+                    ready.ready();
+                    tokio::time::sleep(uptime).await;
+                    return Err(FrameworkError::Startup(format!("attempt {}", attempt)));
+                }
+            }
+            let events = &self.config.events;
+            events
+                .lock()
+                .unwrap()
+                .push(format!("start {}", self.config.name));
+            ready.ready();
+            let _ = shutdown_rx.await;
+            events
+                .lock()
+                .unwrap()
+                .push(format!("stop {}", self.config.name));
+            Ok(())
+        }
+    }
+
+    struct Fixture {
+        host: ApplicationHost,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self {
+                host: ApplicationHost::new(),
+                events: Arc::default(),
+            }
+        }
+
+        /// Adds a service and returns its attempt counter.
+        fn add(
+            &mut self,
+            name: &'static str,
+            depends_on: &[&str],
+            behavior: Behavior,
+            restart: RestartPolicy,
+        ) -> Arc<AtomicU32> {
+            let attempts = Arc::new(AtomicU32::new(0));
+            let config = TestConfig {
+                base_config: BaseServiceConfig::default(),
+                name,
+                behavior,
+                attempts: Arc::clone(&attempts),
+                events: Arc::clone(&self.events),
+            };
+            let options = ServiceOptions {
+                depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+                restart,
+                ready_timeout: Duration::from_secs(5),
+                ..ServiceOptions::new(name)
+            };
+            self.host
+                .add_service_with_registry::<TestService>(
+                    config,
+                    options,
+                    Arc::new(InMemoryRegistry::new()),
+                )
+                .unwrap();
+            attempts
+        }
+
+        fn events(&self) -> Vec<String> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    fn backoff(max_restarts: u32) -> RestartPolicy {
+        RestartPolicy::OnFailure {
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(20),
+            max_restarts,
+            reset_after: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RestartPolicy::OnFailure {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+            max_restarts: 3,
+            reset_after: Duration::from_secs(1),
+        };
+        assert_eq!(
+            policy.next_restart(0, true),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            policy.next_restart(1, true),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            policy.next_restart(2, true),
+            Some(Duration::from_millis(300))
+        );
+        assert_eq!(policy.next_restart(3, true), None);
+        assert_eq!(policy.next_restart(0, false), None);
+        assert_eq!(RestartPolicy::Never.next_restart(0, true), None);
+        assert!(policy.resets_after(Duration::from_secs(1)));
+        assert!(!policy.resets_after(Duration::from_millis(999)));
+        assert!(!RestartPolicy::Never.resets_after(Duration::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_dependency_order_and_reverse_stop() {
+        let mut fixture = Fixture::new();
+        fixture.add(
+            "worker",
+            &["api"],
+            Behavior::FailFirst(0),
+            RestartPolicy::Never,
+        );
+        fixture.add("api", &["db"], Behavior::FailFirst(0), RestartPolicy::Never);
+        fixture.add("db", &[], Behavior::FailFirst(0), RestartPolicy::Never);
+
+        fixture.host.start().await.unwrap();
+        let status = fixture.host.status();
+        assert_eq!(status.overall, ServiceStatus::Running);
+        let names: Vec<&str> = status.services.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["db", "api", "worker"]);
+
+        fixture.host.stop().await;
+        assert_eq!(fixture.host.status().overall, ServiceStatus::Stopped);
+        assert_eq!(
+            fixture.events(),
+            [
+                "start db",
+                "start api",
+                "start worker",
+                "stop worker",
+                "stop api",
+                "stop db"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_dependencies() {
+        let mut fixture = Fixture::new();
+        fixture.add(
+            "api",
+            &["cache"],
+            Behavior::FailFirst(0),
+            RestartPolicy::Never,
+        );
+        assert!(matches!(
+            fixture.host.start().await,
+            Err(FrameworkError::Config(_))
+        ));
+
+        let mut fixture = Fixture::new();
+        fixture.add("a", &["b"], Behavior::FailFirst(0), RestartPolicy::Never);
+        fixture.add("b", &["a"], Behavior::FailFirst(0), RestartPolicy::Never);
+        match fixture.host.start().await {
+            Err(FrameworkError::Config(reason)) => assert!(reason.contains("cycle")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(fixture.events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_on_failure_restarts_within_budget() {
+        let mut fixture = Fixture::new();
+        // 前两次失败，第三次启动成功
+        let attempts = fixture.add("flaky", &[], Behavior::FailFirst(2), backoff(3));
+        fixture.host.start().await.unwrap();
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+        assert_eq!(fixture.host.status().services[0].restarts, 2);
+        fixture.host.stop().await;
+
+        // 超出重启预算后启动失败，已启动的服务被停止
+        let mut fixture = Fixture::new();
+        fixture.add("db", &[], Behavior::FailFirst(0), RestartPolicy::Never);
+        let attempts = fixture.add("broken", &["db"], Behavior::FailFirst(u32::MAX), backoff(1));
+        assert!(matches!(
+            fixture.host.start().await,
+            Err(FrameworkError::Startup(_))
+        ));
+        assert_eq!(attempts.load(Ordering::Relaxed), 2);
+        let status = fixture.host.status();
+        assert!(matches!(status.overall, ServiceStatus::Failed(ref r) if r.contains("broken")));
+        assert_eq!(status.services[0].status, ServiceStatus::Stopped);
+        assert_eq!(fixture.events(), ["start db", "stop db"]);
+    }
+
+    #[tokio::test]
+    async fn test_always_restarts_returned_service() {
+        let mut fixture = Fixture::new();
+        let attempts = fixture.add(
+            "job",
+            &[],
+            Behavior::Exit,
+            RestartPolicy::Always {
+                delay: Duration::from_millis(10),
+            },
+        );
+        fixture.host.start().await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while attempts.load(Ordering::Relaxed) < 3 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        fixture.host.stop().await;
+        let status = fixture.host.status();
+        assert_eq!(status.overall, ServiceStatus::Stopped);
+        assert!(status.services[0].restarts >= 2);
+    }
+
+    #[tokio::test]
+    async fn test_healthy_run_resets_restart_budget() {
+        // 每次运行 50ms 后失败：超过 reset_after 的运行不消耗重启预算
+        let mut fixture = Fixture::new();
+        let uptime = Duration::from_millis(50);
+        let attempts = fixture.add(
+            "worker",
+            &[],
+            Behavior::FailAfter(uptime),
+            RestartPolicy::OnFailure {
+                initial_backoff: Duration::from_millis(10),
+                max_backoff: Duration::from_millis(20),
+                max_restarts: 1,
+                reset_after: Duration::from_millis(20),
+            },
+        );
+        fixture.host.start().await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while attempts.load(Ordering::Relaxed) < 4 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        fixture.host.stop().await;
+        assert!(fixture.host.status().services[0].restarts >= 3);
+
+        // 运行时间不足 reset_after 时，预算用完后不再重启
+        let mut fixture = Fixture::new();
+        let attempts = fixture.add("worker", &[], Behavior::FailAfter(uptime), backoff(1));
+        fixture.host.start().await.unwrap();
+        tokio::time::sleep(uptime * 6).await;
+        assert_eq!(attempts.load(Ordering::Relaxed), 2);
+        assert!(matches!(
+            fixture.host.status().services[0].status,
+            ServiceStatus::Failed(_)
+        ));
+        fixture.host.stop().await;
+    }
+}
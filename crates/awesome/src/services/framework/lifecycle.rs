@@ -1,14 +1,16 @@
 use async_trait::async_trait;
 use std::sync::Arc;
-use tokio::sync::{oneshot, RwLock};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, watch};
 use tokio::task::JoinHandle;
-use tracing::{error, info, instrument};
+use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
 
 use super::{
     config::{BaseServiceConfig, ServiceConfig as TraitServiceConfig},
     error::FrameworkError,
-    registry::{AgentServiceRegistration, ConsulClient}, // Import Consul structs
+    registry::{self, ServiceRegistry},
+    signals::{Signal, SignalListener},
 };
 
 /// Represents the current operational status of a service.
@@ -22,6 +24,61 @@ pub enum ServiceStatus {
     Failed(String), // Includes a reason for failure
 }
 
+/// The shared status of a service.
+/// Every change is published to the `watch` receivers returned by `subscribe`.
+#[derive(Debug, Clone)]
+pub struct StatusHandle {
+    sender: Arc<watch::Sender<ServiceStatus>>,
+}
+
+impl StatusHandle {
+    pub fn new(status: ServiceStatus) -> Self {
+        Self {
+            sender: Arc::new(watch::Sender::new(status)),
+        }
+    }
+
+    /// Returns the current status.
+    pub fn get(&self) -> ServiceStatus {
+        self.sender.borrow().clone()
+    }
+
+    /// Replaces the status and notifies the subscribers.
+    pub fn set(&self, status: ServiceStatus) {
+        self.sender.send_replace(status);
+    }
+
+    /// Subscribes to status transitions.
+    pub fn subscribe(&self) -> watch::Receiver<ServiceStatus> {
+        self.sender.subscribe()
+    }
+}
+
+/// Handed to `start_service_logic`; the service fires it once its listeners are bound
+/// and it is registered, which moves its status from `Starting` to `Running`.
+/// Dropping it without firing leaves the service `Starting`.
+#[derive(Debug)]
+pub struct ReadyHandle {
+    status: StatusHandle,
+}
+
+impl ReadyHandle {
+    pub(crate) fn new(status: StatusHandle) -> Self {
+        Self { status }
+    }
+
+    /// Reports the service ready to accept requests.
+    pub fn ready(self) {
+        self.status.sender.send_if_modified(|status| {
+            let starting = *status == ServiceStatus::Starting;
+            if starting {
+                *status = ServiceStatus::Running;
+            }
+            starting
+        });
+    }
+}
+
 /// The core trait for any runnable application service.
 /// Services implementing this trait can be managed by the ApplicationFramework.
 #[async_trait]
@@ -31,27 +88,49 @@ pub trait RunnableService: Send + Sync + 'static {
 
     /// Creates a new instance of the service.
     /// `instance_id` is a unique ID generated by the framework.
-    /// `status_arc` provides a shared reference to update the service's status.
+    /// `status` is the service's status, shared with the framework.
+    /// `registry` is the service registry the service announces itself in.
     fn new(
         config: Self::Config,
         instance_id: String,
-        status_arc: Arc<RwLock<ServiceStatus>>,
+        status: StatusHandle,
+        registry: Arc<dyn ServiceRegistry>,
     ) -> Self;
 
     /// Returns the unique instance ID of the service.
     fn instance_id(&self) -> &str;
 
-    /// Returns a clone of the shared status Arc.
-    fn get_status(&self) -> Arc<RwLock<ServiceStatus>>;
+    /// Returns a clone of the shared status handle.
+    fn get_status(&self) -> StatusHandle;
 
     /// The main async logic for the service.
     /// It should run indefinitely until `shutdown_rx` receives a signal or a fatal error occurs.
     /// This method is responsible for its own setup (e.g., gRPC server, HTTP server)
     /// and teardown (e.g., graceful shutdown of internal components).
+    /// It fires `ready` once the setup is done and requests can be served.
     async fn start_service_logic(
         &self,
         shutdown_rx: oneshot::Receiver<()>,
+        ready: ReadyHandle,
     ) -> Result<(), FrameworkError>;
+
+    /// Applies a new configuration to the running service, e.g. on SIGHUP.
+    /// Services that cannot change their configuration while running keep this default,
+    /// which refuses the reload.
+    async fn reload(&self, config: Self::Config) -> Result<(), FrameworkError> {
+        let _ = config;
+        Err(FrameworkError::Config(format!(
+            "Service '{}' does not support reloading its configuration",
+            self.instance_id()
+        )))
+    }
+}
+
+/// Resolves once the service has ended, by itself or after a stop.
+async fn service_ended(status: &mut watch::Receiver<ServiceStatus>) {
+    let _ = status
+        .wait_for(|status| matches!(status, ServiceStatus::Stopped | ServiceStatus::Failed(_)))
+        .await;
 }
 
 /// The main framework manager for an application service.
@@ -61,7 +140,7 @@ where
     S: RunnableService,
 {
     service_instance: Arc<S>,
-    status_receiver: Arc<RwLock<ServiceStatus>>,
+    status: StatusHandle,
     shutdown_tx: Option<oneshot::Sender<()>>,
     service_handle: Option<JoinHandle<Result<(), FrameworkError>>>,
 }
@@ -71,14 +150,27 @@ where
     S: RunnableService,
 {
     /// Creates and starts the application service within the framework.
-    #[instrument(name = "framework_start", skip(config), fields(service_name = config.base_config().service_name))]
+    /// The service registry is the one selected by the `registry` field of the base config.
     pub fn new(config: S::Config) -> Result<Self, FrameworkError> {
+        let registry = registry::from_config(config.base_config()).map_err(|e| {
+            FrameworkError::Config(format!("Failed to create service registry: {:#}", e))
+        })?;
+        Self::with_registry(config, registry)
+    }
+
+    /// Creates and starts the application service with the given service registry,
+    /// e.g. an `InMemoryRegistry` shared with the test that inspects it.
+    #[instrument(name = "framework_start", skip(config, registry), fields(service_name = config.base_config().service_name))]
+    pub fn with_registry(
+        config: S::Config,
+        registry: Arc<dyn ServiceRegistry>,
+    ) -> Result<Self, FrameworkError> {
         let instance_id = format!(
             "{}-{}",
             config.base_config().service_id_prefix,
             Uuid::new_v4()
         );
-        let status_arc = Arc::new(RwLock::new(ServiceStatus::Initializing));
+        let status = StatusHandle::new(ServiceStatus::Initializing);
         let (tx_shutdown, rx_shutdown) = oneshot::channel();
 
         info!(
@@ -87,45 +179,45 @@ where
             instance_id
         );
 
-        let service_instance = Arc::new(S::new(config, instance_id, Arc::clone(&status_arc)));
+        let service_instance = Arc::new(S::new(config, instance_id, status.clone(), registry));
         let service_for_task = Arc::clone(&service_instance); // Clone for the spawned task
 
         // Spawn the service's main logic as a background task
         let service_handle = tokio::spawn(async move {
             let instance_id_clone = service_for_task.instance_id().to_string();
-            let status_arc_clone = service_for_task.get_status();
+            let status = service_for_task.get_status();
 
             // Set status to Starting before calling start_service_logic
-            {
-                let mut status_guard = status_arc_clone.write().await;
-                *status_guard = ServiceStatus::Starting;
-                info!(
-                    "Service '{}' status: {:?}",
-                    instance_id_clone, *status_guard
-                );
-            }
+            status.set(ServiceStatus::Starting);
+            info!(
+                "Service '{}' status: {:?}",
+                instance_id_clone,
+                ServiceStatus::Starting
+            );
 
-            // Execute the service's core logic
-            let result = service_for_task.start_service_logic(rx_shutdown).await;
+            // Execute the service's core logic; it moves the status to Running when ready
+            let ready = ReadyHandle::new(status.clone());
+            let result = service_for_task
+                .start_service_logic(rx_shutdown, ready)
+                .await;
 
             // Update final status based on result
-            {
-                let mut status_guard = status_arc_clone.write().await;
-                match &result {
-                    Ok(_) => {
-                        *status_guard = ServiceStatus::Stopped;
-                        info!(
-                            "Service '{}' gracefully stopped. Final status: {:?}",
-                            instance_id_clone, *status_guard
-                        );
-                    }
-                    Err(e) => {
-                        *status_guard = ServiceStatus::Failed(e.to_string());
-                        error!(
-                            "Service '{}' stopped with error. Final status: {:?}",
-                            instance_id_clone, *status_guard
-                        );
-                    }
+            match &result {
+                Ok(_) => {
+                    status.set(ServiceStatus::Stopped);
+                    info!(
+                        "Service '{}' gracefully stopped. Final status: {:?}",
+                        instance_id_clone,
+                        ServiceStatus::Stopped
+                    );
+                }
+                Err(e) => {
+                    status.set(ServiceStatus::Failed(e.to_string()));
+                    error!(
+                        "Service '{}' stopped with error. Final status: {:?}",
+                        instance_id_clone,
+                        status.get()
+                    );
                 }
             }
             result
@@ -133,7 +225,7 @@ where
 
         Ok(Self {
             service_instance,
-            status_receiver: status_arc,
+            status,
             shutdown_tx: Some(tx_shutdown),
             service_handle: Some(service_handle),
         })
@@ -142,20 +234,64 @@ where
     /// Gets the current status of the managed service.
     #[instrument(name = "get_status", skip(self))]
     pub async fn get_status(&self) -> ServiceStatus {
-        let status_guard = self.status_receiver.read().await;
-        status_guard.clone()
+        self.status.get()
     }
 
-    /// Sends a shutdown signal to the service and waits for it to terminate.
-    #[instrument(name = "framework_stop", skip(self))]
-    pub async fn stop(&mut self) -> Result<(), FrameworkError> {
+    /// Subscribes to the status transitions of the managed service.
+    pub fn subscribe_status(&self) -> watch::Receiver<ServiceStatus> {
+        self.status.subscribe()
+    }
+
+    /// Waits until the service reports itself ready (status `Running`).
+    /// Fails if the service stops or fails first, or if `timeout` elapses.
+    #[instrument(name = "framework_wait_ready", skip(self))]
+    pub async fn wait_ready(&self, timeout: Duration) -> Result<(), FrameworkError> {
+        let mut status = self.status.subscribe();
+        let settled = tokio::time::timeout(
+            timeout,
+            status.wait_for(|status| {
+                !matches!(
+                    status,
+                    ServiceStatus::Initializing | ServiceStatus::Starting
+                )
+            }),
+        )
+        .await
+        .map_err(|_| FrameworkError::NotReady(timeout))?
+        .map_err(|e| FrameworkError::Internal(e.to_string()))?
+        .clone();
+
+        match settled {
+            ServiceStatus::Running => Ok(()),
+            ServiceStatus::Failed(reason) => Err(FrameworkError::Startup(reason)),
+            other => Err(FrameworkError::Startup(format!(
+                "Service is {:?} instead of ready",
+                other
+            ))),
+        }
+    }
+
+    /// Sends the shutdown signal to the service, once.
+    fn request_stop(&mut self) {
         info!(
             "Sending shutdown signal to service '{}'.",
             self.service_instance.instance_id()
         );
         if let Some(tx) = self.shutdown_tx.take() {
+            if matches!(
+                self.status.get(),
+                ServiceStatus::Starting | ServiceStatus::Running
+            ) {
+                self.status.set(ServiceStatus::Stopping);
+            }
             let _ = tx.send(()); // Ignore error if receiver is dropped
         }
+    }
+
+    /// Sends a shutdown signal to the service and waits for it to terminate.
+    #[instrument(name = "framework_stop", skip(self))]
+    pub async fn stop(&mut self) -> Result<(), FrameworkError> {
+        self.request_stop();
 
         if let Some(handle) = self.service_handle.take() {
             handle
@@ -166,6 +302,142 @@ where
         Ok(())
     }
 
+    /// Sends a shutdown signal to the service and gives it `drain_timeout` to finish
+    /// its in-flight work. Returns the error the service stopped with; if it is still
+    /// running after `drain_timeout`, its task is aborted and `FrameworkError::Shutdown`
+    /// is returned.
+    #[instrument(name = "framework_stop_with_timeout", skip(self))]
+    pub async fn stop_with_timeout(
+        &mut self,
+        drain_timeout: Duration,
+    ) -> Result<(), FrameworkError> {
+        self.request_stop();
+        let Some(mut handle) = self.service_handle.take() else {
+            return Ok(());
+        };
+        match tokio::time::timeout(drain_timeout, &mut handle).await {
+            Ok(joined) => joined?,
+            Err(_) => {
+                handle.abort();
+                let reason = format!("Service did not stop within {:?}", drain_timeout);
+                error!(
+                    "Service '{}' aborted: {}",
+                    self.service_instance.instance_id(),
+                    reason
+                );
+                self.status.set(ServiceStatus::Failed(reason.clone()));
+                Err(FrameworkError::Shutdown(reason))
+            }
+        }
+    }
+
+    /// Hands a new configuration to the running service through `RunnableService::reload`.
+    #[instrument(name = "framework_reload", skip(self, config))]
+    pub async fn reload(&self, config: S::Config) -> Result<(), FrameworkError> {
+        info!(
+            "Reloading configuration of service '{}'.",
+            self.service_instance.instance_id()
+        );
+        self.service_instance.reload(config).await
+    }
+
+    /// Runs the service until SIGINT or SIGTERM, then stops it gracefully within
+    /// `drain_timeout` (see `stop_with_timeout`). On SIGHUP the service is reloaded
+    /// with the configuration returned by `load_config`. A second SIGINT or SIGTERM
+    /// during the stop exits the process at once.
+    /// Also returns when the service ends by itself.
+    pub async fn run_until_signal<F>(
+        &mut self,
+        drain_timeout: Duration,
+        load_config: F,
+    ) -> Result<(), FrameworkError>
+    where
+        F: FnMut() -> Result<S::Config, FrameworkError>,
+    {
+        let signals = SignalListener::new()?.into_channel();
+        match self
+            .run_with_signals(signals, drain_timeout, load_config)
+            .await
+        {
+            Err(FrameworkError::ForcedShutdown(signal)) => {
+                error!("Received a second {}, exiting immediately.", signal);
+                std::process::exit(signal.exit_code());
+            }
+            result => result,
+        }
+    }
+
+    /// `run_until_signal` with the signals read from `signals`, for callers that handle
+    /// the OS signals themselves. A second shutdown signal aborts the service task and
+    /// returns `FrameworkError::ForcedShutdown` instead of exiting the process.
+    pub async fn run_with_signals<F>(
+        &mut self,
+        mut signals: mpsc::Receiver<Signal>,
+        drain_timeout: Duration,
+        mut load_config: F,
+    ) -> Result<(), FrameworkError>
+    where
+        F: FnMut() -> Result<S::Config, FrameworkError>,
+    {
+        let instance_id = self.service_instance.instance_id().to_string();
+        let mut status = self.status.subscribe();
+
+        // Serve, reloading on SIGHUP, until a shutdown signal
+        let signal = loop {
+            tokio::select! {
+                Some(signal) = signals.recv() => {
+                    if signal.is_shutdown() {
+                        break signal;
+                    }
+                    info!("Received {}, reloading service '{}'.", signal, instance_id);
+                    let reloaded = match load_config() {
+                        Ok(config) => self.reload(config).await,
+                        Err(e) => Err(e),
+                    };
+                    match reloaded {
+                        Ok(()) => info!("Service '{}' reloaded.", instance_id),
+                        Err(e) => warn!("Reloading service '{}' failed: {}", instance_id, e),
+                    }
+                }
+                () = service_ended(&mut status) => {
+                    info!("Service '{}' ended without a signal.", instance_id);
+                    return self.stop_with_timeout(drain_timeout).await;
+                }
+            }
+        };
+
+        info!(
+            "Received {}, stopping service '{}' (drain timeout {:?}).",
+            signal, instance_id, drain_timeout
+        );
+        let abort = self.service_handle.as_ref().map(|h| h.abort_handle());
+        let forced = {
+            let stop = self.stop_with_timeout(drain_timeout);
+            tokio::pin!(stop);
+            loop {
+                tokio::select! {
+                    result = &mut stop => return result,
+                    Some(second) = signals.recv() => {
+                        if second.is_shutdown() {
+                            break second;
+                        }
+                        info!("Ignoring {} while stopping service '{}'.", second, instance_id);
+                    }
+                }
+            }
+        };
+
+        // A second shutdown signal: stop waiting for the service to drain
+        if let Some(abort) = abort {
+            abort.abort();
+        }
+        self.status.set(ServiceStatus::Failed(format!(
+            "Shutdown forced by a second {}",
+            forced
+        )));
+        Err(FrameworkError::ForcedShutdown(forced))
+    }
+
     /// Provides access to the underlying `RunnableService` instance.
     pub fn service(&self) -> Arc<S> {
         Arc::clone(&self.service_instance)
@@ -178,3 +450,193 @@ where
 // Or, if used only by framework, they could live here.
 // For the sake of not having circular dependencies when defining consul.rs,
 // they are placed in consul.rs and imported here.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::framework::local_registry::InMemoryRegistry;
+    use std::sync::Mutex;
+
+    /// What the test service does in `start_service_logic`
+    #[derive(Debug, Clone, Copy)]
+    enum Behavior {
+        Ready,
+        Fail,
+        Hang,
+        /// Becomes ready, but takes 10 seconds to drain after the shutdown signal
+        SlowStop,
+    }
+
+    #[derive(Debug, Clone)]
+    struct TestConfig {
+        base_config: BaseServiceConfig,
+        behavior: Behavior,
+    }
+
+    impl TraitServiceConfig for TestConfig {
+        fn base_config(&self) -> &BaseServiceConfig {
+            &self.base_config
+        }
+    }
+
+    #[derive(Debug)]
+    struct TestService {
+        behavior: Behavior,
+        instance_id: String,
+        status: StatusHandle,
+        /// Service name of the last configuration passed to `reload`
+        reloaded: Mutex<Option<String>>,
+    }
+
+    #[async_trait]
+    impl RunnableService for TestService {
+        type Config = TestConfig;
+
+        fn new(
+            config: Self::Config,
+            instance_id: String,
+            status: StatusHandle,
+            _registry: Arc<dyn ServiceRegistry>,
+        ) -> Self {
+            Self {
+                behavior: config.behavior,
+                instance_id,
+                status,
+                reloaded: Mutex::new(None),
+            }
+        }
+
+        fn instance_id(&self) -> &str {
+            &self.instance_id
+        }
+
+        fn get_status(&self) -> StatusHandle {
+            self.status.clone()
+        }
+
+        async fn start_service_logic(
+            &self,
+            shutdown_rx: oneshot::Receiver<()>,
+            ready: ReadyHandle,
+        ) -> Result<(), FrameworkError> {
+            match self.behavior {
+                Behavior::Ready | Behavior::SlowStop => ready.ready(),
+                Behavior::Fail => return Err(FrameworkError::Startup("port taken".to_string())),
+                Behavior::Hang => {}
+            }
+            let _ = shutdown_rx.await;
+            if let Behavior::SlowStop = self.behavior {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+            Ok(())
+        }
+
+        async fn reload(&self, config: Self::Config) -> Result<(), FrameworkError> {
+            *self.reloaded.lock().unwrap() = Some(config.base_config.service_name);
+            Ok(())
+        }
+    }
+
+    fn config(service_name: &str, behavior: Behavior) -> TestConfig {
+        TestConfig {
+            base_config: BaseServiceConfig {
+                service_name: service_name.to_string(),
+                ..Default::default()
+            },
+            behavior,
+        }
+    }
+
+    fn start(behavior: Behavior) -> ApplicationFramework<TestService> {
+        let config = config("test-service", behavior);
+        ApplicationFramework::with_registry(config, Arc::new(InMemoryRegistry::new())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_status_transitions() {
+        let mut app = start(Behavior::Ready);
+        let mut status = app.subscribe_status();
+        app.wait_ready(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(*status.borrow_and_update(), ServiceStatus::Running);
+
+        app.stop().await.unwrap();
+        assert_eq!(app.get_status().await, ServiceStatus::Stopped);
+        // 订阅者收到最终状态
+        assert!(status.has_changed().unwrap());
+        assert_eq!(*status.borrow_and_update(), ServiceStatus::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_wait_ready_reports_failure_and_timeout() {
+        let app = start(Behavior::Fail);
+        match app.wait_ready(Duration::from_secs(5)).await {
+            Err(FrameworkError::Startup(reason)) => assert!(reason.contains("port taken")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let mut app = start(Behavior::Hang);
+        assert!(matches!(
+            app.wait_ready(Duration::from_millis(100)).await,
+            Err(FrameworkError::NotReady(_))
+        ));
+        assert_eq!(app.get_status().await, ServiceStatus::Starting);
+        app.stop().await.unwrap();
+        assert_eq!(app.get_status().await, ServiceStatus::Stopped);
+    }
+
+    /// Feeds `sent` to `run_with_signals`, keeping the channel open afterwards.
+    async fn run_with(
+        app: &mut ApplicationFramework<TestService>,
+        sent: &[Signal],
+        drain_timeout: Duration,
+    ) -> Result<(), FrameworkError> {
+        let (tx, rx) = mpsc::channel(8);
+        for signal in sent {
+            tx.send(*signal).await.unwrap();
+        }
+        let run = app.run_with_signals(rx, drain_timeout, || {
+            Ok(config("reloaded", Behavior::Ready))
+        });
+        tokio::time::timeout(Duration::from_secs(5), run)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_signals_reload_then_stop() {
+        let mut app = start(Behavior::Ready);
+        app.wait_ready(Duration::from_secs(5)).await.unwrap();
+
+        let sent = [Signal::Hangup, Signal::Terminate];
+        run_with(&mut app, &sent, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(
+            app.service().reloaded.lock().unwrap().as_deref(),
+            Some("reloaded")
+        );
+        assert_eq!(app.get_status().await, ServiceStatus::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_drain_timeout_and_second_signal() {
+        // 超过 drain timeout 的服务被中止
+        let mut app = start(Behavior::SlowStop);
+        app.wait_ready(Duration::from_secs(5)).await.unwrap();
+        match run_with(&mut app, &[Signal::Terminate], Duration::from_millis(50)).await {
+            Err(FrameworkError::Shutdown(reason)) => assert!(reason.contains("did not stop")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(matches!(app.get_status().await, ServiceStatus::Failed(_)));
+
+        // 第二个信号不再等待服务排空
+        let mut app = start(Behavior::SlowStop);
+        app.wait_ready(Duration::from_secs(5)).await.unwrap();
+        let sent = [Signal::Interrupt, Signal::Interrupt];
+        assert!(matches!(
+            run_with(&mut app, &sent, Duration::from_secs(30)).await,
+            Err(FrameworkError::ForcedShutdown(Signal::Interrupt))
+        ));
+        assert!(matches!(app.get_status().await, ServiceStatus::Failed(_)));
+    }
+}
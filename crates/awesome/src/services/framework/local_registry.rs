@@ -0,0 +1,334 @@
+//! Service registries that need no Consul agent.
+//!
+//! `InMemoryRegistry` lives inside the process, so a whole service stack can run in a
+//! unit test. `FileRegistry` keeps the catalog in a JSON file that every service of a
+//! machine shares during local development.
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Mutex};
+use tracing::{info, instrument, warn};
+
+use super::registry::{AgentServiceRegistration, CatalogServiceNode, ServiceRegistry};
+
+/// Node name reported for the instances of the local registries
+const LOCAL_NODE: &str = "local";
+
+/// How often `FileRegistry` re-reads its file while watching
+const FILE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Every registered instance, by service ID
+type Catalog = BTreeMap<String, CatalogServiceNode>;
+
+/// Turns a registration into the catalog entry Consul would report for it.
+fn catalog_node(registration: &AgentServiceRegistration) -> Result<CatalogServiceNode> {
+    let id = registration
+        .id
+        .clone()
+        .ok_or_else(|| anyhow!("Service registration must have a valid ID"))?;
+    let address = registration.address.clone().unwrap_or_default();
+    Ok(CatalogServiceNode {
+        node: LOCAL_NODE.to_string(),
+        address: if address.is_empty() {
+            "127.0.0.1".to_string()
+        } else {
+            address.clone()
+        },
+        service_id: id,
+        service_name: registration.name.clone(),
+        service_address: address,
+        service_port: registration.port.unwrap_or_default(),
+        service_tags: registration.tags.clone(),
+        service_meta: registration.meta.clone(),
+    })
+}
+
+/// The instances of `service_name` in `catalog`, ordered by service ID.
+fn instances(catalog: &Catalog, service_name: &str) -> Vec<CatalogServiceNode> {
+    catalog
+        .values()
+        .filter(|node| node.service_name == service_name)
+        .cloned()
+        .collect()
+}
+
+/// Stores `nodes` in `tx` if they differ from what it holds.
+fn publish(tx: &watch::Sender<Vec<CatalogServiceNode>>, nodes: Vec<CatalogServiceNode>) {
+    tx.send_if_modified(|current| {
+        let modified = *current != nodes;
+        *current = nodes;
+        modified
+    });
+}
+
+/// A registry held in memory; clones share the same catalog.
+#[derive(Debug, Clone)]
+pub struct InMemoryRegistry {
+    catalog: Arc<watch::Sender<Catalog>>,
+}
+
+impl InMemoryRegistry {
+    pub fn new() -> Self {
+        Self {
+            catalog: Arc::new(watch::Sender::new(Catalog::new())),
+        }
+    }
+}
+
+impl Default for InMemoryRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ServiceRegistry for InMemoryRegistry {
+    #[instrument(name = "memory_register", skip(self, registration))]
+    async fn register_service(&self, registration: &AgentServiceRegistration) -> Result<()> {
+        let node = catalog_node(registration)?;
+        info!("Registered service '{}' in memory.", node.service_id);
+        self.catalog.send_modify(|catalog| {
+            catalog.insert(node.service_id.clone(), node);
+        });
+        Ok(())
+    }
+
+    #[instrument(name = "memory_deregister", skip(self))]
+    async fn deregister_service(&self, service_id: &str) -> Result<()> {
+        if !self
+            .catalog
+            .send_if_modified(|catalog| catalog.remove(service_id).is_some())
+        {
+            return Err(anyhow!("Service '{}' is not registered", service_id));
+        }
+        info!("Deregistered service '{}' from memory.", service_id);
+        Ok(())
+    }
+
+    async fn discover_service(&self, service_name: &str) -> Result<Vec<CatalogServiceNode>> {
+        Ok(instances(&self.catalog.borrow(), service_name))
+    }
+
+    async fn watch_service(
+        &self,
+        service_name: &str,
+    ) -> Result<watch::Receiver<Vec<CatalogServiceNode>>> {
+        let mut catalog = self.catalog.subscribe();
+        let service_name = service_name.to_string();
+        let (tx, rx) = watch::channel(instances(&catalog.borrow_and_update(), &service_name));
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    changed = catalog.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                    () = tx.closed() => break,
+                }
+                let nodes = instances(&catalog.borrow_and_update(), &service_name);
+                publish(&tx, nodes);
+            }
+        });
+        Ok(rx)
+    }
+}
+
+/// A registry stored as a JSON object of catalog entries keyed by service ID.
+/// Writes within a process are serialized; concurrent writers in several processes
+/// may lose each other's updates, which is acceptable for local development.
+#[derive(Debug, Clone)]
+pub struct FileRegistry {
+    path: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+impl FileRegistry {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Path of the JSON file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the catalog; a missing file is an empty catalog.
+    fn load(path: &Path) -> Result<Catalog> {
+        match std::fs::read_to_string(path) {
+            Ok(content) if content.trim().is_empty() => Ok(Catalog::new()),
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Invalid registry file {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Catalog::new()),
+            Err(e) => {
+                Err(e).with_context(|| format!("Failed to read registry file {}", path.display()))
+            }
+        }
+    }
+
+    /// Writes the catalog through a temporary file, so readers never see half of it.
+    fn save(&self, catalog: &Catalog) -> Result<()> {
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(catalog)?)?;
+        std::fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to write registry file {}", self.path.display()))
+    }
+}
+
+#[async_trait]
+impl ServiceRegistry for FileRegistry {
+    #[instrument(name = "file_register", skip(self, registration))]
+    async fn register_service(&self, registration: &AgentServiceRegistration) -> Result<()> {
+        let node = catalog_node(registration)?;
+        let _guard = self.lock.lock().await;
+        let mut catalog = Self::load(&self.path)?;
+        info!(
+            "Registered service '{}' in {}.",
+            node.service_id,
+            self.path.display()
+        );
+        catalog.insert(node.service_id.clone(), node);
+        self.save(&catalog)
+    }
+
+    #[instrument(name = "file_deregister", skip(self))]
+    async fn deregister_service(&self, service_id: &str) -> Result<()> {
+        let _guard = self.lock.lock().await;
+        let mut catalog = Self::load(&self.path)?;
+        if catalog.remove(service_id).is_none() {
+            return Err(anyhow!("Service '{}' is not registered", service_id));
+        }
+        info!(
+            "Deregistered service '{}' from {}.",
+            service_id,
+            self.path.display()
+        );
+        self.save(&catalog)
+    }
+
+    async fn discover_service(&self, service_name: &str) -> Result<Vec<CatalogServiceNode>> {
+        Ok(instances(&Self::load(&self.path)?, service_name))
+    }
+
+    async fn watch_service(
+        &self,
+        service_name: &str,
+    ) -> Result<watch::Receiver<Vec<CatalogServiceNode>>> {
+        let (tx, rx) = watch::channel(self.discover_service(service_name).await?);
+        let path = self.path.clone();
+        let service_name = service_name.to_string();
+
+        // Other processes may change the file at any time: poll it
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    () = tokio::time::sleep(FILE_POLL_INTERVAL) => {}
+                    () = tx.closed() => break,
+                }
+                match Self::load(&path) {
+                    Ok(catalog) => publish(&tx, instances(&catalog, &service_name)),
+                    Err(e) => warn!("Watching service '{}' failed: {:#}", service_name, e),
+                }
+            }
+        });
+        Ok(rx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registration(id: &str, name: &str, port: u16) -> AgentServiceRegistration {
+        AgentServiceRegistration {
+            id: Some(id.to_string()),
+            name: name.to_string(),
+            tags: None,
+            address: Some("127.0.0.1".to_string()),
+            port: Some(port),
+            meta: None,
+            check: None,
+        }
+    }
+
+    async fn register_discover_deregister(registry: &dyn ServiceRegistry) {
+        registry
+            .register_service(&registration("greeter-1", "greeter", 50051))
+            .await
+            .unwrap();
+        registry
+            .register_service(&registration("store-1", "store", 50052))
+            .await
+            .unwrap();
+
+        let nodes = registry.discover_service("greeter").await.unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].service_id, "greeter-1");
+        assert_eq!(nodes[0].service_port, 50051);
+
+        registry.deregister_service("greeter-1").await.unwrap();
+        assert!(registry
+            .discover_service("greeter")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(registry.deregister_service("greeter-1").await.is_err());
+    }
+
+    async fn watch_follows_changes(registry: &dyn ServiceRegistry) {
+        let mut rx = registry.watch_service("greeter").await.unwrap();
+        assert!(rx.borrow_and_update().is_empty());
+
+        registry
+            .register_service(&registration("greeter-2", "greeter", 50053))
+            .await
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(5), rx.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rx.borrow_and_update()[0].service_id, "greeter-2");
+
+        registry.deregister_service("greeter-2").await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), rx.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(rx.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_registry() {
+        let registry = InMemoryRegistry::new();
+        register_discover_deregister(&registry).await;
+        watch_follows_changes(&registry.clone()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_registry() {
+        let dir = std::env::temp_dir().join(format!("file-registry-{}", uuid::Uuid::new_v4()));
+        let registry = FileRegistry::new(dir.join("registry.json"));
+        register_discover_deregister(&registry).await;
+        watch_follows_changes(&registry).await;
+
+        // Another process sees the same catalog through the file
+        registry
+            .register_service(&registration("greeter-3", "greeter", 50054))
+            .await
+            .unwrap();
+        let other = FileRegistry::new(registry.path());
+        assert_eq!(other.discover_service("greeter").await.unwrap().len(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
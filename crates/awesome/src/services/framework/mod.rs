@@ -1,4 +1,7 @@
 pub mod config;
 pub mod error;
+pub mod host;
 pub mod lifecycle;
+pub mod local_registry;
 pub mod registry;
+pub mod signals;
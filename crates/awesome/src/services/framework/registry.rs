@@ -1,10 +1,18 @@
 use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
 use tracing::{error, info, instrument, warn};
 use url::Url;
+
+use super::config::{BaseServiceConfig, RegistryBackend};
+use super::local_registry::{FileRegistry, InMemoryRegistry};
 // --- Consul API Structs (these are the actual data structures matching Consul's JSON API) ---
 
 #[derive(Serialize, Debug, Clone)]
@@ -45,7 +53,7 @@ pub struct AgentServiceCheck {
     // Add other check types as needed (e.g., "TTL", "Script", "GRPC")
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "PascalCase")]
 pub struct CatalogServiceNode {
     pub node: String,
@@ -61,6 +69,43 @@ pub struct CatalogServiceNode {
     pub service_meta: Option<HashMap<String, String>>,
 }
 
+/// A service registry: where services announce themselves and find each other.
+/// `ConsulClient` talks to a Consul agent; `InMemoryRegistry` and `FileRegistry`
+/// (see `local_registry`) need no external process.
+#[async_trait]
+pub trait ServiceRegistry: Debug + Send + Sync {
+    /// Registers (or replaces) the service instance `registration.id`.
+    async fn register_service(&self, registration: &AgentServiceRegistration) -> Result<()>;
+
+    /// Removes the service instance `service_id`.
+    async fn deregister_service(&self, service_id: &str) -> Result<()>;
+
+    /// Lists the registered instances of `service_name`.
+    async fn discover_service(&self, service_name: &str) -> Result<Vec<CatalogServiceNode>>;
+
+    /// Follows the instances of `service_name`.
+    /// The receiver holds the current instances and is updated whenever they change;
+    /// watching stops once every receiver is dropped.
+    async fn watch_service(
+        &self,
+        service_name: &str,
+    ) -> Result<watch::Receiver<Vec<CatalogServiceNode>>>;
+}
+
+/// Creates the registry selected by `config.registry`.
+pub fn from_config(config: &BaseServiceConfig) -> Result<Arc<dyn ServiceRegistry>> {
+    Ok(match &config.registry {
+        RegistryBackend::Consul => Arc::new(ConsulClient::new(&config.consul.registry_url)?),
+        RegistryBackend::Memory => Arc::new(InMemoryRegistry::new()),
+        RegistryBackend::File { path } => Arc::new(FileRegistry::new(path)),
+    })
+}
+
+/// How long a Consul blocking query waits for a change
+const CONSUL_WATCH_WAIT: &str = "30s";
+/// Pause before the next blocking query after an error or an unchanged index
+const CONSUL_WATCH_RETRY: Duration = Duration::from_secs(1);
+
 /// A client for interacting with the Consul Agent and Catalog APIs.
 #[derive(Debug, Clone)]
 pub struct ConsulClient {
@@ -77,8 +122,52 @@ impl ConsulClient {
         })
     }
 
+    /// Queries the catalog for `service_name`, returning the Consul index with the nodes.
+    /// With `index`, this is a blocking query: Consul answers once the index moves past it
+    /// or after `CONSUL_WATCH_WAIT`.
+    async fn query_catalog(
+        &self,
+        service_name: &str,
+        index: Option<u64>,
+    ) -> Result<(u64, Vec<CatalogServiceNode>)> {
+        let url = self
+            .consul_api_base_url
+            .join(&format!("catalog/service/{}", service_name))?;
+
+        let mut request = self.http_client.get(url);
+        if let Some(index) = index {
+            request = request.query(&[
+                ("index", index.to_string().as_str()),
+                ("wait", CONSUL_WATCH_WAIT),
+            ]);
+        }
+        let response = request
+            .send()
+            .await
+            .context(format!(
+                "Failed to send service discovery request for '{}'",
+                service_name
+            ))?
+            .error_for_status()?;
+
+        let new_index = response
+            .headers()
+            .get("X-Consul-Index")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0);
+        let nodes = response.json().await.context(format!(
+            "Failed to parse service discovery response for '{}'",
+            service_name
+        ))?;
+        Ok((new_index, nodes))
+    }
+}
+
+#[async_trait]
+impl ServiceRegistry for ConsulClient {
     #[instrument(name = "consul_register", skip(self, registration))]
-    pub async fn register_service(&self, registration: &AgentServiceRegistration) -> Result<()> {
+    async fn register_service(&self, registration: &AgentServiceRegistration) -> Result<()> {
         // Ensure the registration has a valid ID
         if registration.id.is_none() {
             return Err(anyhow!("Service registration must have a valid ID"));
@@ -118,7 +207,7 @@ impl ConsulClient {
     }
 
     #[instrument(name = "consul_deregister", skip(self))]
-    pub async fn deregister_service(&self, service_id: &str) -> Result<()> {
+    async fn deregister_service(&self, service_id: &str) -> Result<()> {
         let url = self
             .consul_api_base_url
             .join(&format!("agent/service/deregister/{}", service_id))?;
@@ -153,26 +242,8 @@ impl ConsulClient {
     }
 
     #[instrument(name = "consul_discover", skip(self))]
-    pub async fn discover_service(&self, service_name: &str) -> Result<Vec<CatalogServiceNode>> {
-        let url = self
-            .consul_api_base_url
-            .join(&format!("catalog/service/{}", service_name))?;
-
-        let nodes: Vec<CatalogServiceNode> = self
-            .http_client
-            .get(url)
-            .send()
-            .await
-            .context(format!(
-                "Failed to send service discovery request for '{}'",
-                service_name
-            ))?
-            .json()
-            .await
-            .context(format!(
-                "Failed to parse service discovery response for '{}'",
-                service_name
-            ))?;
+    async fn discover_service(&self, service_name: &str) -> Result<Vec<CatalogServiceNode>> {
+        let (_, nodes) = self.query_catalog(service_name, None).await?;
 
         info!(
             "Discovered {} instances for service '{}'.",
@@ -181,6 +252,47 @@ impl ConsulClient {
         );
         Ok(nodes)
     }
+
+    #[instrument(name = "consul_watch", skip(self))]
+    async fn watch_service(
+        &self,
+        service_name: &str,
+    ) -> Result<watch::Receiver<Vec<CatalogServiceNode>>> {
+        let (mut index, nodes) = self.query_catalog(service_name, None).await?;
+        let (tx, rx) = watch::channel(nodes);
+        let client = self.clone();
+        let service_name = service_name.to_string();
+
+        // Follow the catalog with blocking queries until every receiver is gone
+        tokio::spawn(async move {
+            loop {
+                let result = tokio::select! {
+                    result = client.query_catalog(&service_name, Some(index)) => result,
+                    () = tx.closed() => break,
+                };
+                match result {
+                    Ok((new_index, nodes)) => {
+                        let unchanged = new_index == index;
+                        // The index going backwards means Consul restarted: start over
+                        index = if new_index < index { 0 } else { new_index };
+                        tx.send_if_modified(|current| {
+                            let modified = *current != nodes;
+                            *current = nodes;
+                            modified
+                        });
+                        if unchanged {
+                            tokio::time::sleep(CONSUL_WATCH_RETRY).await;
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Watching service '{}' failed: {:#}", service_name, e);
+                        tokio::time::sleep(CONSUL_WATCH_RETRY).await;
+                    }
+                }
+            }
+        });
+        Ok(rx)
+    }
 }
 
 ///
@@ -0,0 +1,117 @@
+//! The OS signals the framework reacts to.
+//!
+//! SIGINT and SIGTERM ask for a graceful stop, SIGHUP for a configuration reload.
+//! On non-Unix platforms only Ctrl+C is delivered, as `Signal::Interrupt`.
+
+use std::fmt;
+use std::io;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+/// A signal received by the process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    /// SIGINT, e.g. Ctrl+C
+    Interrupt,
+    /// SIGTERM, e.g. `kill <pid>` or a container runtime stopping the process
+    Terminate,
+    /// SIGHUP, conventionally "reload your configuration"
+    Hangup,
+}
+
+impl Signal {
+    /// Whether the signal asks the process to stop.
+    pub fn is_shutdown(self) -> bool {
+        matches!(self, Signal::Interrupt | Signal::Terminate)
+    }
+
+    /// The exit status of a process killed by the signal, as shells report it (128 + number).
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Signal::Hangup => 129,
+            Signal::Interrupt => 130,
+            Signal::Terminate => 143,
+        }
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Signal::Interrupt => "SIGINT",
+            Signal::Terminate => "SIGTERM",
+            Signal::Hangup => "SIGHUP",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Listens for SIGINT, SIGTERM and SIGHUP.
+/// The handlers are installed by `new` and replace the default actions for the
+/// rest of the process' life, so the process no longer dies on these signals.
+#[derive(Debug)]
+pub struct SignalListener {
+    #[cfg(unix)]
+    interrupt: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    terminate: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    hangup: tokio::signal::unix::Signal,
+}
+
+impl SignalListener {
+    /// Installs the signal handlers.
+    #[cfg(unix)]
+    pub fn new() -> io::Result<Self> {
+        use tokio::signal::unix::{signal, SignalKind};
+        Ok(Self {
+            interrupt: signal(SignalKind::interrupt())?,
+            terminate: signal(SignalKind::terminate())?,
+            hangup: signal(SignalKind::hangup())?,
+        })
+    }
+
+    /// Installs the Ctrl+C handler.
+    #[cfg(not(unix))]
+    pub fn new() -> io::Result<Self> {
+        Ok(Self {})
+    }
+
+    /// Waits for the next signal.
+    #[cfg(unix)]
+    pub async fn recv(&mut self) -> Signal {
+        let signal = tokio::select! {
+            _ = self.interrupt.recv() => Signal::Interrupt,
+            _ = self.terminate.recv() => Signal::Terminate,
+            _ = self.hangup.recv() => Signal::Hangup,
+        };
+        debug!("Received {}", signal);
+        signal
+    }
+
+    /// Waits for the next signal.
+    #[cfg(not(unix))]
+    pub async fn recv(&mut self) -> Signal {
+        let _ = tokio::signal::ctrl_c().await;
+        debug!("Received {}", Signal::Interrupt);
+        Signal::Interrupt
+    }
+
+    /// Forwards the signals to a channel, until its receiver is dropped.
+    pub fn into_channel(mut self) -> mpsc::Receiver<Signal> {
+        let (tx, rx) = mpsc::channel(8);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    signal = self.recv() => {
+                        if tx.send(signal).await.is_err() {
+                            break;
+                        }
+                    }
+                    () = tx.closed() => break,
+                }
+            }
+        });
+        rx
+    }
+}
@@ -23,25 +23,25 @@ use framework::{
     config::{BaseServiceConfig, ServiceConfig as TraitServiceConfig},
     error::FrameworkError,
     lifecycle::{RunnableService, ServiceStatus},
-    registry::{AgentServiceCheck, AgentServiceRegistration, ConsulClient},
+    registry::{AgentServiceCheck, AgentServiceRegistration, ConsulClient, ServiceRegistry},
 };
 
-use crate::services::framework::{self, config::RegistryConfig};
+use crate::services::framework;
 
 // The main function for the client.
-// #[tokio::main]
-pub async fn start_consume(url: String) -> Result<()> {
+// Discovers `target_service_name` through the Consul API at `registry_url` and calls it
+// every 5 seconds; returns after `max_calls` successful calls, or never with `None`.
+pub async fn start_consume(
+    registry_url: &str,
+    target_service_name: &str,
+    max_calls: Option<usize>,
+) -> Result<()> {
     info!("Starting gRPC client for service discovery...");
 
     // --- 2. Configure Consul Client ---
-    let consul_config = RegistryConfig {
-        registry_url: "http://192.168.2.6:8500/v1/".to_string(),
-        service_ip: "192.168.2.7".to_string(), // Client's IP, not strictly used for discovery, but good to have
-    };
     let consul_client =
-        ConsulClient::new(&consul_config.registry_url).context("Failed to create Consul client")?;
-
-    let target_service_name = "my-greeter-service"; // The name our server registers with
+        ConsulClient::new(registry_url).context("Failed to create Consul client")?;
+    let mut calls = 0;
 
     // --- 3. Service Discovery Loop ---
     let mut client_opt: Option<GreeterClient<Channel>> = None;
@@ -126,6 +126,10 @@ pub async fn start_consume(url: String) -> Result<()> {
                 Ok(response) => {
                     let msg = response.into_inner().message;
                     info!("gRPC Response: {}", msg);
+                    calls += 1;
+                    if max_calls.is_some_and(|max| calls >= max) {
+                        break;
+                    }
                 }
                 Err(e) => {
                     error!("gRPC Call Failed: {}. Re-discovering...", e);
@@ -147,34 +151,47 @@ pub async fn start_consume(url: String) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[ignore = "tonic grpc server"]
-    #[test]
-    fn test_hello_client() {
-        // --- 1. Initialize Logging ---
-        // This should be done once at the very beginning of your application.
-        // 1. Initialize the tracing subscriber
-        // This should be done once at the very beginning of your application.
-        tracing_subscriber::registry()
-            .with(
-                fmt::layer() // Use the fmt layer for console output
-                    .compact() // Make the output more compact (optional)
-                    .with_target(true) // Include the target (module path) of the event
-                    .with_level(true) // Include the log level
-                    .with_thread_ids(true) // Include thread IDs (optional)
-                    .with_thread_names(true), // Include thread names (optional)
-            )
-            .with(
-                EnvFilter::from_default_env() // Allow filtering via RUST_LOG env var
-                    .add_directive(Level::INFO.into()), // Default log level if RUST_LOG is not set
-            )
-            .init(); // Initialize the global default subscriber
-
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        rt.block_on(async {
-            start_consume("http://192.168.2.7:50051".to_owned())
-                .await
-                .unwrap();
-        });
+    use crate::services::fake_consul::FakeConsul;
+    use crate::services::framework::{
+        config::{RegistryBackend, RegistryConfig},
+        lifecycle::ApplicationFramework,
+    };
+    use crate::services::greeter_service::{
+        GreeterApplicationService, GreeterServiceConfig, DEFAULT_GREETING,
+    };
+    use crate::services::test_support::free_port;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_hello_client() {
+        // 在进程内启动 Consul 替身和 greeter 服务
+        let consul = FakeConsul::start().await.unwrap();
+        let config = GreeterServiceConfig {
+            base_config: BaseServiceConfig {
+                service_id_prefix: "greeter-consume".to_string(),
+                service_name: "greeter-consume-service".to_string(),
+                consul: RegistryConfig {
+                    registry_url: consul.api_url(),
+                    service_ip: "127.0.0.1".to_string(),
+                },
+                registry: RegistryBackend::Consul,
+            },
+            grpc_port: free_port(),
+            http_health_port: free_port(),
+            greeting: DEFAULT_GREETING.to_string(),
+        };
+        let mut app = ApplicationFramework::<GreeterApplicationService>::new(config).unwrap();
+        app.wait_ready(Duration::from_secs(5)).await.unwrap();
+
+        // The client finds the greeter through Consul and calls it
+        tokio::time::timeout(
+            Duration::from_secs(10),
+            start_consume(&consul.api_url(), "greeter-consume-service", Some(1)),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        app.stop().await.unwrap();
     }
 }
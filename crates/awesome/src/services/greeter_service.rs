@@ -1,8 +1,13 @@
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use axum::http::StatusCode;
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
-use tokio::sync::{oneshot, RwLock};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, RwLock},
+};
+use tokio::sync::oneshot;
 use tonic::{Request, Response, Status};
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
@@ -13,8 +18,8 @@ use tracing::{error, info, instrument};
 use framework::{
     config::{BaseServiceConfig, ServiceConfig as TraitServiceConfig},
     error::FrameworkError,
-    lifecycle::{RunnableService, ServiceStatus},
-    registry::{AgentServiceCheck, AgentServiceRegistration, ConsulClient},
+    lifecycle::{ReadyHandle, RunnableService, StatusHandle},
+    registry::{AgentServiceCheck, AgentServiceRegistration, ServiceRegistry},
 };
 
 // --- gRPC auto-generated code ---
@@ -29,8 +34,10 @@ use helloworld::{
 use crate::services::framework;
 
 // --- gRPC Service Logic ---
-#[derive(Default)]
-pub struct MyGreeter;
+pub struct MyGreeter {
+    // Shared with the service, so a reload changes the replies at once
+    greeting: Arc<RwLock<String>>,
+}
 
 #[tonic::async_trait]
 impl Greeter for MyGreeter {
@@ -43,23 +50,34 @@ impl Greeter for MyGreeter {
         info!("Received greeting request from: {}", name);
 
         let trace_id = uuid::Uuid::new_v4().to_string();
+        let greeting = self.greeting.read().unwrap().clone();
 
         let reply = helloworld::HelloReply {
             message: format!(
-                "Hello {} from gRPC Greeter Service! traceId:{}",
-                name, trace_id
+                "{} {} from gRPC Greeter Service! traceId:{}",
+                greeting, name, trace_id
             ),
         };
         Ok(Response::new(reply))
     }
 }
 
+/// Greeting used when the configuration does not set one
+pub const DEFAULT_GREETING: &str = "Hello";
+
+fn default_greeting() -> String {
+    DEFAULT_GREETING.to_string()
+}
+
 // --- Greeter Service Specific Configuration ---
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GreeterServiceConfig {
     pub base_config: BaseServiceConfig,
     pub grpc_port: u16,
     pub http_health_port: u16,
+    // Word the replies start with; the only setting a reload can change
+    #[serde(default = "default_greeting")]
+    pub greeting: String,
 }
 
 impl TraitServiceConfig for GreeterServiceConfig {
@@ -72,9 +90,10 @@ impl TraitServiceConfig for GreeterServiceConfig {
 #[derive(Debug)]
 pub struct GreeterApplicationService {
     config: GreeterServiceConfig,
+    greeting: Arc<RwLock<String>>,
     instance_id: String,
-    status: Arc<RwLock<ServiceStatus>>,
-    consul_client: ConsulClient,
+    status: StatusHandle,
+    registry: Arc<dyn ServiceRegistry>,
 }
 
 #[async_trait]
@@ -84,15 +103,15 @@ impl RunnableService for GreeterApplicationService {
     fn new(
         config: Self::Config,
         instance_id: String,
-        status_arc: Arc<RwLock<ServiceStatus>>,
+        status: StatusHandle,
+        registry: Arc<dyn ServiceRegistry>,
     ) -> Self {
-        let consul_client = ConsulClient::new(&config.base_config.consul.registry_url)
-            .expect("Failed to create Consul client in GreeterService");
         Self {
+            greeting: Arc::new(RwLock::new(config.greeting.clone())),
             config,
             instance_id,
-            status: status_arc,
-            consul_client,
+            status,
+            registry,
         }
     }
 
@@ -100,14 +119,15 @@ impl RunnableService for GreeterApplicationService {
         &self.instance_id
     }
 
-    fn get_status(&self) -> Arc<RwLock<ServiceStatus>> {
-        Arc::clone(&self.status)
+    fn get_status(&self) -> StatusHandle {
+        self.status.clone()
     }
 
-    #[instrument(name = "greeter_service_logic", skip(self, shutdown_rx))]
+    #[instrument(name = "greeter_service_logic", skip(self, shutdown_rx, ready))]
     async fn start_service_logic(
         &self,
         shutdown_rx: oneshot::Receiver<()>,
+        ready: ReadyHandle,
     ) -> Result<(), FrameworkError> {
         let grpc_addr: SocketAddr = format!(
             "{}:{}",
@@ -123,13 +143,20 @@ impl RunnableService for GreeterApplicationService {
         .map_err(|e| FrameworkError::Config(format!("Invalid HTTP health bind address: {}", e)))?;
 
         // --- Build gRPC Service ---
-        let greeter_service = helloworld::greeter_server::GreeterServer::new(MyGreeter::default());
+        let greeter_service = helloworld::greeter_server::GreeterServer::new(MyGreeter {
+            greeting: self.greeting.clone(),
+        });
+
+        // Bind before serving, so a taken port fails the startup instead of the running server
+        let grpc_listener = tokio::net::TcpListener::bind(grpc_addr)
+            .await
+            .map_err(|e| FrameworkError::Startup(format!("Failed to bind gRPC server: {}", e)))?;
 
         let grpc_server = tonic::transport::Server::builder()
             .layer(TraceLayer::new_for_grpc()) // Add gRPC tracing (from tower-http)
             // Add other Tower middleware here, e.g., .timeout(), .rate_limit()
             .add_service(greeter_service)
-            .serve(grpc_addr);
+            .serve_with_incoming(tonic::transport::server::TcpIncoming::from(grpc_listener));
 
         // --- Start HTTP Health Check Server ---
         let health_app: axum::Router<()> = axum::Router::new().route(
@@ -142,13 +169,15 @@ impl RunnableService for GreeterApplicationService {
 
         let listener = tokio::net::TcpListener::bind(&http_health_addr)
             .await
-            .unwrap();
+            .map_err(|e| {
+                FrameworkError::Startup(format!("Failed to bind health check server: {}", e))
+            })?;
 
         let health_server = axum::serve(listener, health_app);
 
         // let health_server = Server::bind(&http_health_addr).serve(health_app.into_make_service());
 
-        // --- Register with Consul ---
+        // --- Register with the service registry ---
         let registration_payload = AgentServiceRegistration {
             id: Some(self.instance_id.clone()),
             name: self.config.base_config.service_name.clone(),
@@ -175,12 +204,13 @@ impl RunnableService for GreeterApplicationService {
             }),
         };
 
-        self.consul_client
+        self.registry
             .register_service(&registration_payload)
             .await
-            .map_err(|e| {
-                FrameworkError::Consul(format!("Failed to register service with Consul: {}", e))
-            })?;
+            .map_err(|e| FrameworkError::Registry(format!("Failed to register service: {}", e)))?;
+
+        // Both listeners are bound and the instance is registered: accept requests
+        ready.ready();
 
         // --- Wait for Shutdown or Internal Server Failure ---
         tokio::select! {
@@ -205,14 +235,213 @@ impl RunnableService for GreeterApplicationService {
             }
         }
 
-        // --- Deregister from Consul ---
-        self.consul_client
+        // --- Deregister from the service registry ---
+        self.registry
             .deregister_service(&self.instance_id)
             .await
             .map_err(|e| {
-                FrameworkError::Consul(format!("Failed to deregister service from Consul: {}", e))
+                FrameworkError::Registry(format!("Failed to deregister service: {}", e))
             })?;
 
         Ok(())
     }
+
+    /// Applies a new greeting to the running servers. The listeners and the
+    /// registration are set up once, so a config changing them is refused.
+    async fn reload(&self, config: Self::Config) -> Result<(), FrameworkError> {
+        let running = &self.config;
+        if config.grpc_port != running.grpc_port
+            || config.http_health_port != running.http_health_port
+            || config.base_config.service_name != running.base_config.service_name
+            || config.base_config.consul != running.base_config.consul
+            || config.base_config.registry != running.base_config.registry
+        {
+            return Err(FrameworkError::Config(format!(
+                "Service '{}' must be restarted to change its ports, name or registry",
+                self.instance_id
+            )));
+        }
+        info!(
+            "Service '{}' now greets with '{}'.",
+            self.instance_id, config.greeting
+        );
+        *self.greeting.write().unwrap() = config.greeting;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::fake_consul::FakeConsul;
+    use crate::services::framework::{
+        config::{RegistryBackend, RegistryConfig},
+        lifecycle::{ApplicationFramework, ServiceStatus},
+        local_registry::InMemoryRegistry,
+        registry::ConsulClient,
+    };
+    use crate::services::test_support::free_port;
+    use helloworld::greeter_client::GreeterClient;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_greeter_runs_with_in_memory_registry() {
+        let registry = Arc::new(InMemoryRegistry::new());
+        let config = GreeterServiceConfig {
+            base_config: BaseServiceConfig {
+                service_id_prefix: "greeter-test".to_string(),
+                service_name: "greeter-test-service".to_string(),
+                registry: RegistryBackend::Memory,
+                ..Default::default()
+            },
+            grpc_port: free_port(),
+            http_health_port: free_port(),
+            greeting: DEFAULT_GREETING.to_string(),
+        };
+        let mut app = ApplicationFramework::<GreeterApplicationService>::with_registry(
+            config,
+            registry.clone(),
+        )
+        .unwrap();
+
+        // Once ready, the service is registered and listening
+        app.wait_ready(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(app.get_status().await, ServiceStatus::Running);
+        let nodes = registry
+            .discover_service("greeter-test-service")
+            .await
+            .unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].service_id, app.service().instance_id());
+
+        // Clients find it through the registry
+        let addr = format!(
+            "http://{}:{}",
+            nodes[0].service_address, nodes[0].service_port
+        );
+        let mut client = GreeterClient::connect(addr).await.unwrap();
+        let reply = client
+            .say_hello(HelloRequest {
+                name: "test".to_string(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(reply.message.starts_with("Hello test"));
+
+        // Stopping deregisters it
+        app.stop().await.unwrap();
+        assert_eq!(app.get_status().await, ServiceStatus::Stopped);
+        assert!(registry
+            .discover_service("greeter-test-service")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reload_changes_greeting() {
+        let config = GreeterServiceConfig {
+            base_config: BaseServiceConfig {
+                service_id_prefix: "greeter-test".to_string(),
+                service_name: "greeter-reload-service".to_string(),
+                registry: RegistryBackend::Memory,
+                ..Default::default()
+            },
+            grpc_port: free_port(),
+            http_health_port: free_port(),
+            greeting: DEFAULT_GREETING.to_string(),
+        };
+        let mut app = ApplicationFramework::<GreeterApplicationService>::with_registry(
+            config.clone(),
+            Arc::new(InMemoryRegistry::new()),
+        )
+        .unwrap();
+        app.wait_ready(Duration::from_secs(5)).await.unwrap();
+
+        let addr = format!("http://127.0.0.1:{}", config.grpc_port);
+        let mut client = GreeterClient::connect(addr).await.unwrap();
+        let mut greet = async || {
+            let request = HelloRequest {
+                name: "test".to_string(),
+            };
+            client
+                .say_hello(request)
+                .await
+                .unwrap()
+                .into_inner()
+                .message
+        };
+        assert!(greet().await.starts_with("Hello test"));
+
+        // The running servers pick up the new greeting
+        let reloaded = GreeterServiceConfig {
+            greeting: "Bonjour".to_string(),
+            ..config.clone()
+        };
+        app.reload(reloaded).await.unwrap();
+        assert!(greet().await.starts_with("Bonjour test"));
+
+        // Listeners cannot move while running
+        let moved = GreeterServiceConfig {
+            grpc_port: free_port(),
+            ..config
+        };
+        assert!(matches!(
+            app.reload(moved).await,
+            Err(FrameworkError::Config(_))
+        ));
+        assert!(greet().await.starts_with("Bonjour test"));
+
+        app.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_greeter_registers_with_consul_agent() {
+        let consul = FakeConsul::start().await.unwrap();
+        let config = GreeterServiceConfig {
+            base_config: BaseServiceConfig {
+                service_id_prefix: "greeter-test".to_string(),
+                service_name: "greeter-consul-service".to_string(),
+                consul: RegistryConfig {
+                    registry_url: consul.api_url(),
+                    service_ip: "127.0.0.1".to_string(),
+                },
+                registry: RegistryBackend::Consul,
+            },
+            grpc_port: free_port(),
+            http_health_port: free_port(),
+            greeting: DEFAULT_GREETING.to_string(),
+        };
+        let mut app = ApplicationFramework::<GreeterApplicationService>::new(config).unwrap();
+        app.wait_ready(Duration::from_secs(5)).await.unwrap();
+
+        // The agent runs the greeter's HTTP check until the instance passes
+        let passing = format!(
+            "{}health/service/greeter-consul-service?passing",
+            consul.api_url()
+        );
+        let id = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let instances: Vec<serde_json::Value> =
+                    reqwest::get(&passing).await.unwrap().json().await.unwrap();
+                if let Some(instance) = instances.first() {
+                    break instance["Service"]["ID"].as_str().unwrap().to_string();
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(id, app.service().instance_id());
+
+        app.stop().await.unwrap();
+        assert_eq!(app.get_status().await, ServiceStatus::Stopped);
+        let client = ConsulClient::new(&consul.api_url()).unwrap();
+        assert!(client
+            .discover_service("greeter-consul-service")
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -2,6 +2,9 @@ pub mod concrete_injection_sample;
 pub mod consul_sample;
 pub mod dynmaic_injection_arc_sample;
 pub mod dynmaic_injection_box_sample;
+pub mod fake_consul;
+#[cfg(test)]
+mod test_support;
 pub mod framework;
 pub mod greeter_consume;
 pub mod greeter_service;
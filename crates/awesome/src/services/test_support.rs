@@ -0,0 +1,13 @@
+//! Helpers shared by the service tests.
+
+/// A port on 127.0.0.1 that is free right now, for servers started by a test.
+///
+/// The port is only reserved while probing, so another process may take it
+/// before the test binds it; that is rare enough for tests.
+pub fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
@@ -9,7 +9,6 @@ use helloworld::greeter_client::GreeterClient;
 use helloworld::HelloRequest;
 
 // The main function for the client.
-#[tokio::main]
 pub async fn hello_client(url: String) -> Result<(), Box<dyn std::error::Error>> {
     // Connect to the gRPC server.
     // The address should match the server's listening address.
@@ -33,10 +32,24 @@ pub async fn hello_client(url: String) -> Result<(), Box<dyn std::error::Error>>
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[ignore = "tonic grpc server"]
-    #[test]
-    fn test_hello_client() {
-        hello_client("http://192.168.2.7:50051".to_owned()).unwrap();
+    use crate::services::test_support::free_port;
+    use crate::services::tonic_hello_server::hello_server;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_hello_client() {
+        // 选一个空闲端口，在进程内启动服务端
+        let port = free_port();
+        let server = tokio::spawn(async move { hello_server("127.0.0.1", port.into()).await });
+
+        let url = format!("http://127.0.0.1:{}", port);
+        let mut attempts = 0;
+        // 服务端启动需要一点时间，连接失败时重试
+        while let Err(e) = hello_client(url.clone()).await {
+            attempts += 1;
+            assert!(attempts < 50, "hello_client failed: {}", e);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        server.abort();
     }
 }
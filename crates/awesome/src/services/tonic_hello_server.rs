@@ -50,8 +50,7 @@ impl Greeter for MyGreeter {
 type MainFn = fn() -> Result<(), Box<dyn std::error::Error>>;
 
 // The main function where the server is started.
-#[tokio::main]
-pub async fn hello_server(host: &str, port: u32) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn hello_server(host: &str, port: u32) -> anyhow::Result<()> {
     // Define the address for the server to listen on.
     let url = format!("{}:{}", host, port);
     let addr: SocketAddr = url.parse()?;
@@ -76,8 +75,8 @@ mod tests {
     use super::*;
 
     #[ignore = "tonic grpc server"]
-    #[test]
-    fn test_hello_server() {
-        hello_server("[::1]", 50051).unwrap();
+    #[tokio::test]
+    async fn test_hello_server() {
+        hello_server("[::1]", 50051).await.unwrap();
     }
 }
@@ -272,8 +272,7 @@ impl Inventory for StoreInventory {
     }
 }
 
-#[tokio::main]
-pub async fn store_server(host: &str, port: u32) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn store_server(host: &str, port: u32) -> anyhow::Result<()> {
     let url = format!("{}:{}", host, port);
     let addr = url.parse()?;
     let inventory = StoreInventory::default();
@@ -1,22 +1,25 @@
 //! LeetCode 学习样例解题代码
 //!
-//! 问题解决rust 实例代码运行，直接使用单元测试的mian_test 运行
+//! 问题解决rust 实例代码运行，直接使用单元测试的mian_test 运行。
+//! 每道题在 `solution_xxxx` 中登记 [`problem::Problem`]，`hello leetcode <题号>` 运行全部解法。
 
 pub struct Solution;
 
+pub mod problem;
+
 //问题0001的解决
-mod solution_0001;
+pub mod solution_0001;
 
 //问题0002
-mod solution_0002;
+pub mod solution_0002;
 
 /**
  * leetcode 样例入口
  */
 pub fn leetcode_example() {
-    Solution::two_sum(vec![2, 3, 5, 6], 8);
-
-    Solution::two_sum2(vec![2, 3, 5, 6], 8);
+    println!("{}", solution_0001::PROBLEM.run());
+    println!();
+    println!("{}", solution_0002::PROBLEM.run());
 }
 
 pub fn main_leet() {
@@ -0,0 +1,268 @@
+//! 题目描述与测试用例运行器
+//!
+//! 每道题登记编号、标题、难度、多种解法（variant）和测试用例；
+//! [`Problem::run`] 让每种解法跑完全部用例，记录耗时，并交叉校验各解法的输出是否一致。
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// 题目难度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// 英文名称（与 LeetCode 网站一致）
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    /// 中文名称
+    pub fn name_zh(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "简单",
+            Difficulty::Medium => "中等",
+            Difficulty::Hard => "困难",
+        }
+    }
+}
+
+/// 一种解法：名称和入口函数
+pub struct Variant<I, O> {
+    /// 解法名称，通常是 `Solution` 上的方法名
+    pub name: &'static str,
+    /// 解题函数，输入为整道题的参数元组
+    pub solve: fn(I) -> O,
+}
+
+/// 一个测试用例：输入和期望输出
+#[derive(Debug, Clone)]
+pub struct Case<I, O> {
+    pub input: I,
+    pub expected: O,
+}
+
+/// 一道题目
+pub struct Problem<I: 'static, O: 'static> {
+    /// 题号
+    pub number: u32,
+    /// 英文标题
+    pub title: &'static str,
+    /// 中文标题
+    pub title_zh: &'static str,
+    pub difficulty: Difficulty,
+    /// 全部解法，第一个作为交叉校验的基准
+    pub variants: &'static [Variant<I, O>],
+    /// 生成测试用例
+    pub cases: fn() -> Vec<Case<I, O>>,
+}
+
+/// 一种解法的运行结果
+#[derive(Debug, Clone)]
+pub struct VariantReport {
+    pub name: &'static str,
+    /// 输出与期望一致的用例数
+    pub passed: usize,
+    /// 全部用例的总耗时
+    pub elapsed: Duration,
+    /// 未通过的用例说明
+    pub failures: Vec<String>,
+}
+
+/// 一道题目的运行结果
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub number: u32,
+    pub title: &'static str,
+    pub title_zh: &'static str,
+    pub difficulty: Difficulty,
+    /// 用例数
+    pub cases: usize,
+    pub variants: Vec<VariantReport>,
+    /// 与基准解法输出不一致的用例说明
+    pub disagreements: Vec<String>,
+}
+
+impl<I: Clone + fmt::Debug + 'static, O: PartialEq + fmt::Debug + 'static> Problem<I, O> {
+    /// 运行每种解法的全部用例，并交叉校验
+    pub fn run(&self) -> Report {
+        let cases = (self.cases)();
+        let mut variants = Vec::new();
+        let mut disagreements = Vec::new();
+        let mut baseline: Vec<O> = Vec::new();
+
+        for (position, variant) in self.variants.iter().enumerate() {
+            let mut report = VariantReport {
+                name: variant.name,
+                passed: 0,
+                elapsed: Duration::ZERO,
+                failures: Vec::new(),
+            };
+            let mut outputs = Vec::new();
+            for (index, case) in cases.iter().enumerate() {
+                let input = case.input.clone();
+                let started = Instant::now();
+                let output = (variant.solve)(input);
+                report.elapsed += started.elapsed();
+
+                if output == case.expected {
+                    report.passed += 1;
+                } else {
+                    report.failures.push(format!(
+                        "case #{} {:?}: expected {:?}, got {:?}",
+                        index + 1,
+                        case.input,
+                        case.expected,
+                        output
+                    ));
+                }
+                outputs.push(output);
+            }
+
+            if position == 0 {
+                baseline = outputs;
+            } else {
+                for (index, (output, expected)) in outputs.iter().zip(&baseline).enumerate() {
+                    if output != expected {
+                        disagreements.push(format!(
+                            "case #{}: {} returned {:?}, {} returned {:?}",
+                            index + 1,
+                            variant.name,
+                            output,
+                            self.variants[0].name,
+                            expected
+                        ));
+                    }
+                }
+            }
+            variants.push(report);
+        }
+
+        Report {
+            number: self.number,
+            title: self.title,
+            title_zh: self.title_zh,
+            difficulty: self.difficulty,
+            cases: cases.len(),
+            variants,
+            disagreements,
+        }
+    }
+}
+
+impl Report {
+    /// 是否所有解法都通过了全部用例且输出一致
+    pub fn is_ok(&self) -> bool {
+        self.disagreements.is_empty()
+            && self
+                .variants
+                .iter()
+                .all(|variant| variant.passed == self.cases)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "#{} {} ({}) · {}",
+            self.number,
+            self.title,
+            self.title_zh,
+            self.difficulty.name()
+        )?;
+        writeln!(f)?;
+        writeln!(f, "{:<28}  {:>7}  {:>12}", "VARIANT", "CASES", "TIME")?;
+        writeln!(f, "{:-<28}  {:->7}  {:->12}", "", "", "")?;
+        for variant in &self.variants {
+            writeln!(
+                f,
+                "{:<28}  {:>7}  {:>12}",
+                variant.name,
+                format!("{}/{}", variant.passed, self.cases),
+                format!("{:.1?}", variant.elapsed)
+            )?;
+            for failure in &variant.failures {
+                writeln!(f, "  ✘ {}", failure)?;
+            }
+        }
+        writeln!(f)?;
+        if self.disagreements.is_empty() {
+            write!(
+                f,
+                "✔ {} variants agree on {} cases",
+                self.variants.len(),
+                self.cases
+            )
+        } else {
+            write!(f, "✘ Variants disagree:")?;
+            for disagreement in &self.disagreements {
+                write!(f, "\n  {}", disagreement)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: Problem<i64, i64> = Problem {
+        number: 0,
+        title: "Square",
+        title_zh: "平方",
+        difficulty: Difficulty::Easy,
+        variants: &[
+            Variant {
+                name: "multiply",
+                solve: |x| x * x,
+            },
+            Variant {
+                name: "pow",
+                solve: |x| x.pow(2),
+            },
+            Variant {
+                name: "broken",
+                solve: |x| x + x,
+            },
+        ],
+        cases: || {
+            vec![
+                Case {
+                    input: 2,
+                    expected: 4,
+                },
+                Case {
+                    input: 3,
+                    expected: 9,
+                },
+            ]
+        },
+    };
+
+    #[test]
+    fn test_run_reports_failures_and_disagreements() {
+        let report = SQUARE.run();
+        assert_eq!(report.cases, 2);
+        assert_eq!(report.variants[0].passed, 2);
+        assert_eq!(report.variants[1].passed, 2);
+        assert_eq!(report.variants[2].passed, 1);
+        assert_eq!(
+            report.variants[2].failures,
+            vec!["case #2 3: expected 9, got 6"]
+        );
+        assert_eq!(
+            report.disagreements,
+            vec!["case #2: broken returned 6, multiply returned 9"]
+        );
+        assert!(!report.is_ok());
+    }
+}
@@ -1,7 +1,60 @@
+//! 0001. 两数之和 (Two Sum)
+//!
+//! 给定整数数组 `nums` 和目标值 `target`，找出和为 `target` 的两个数的下标；
+//! 没有答案时返回空数组。各解法返回下标的顺序不同，登记时统一按从小到大排序后比较。
+
 use super::Solution;
+use crate::problem::{Case, Difficulty, Problem, Variant};
 use std::collections::HashMap;
 use std::vec::Vec;
 
+/// 题目参数：(nums, target)
+type Input = (Vec<i32>, i32);
+
+/// 题目登记：三种解法和测试用例
+pub const PROBLEM: Problem<Input, Vec<i32>> = Problem {
+    number: 1,
+    title: "Two Sum",
+    title_zh: "两数之和",
+    difficulty: Difficulty::Easy,
+    variants: &[
+        Variant {
+            name: "two_sum",
+            solve: |(nums, target)| ascending(Solution::two_sum(nums, target)),
+        },
+        Variant {
+            name: "two_sum2",
+            solve: |(nums, target)| ascending(Solution::two_sum2(nums, target)),
+        },
+        Variant {
+            name: "two_sum_brute_force",
+            solve: |(nums, target)| ascending(Solution::two_sum_brute_force(nums, target)),
+        },
+    ],
+    cases,
+};
+
+fn cases() -> Vec<Case<Input, Vec<i32>>> {
+    let case = |nums: &[i32], target, expected: &[i32]| Case {
+        input: (nums.to_vec(), target),
+        expected: expected.to_vec(),
+    };
+    vec![
+        case(&[2, 7, 11, 15], 9, &[0, 1]),
+        case(&[3, 2, 4], 6, &[1, 2]),
+        case(&[3, 3], 6, &[0, 1]),
+        case(&[2, 3, 5, 7], 8, &[1, 2]),
+        case(&[-3, 4, 3, 90], 0, &[0, 2]),
+        case(&[1, 2, 3], 7, &[]),
+    ]
+}
+
+/// 把下标按从小到大排序，使各解法的输出可以直接比较
+fn ascending(mut indices: Vec<i32>) -> Vec<i32> {
+    indices.sort_unstable();
+    indices
+}
+
 impl Solution {
     pub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> {
         let mut result = Vec::new();
@@ -126,6 +179,19 @@ impl Solution {
 
         return result;
     }
+
+    /// 暴力枚举每一对下标，O(n²)
+    pub fn two_sum_brute_force(nums: Vec<i32>, target: i32) -> Vec<i32> {
+        for i in 0..nums.len() {
+            for j in i + 1..nums.len() {
+                if nums[i] + nums[j] == target {
+                    return vec![i as i32, j as i32];
+                }
+            }
+        }
+
+        Vec::new()
+    }
 }
 
 #[cfg(test)]
@@ -146,4 +212,10 @@ mod tests {
 
         println!("result:{:?}", r)
     }
+
+    #[test]
+    fn problem_test() {
+        let report = PROBLEM.run();
+        assert!(report.is_ok(), "{}", report);
+    }
 }
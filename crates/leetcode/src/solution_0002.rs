@@ -1,5 +1,9 @@
+//! 0002. 两数相加 (Add Two Numbers)
+//!
+//! 两个非负整数以链表逆序存储（每个节点一位数字），返回它们的和，同样以逆序链表表示。
+
 use super::Solution;
-use std::collections::HashMap;
+use crate::problem::{Case, Difficulty, Problem, Variant};
 use std::vec::Vec;
 
 // Definition for singly-linked list.
@@ -14,15 +18,127 @@ impl ListNode {
     fn new(val: i32) -> Self {
         ListNode { next: None, val }
     }
+
+    /// 由数字数组（低位在前）构造链表
+    pub fn from_digits(digits: &[i32]) -> Option<Box<ListNode>> {
+        digits
+            .iter()
+            .rev()
+            .fold(None, |next, &val| Some(Box::new(ListNode { val, next })))
+    }
+
+    /// 把链表转换回数字数组（低位在前）
+    pub fn to_digits(mut list: &Option<Box<ListNode>>) -> Vec<i32> {
+        let mut digits = Vec::new();
+        while let Some(node) = list {
+            digits.push(node.val);
+            list = &node.next;
+        }
+        digits
+    }
 }
+
+/// 题目参数：两个链表的数字数组
+type Input = (Vec<i32>, Vec<i32>);
+
+/// 题目登记：输入输出用数字数组表示，便于书写用例
+pub const PROBLEM: Problem<Input, Vec<i32>> = Problem {
+    number: 2,
+    title: "Add Two Numbers",
+    title_zh: "两数相加",
+    difficulty: Difficulty::Medium,
+    variants: &[
+        Variant {
+            name: "add_two_numbers",
+            solve: |(l1, l2)| {
+                ListNode::to_digits(&Solution::add_two_numbers(
+                    ListNode::from_digits(&l1),
+                    ListNode::from_digits(&l2),
+                ))
+            },
+        },
+        Variant {
+            name: "add_two_numbers_recursive",
+            solve: |(l1, l2)| {
+                ListNode::to_digits(&Solution::add_two_numbers_recursive(
+                    ListNode::from_digits(&l1),
+                    ListNode::from_digits(&l2),
+                ))
+            },
+        },
+    ],
+    cases,
+};
+
+fn cases() -> Vec<Case<Input, Vec<i32>>> {
+    let case = |l1: &[i32], l2: &[i32], expected: &[i32]| Case {
+        input: (l1.to_vec(), l2.to_vec()),
+        expected: expected.to_vec(),
+    };
+    vec![
+        case(&[2, 4, 3], &[5, 6, 4], &[7, 0, 8]),
+        case(&[0], &[0], &[0]),
+        case(
+            &[9, 9, 9, 9, 9, 9, 9],
+            &[9, 9, 9, 9],
+            &[8, 9, 9, 9, 0, 0, 0, 1],
+        ),
+        case(&[5], &[5], &[0, 1]),
+        case(&[1, 8], &[0], &[1, 8]),
+    ]
+}
+
 impl Solution {
+    /// 迭代：同时遍历两个链表，逐位相加并记录进位
     pub fn add_two_numbers(
         l1: Option<Box<ListNode>>,
         l2: Option<Box<ListNode>>,
     ) -> Option<Box<ListNode>> {
-        let result = Some(Box::new(ListNode::new(0)));
+        let mut head = ListNode::new(0);
+        let mut tail = &mut head;
+        let (mut l1, mut l2, mut carry) = (l1, l2, 0);
 
-        result
+        while l1.is_some() || l2.is_some() || carry > 0 {
+            let mut sum = carry;
+            if let Some(node) = l1 {
+                sum += node.val;
+                l1 = node.next;
+            }
+            if let Some(node) = l2 {
+                sum += node.val;
+                l2 = node.next;
+            }
+            carry = sum / 10;
+            tail.next = Some(Box::new(ListNode::new(sum % 10)));
+            tail = tail.next.as_mut().unwrap();
+        }
+
+        head.next
+    }
+
+    /// 递归：当前位相加，进位并入下一层
+    pub fn add_two_numbers_recursive(
+        l1: Option<Box<ListNode>>,
+        l2: Option<Box<ListNode>>,
+    ) -> Option<Box<ListNode>> {
+        fn add(
+            l1: Option<Box<ListNode>>,
+            l2: Option<Box<ListNode>>,
+            carry: i32,
+        ) -> Option<Box<ListNode>> {
+            if l1.is_none() && l2.is_none() && carry == 0 {
+                return None;
+            }
+            let (v1, n1) = l1.map_or((0, None), |node| (node.val, node.next));
+            let (v2, n2) = l2.map_or((0, None), |node| (node.val, node.next));
+            let sum = v1 + v2 + carry;
+            Some(Box::new(ListNode {
+                val: sum % 10,
+                next: add(n1, n2, sum / 10),
+            }))
+        }
+
+        add(l1, l2, 0)
     }
 }
 
@@ -44,4 +160,10 @@ mod tests {
 
         println!("result:{:?}", r)
     }
+
+    #[test]
+    fn problem_test() {
+        let report = PROBLEM.run();
+        assert!(report.is_ok(), "{}", report);
+    }
 }
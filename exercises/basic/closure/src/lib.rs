@@ -0,0 +1,39 @@
+//! 练习：闭包与 Fn / FnMut / FnOnce
+//!
+//! 完成下面带有 `TODO` 的函数，然后运行 `hello exercise closure` 检查答案。
+
+/// 对 `x` 连续调用两次 `f`
+///
+/// `apply_twice(|x| x + 3, 1)` 返回 `7`
+pub fn apply_twice<F>(f: F, x: i32) -> i32
+where
+    F: Fn(i32) -> i32,
+{
+    // TODO: 返回 f(f(x))
+    todo!()
+}
+
+/// 返回一个闭包，它把参数加上 `n`
+///
+/// 提示：闭包需要用 `move` 捕获 `n`
+pub fn make_adder(n: i32) -> impl Fn(i32) -> i32 {
+    // TODO: 改为 move |x| ...，把 x 加上 n
+    |_x| todo!()
+}
+
+/// 返回一个计数器闭包：第一次调用返回 1，之后每次加 1
+///
+/// 提示：闭包修改了捕获的状态，所以它实现的是 `FnMut`
+pub fn make_counter() -> impl FnMut() -> u32 {
+    // TODO: 在闭包外声明一个可变的计数变量，并在闭包内递增它
+    || todo!()
+}
+
+/// 用 `f` 处理 `items` 中的每个元素，只保留结果为 `Some` 的值
+pub fn filter_map_all<T, U, F>(items: Vec<T>, f: F) -> Vec<U>
+where
+    F: FnMut(T) -> Option<U>,
+{
+    // TODO: 使用 into_iter().filter_map(f)
+    todo!()
+}
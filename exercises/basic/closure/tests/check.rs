@@ -0,0 +1,40 @@
+use exercise::*;
+
+#[test]
+fn apply_twice_calls_closure_twice() {
+    assert_eq!(apply_twice(|x| x + 3, 1), 7);
+    assert_eq!(apply_twice(|x| x * x, 3), 81);
+}
+
+#[test]
+fn make_adder_captures_n() {
+    let add_five = make_adder(5);
+    assert_eq!(add_five(1), 6);
+    assert_eq!(add_five(-5), 0);
+    assert_eq!(apply_twice(make_adder(10), 0), 20);
+}
+
+#[test]
+fn make_counter_keeps_state() {
+    let mut counter = make_counter();
+    assert_eq!(counter(), 1);
+    assert_eq!(counter(), 2);
+    assert_eq!(counter(), 3);
+
+    let mut other = make_counter();
+    assert_eq!(other(), 1);
+}
+
+#[test]
+fn filter_map_all_keeps_some() {
+    let parsed: Vec<i32> = filter_map_all(vec!["1", "x", "3"], |s| s.parse().ok());
+    assert_eq!(parsed, vec![1, 3]);
+
+    let mut calls = 0;
+    let doubled: Vec<i32> = filter_map_all(vec![1, 2, 3], |x| {
+        calls += 1;
+        (x % 2 == 1).then_some(x * 2)
+    });
+    assert_eq!(doubled, vec![2, 6]);
+    assert_eq!(calls, 3);
+}
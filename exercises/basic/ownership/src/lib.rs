@@ -0,0 +1,32 @@
+//! 练习：所有权与借用
+//!
+//! 完成下面带有 `TODO` 的函数，然后运行 `hello exercise ownership` 检查答案。
+//! 提示：注意每个函数的参数是获取所有权 (`String`) 还是借用 (`&str` / `&mut String`)。
+
+/// 获取 `s` 的所有权，在末尾追加 `"!"` 后把所有权交还给调用者
+///
+/// `shout(String::from("hello"))` 返回 `"hello!"`
+pub fn shout(s: String) -> String {
+    // TODO: 追加 "!" 并返回 s
+    todo!()
+}
+
+/// 只借用 `s`，返回其中第一个单词（以空格分隔）
+///
+/// `first_word("hello world")` 返回 `"hello"`；没有空格时返回整个字符串
+pub fn first_word(s: &str) -> &str {
+    // TODO: 找到第一个空格的位置，返回它之前的切片
+    todo!()
+}
+
+/// 通过可变借用，把 `s` 中的所有字母改为大写
+pub fn make_uppercase(s: &mut String) {
+    // TODO: 用 to_uppercase 生成新字符串，再赋值给 *s
+    todo!()
+}
+
+/// 返回两个字符串切片中较长的一个（长度相同时返回 `a`）
+pub fn longest<'a>(a: &'a str, b: &'a str) -> &'a str {
+    // TODO: 比较 len() 并返回较长者
+    todo!()
+}
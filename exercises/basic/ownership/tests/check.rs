@@ -0,0 +1,32 @@
+use exercise::*;
+
+#[test]
+fn shout_returns_ownership() {
+    let s = String::from("hello");
+    let s = shout(s);
+    assert_eq!(s, "hello!");
+}
+
+#[test]
+fn first_word_borrows() {
+    let text = String::from("hello world");
+    assert_eq!(first_word(&text), "hello");
+    assert_eq!(first_word("rust"), "rust");
+    assert_eq!(first_word(""), "");
+    // text is still usable: first_word only borrowed it
+    assert_eq!(text.len(), 11);
+}
+
+#[test]
+fn make_uppercase_mutates_in_place() {
+    let mut s = String::from("Ferris");
+    make_uppercase(&mut s);
+    assert_eq!(s, "FERRIS");
+}
+
+#[test]
+fn longest_prefers_first_on_tie() {
+    assert_eq!(longest("abc", "de"), "abc");
+    assert_eq!(longest("ab", "cde"), "cde");
+    assert_eq!(longest("ab", "cd"), "ab");
+}
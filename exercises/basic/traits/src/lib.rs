@@ -0,0 +1,54 @@
+//! 练习：Trait 定义、实现与多态
+//!
+//! 完成下面带有 `TODO` 的部分，然后运行 `hello exercise traits` 检查答案。
+
+/// 可以计算面积的几何图形
+pub trait Shape {
+    /// 图形的面积
+    fn area(&self) -> f64;
+
+    /// 图形的名称，默认为 "shape"
+    fn name(&self) -> String {
+        "shape".to_string()
+    }
+}
+
+/// 圆
+pub struct Circle {
+    pub radius: f64,
+}
+
+/// 正方形
+pub struct Square {
+    pub side: f64,
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        // TODO: 使用 std::f64::consts::PI 计算 π * r²
+        todo!()
+    }
+
+    // TODO: 覆盖默认实现，返回 "circle"
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        // TODO: 边长的平方
+        todo!()
+    }
+
+    // Square 使用 name() 的默认实现
+}
+
+/// 计算一组图形的总面积（通过 trait 对象实现多态）
+pub fn total_area(shapes: &[Box<dyn Shape>]) -> f64 {
+    // TODO: 对每个图形调用 area() 并求和
+    todo!()
+}
+
+/// 返回面积最大的图形的名称；列表为空时返回 None
+pub fn largest(shapes: &[Box<dyn Shape>]) -> Option<String> {
+    // TODO: 使用 max_by 和 f64::total_cmp 比较面积
+    todo!()
+}
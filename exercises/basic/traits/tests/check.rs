@@ -0,0 +1,40 @@
+use exercise::*;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn circle_area_and_name() {
+    let circle = Circle { radius: 2.0 };
+    assert!(close(circle.area(), std::f64::consts::PI * 4.0));
+    assert_eq!(circle.name(), "circle");
+}
+
+#[test]
+fn square_uses_default_name() {
+    let square = Square { side: 3.0 };
+    assert!(close(square.area(), 9.0));
+    assert_eq!(square.name(), "shape");
+}
+
+#[test]
+fn total_area_of_trait_objects() {
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Square { side: 2.0 }),
+        Box::new(Circle { radius: 1.0 }),
+    ];
+    assert!(close(total_area(&shapes), 4.0 + std::f64::consts::PI));
+    assert!(close(total_area(&[]), 0.0));
+}
+
+#[test]
+fn largest_shape() {
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Square { side: 1.0 }),
+        Box::new(Circle { radius: 1.0 }),
+        Box::new(Square { side: 1.5 }),
+    ];
+    assert_eq!(largest(&shapes).as_deref(), Some("circle"));
+    assert_eq!(largest(&[]), None);
+}
@@ -2,7 +2,6 @@ use axum::{response::IntoResponse, routing::get, Json, Router};
 use hyper::StatusCode;
 use serde::{Deserialize, Serialize};
 
-#[tokio::main]
 async fn axum_simple_sample() {
     // build our application with a single route
     let app = Router::new().route("/", get(|| async { "Hello, World!" }));
@@ -12,7 +11,6 @@ async fn axum_simple_sample() {
     axum::serve(listener, app).await.unwrap();
 }
 
-#[tokio::main]
 async fn axum_grace_shutdown_sample() {
     // build our application with a single route
     let app = Router::new().route("/", get(|| async { "Hello, World!" }));
@@ -31,7 +29,6 @@ async fn axum_grace_shutdown_sample() {
     println!("axum server shutdown done");
 }
 
-#[tokio::main]
 async fn axum_service_sample() {
     // our router
     let app = Router::new()
@@ -119,20 +116,20 @@ mod tests {
     use super::*;
 
     #[ignore]
-    #[test]
-    fn test_axum_sample_main() {
-        axum_simple_sample();
+    #[tokio::test]
+    async fn test_axum_sample_main() {
+        axum_simple_sample().await;
     }
 
     #[ignore]
-    #[test]
-    fn test_axum_service_sample_main() {
-        axum_service_sample();
+    #[tokio::test]
+    async fn test_axum_service_sample_main() {
+        axum_service_sample().await;
     }
 
     #[ignore]
-    #[test]
-    fn test_axum_grace_shutdown_sample() {
-        axum_grace_shutdown_sample();
+    #[tokio::test]
+    async fn test_axum_grace_shutdown_sample() {
+        axum_grace_shutdown_sample().await;
     }
 }
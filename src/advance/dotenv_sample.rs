@@ -5,15 +5,15 @@
 ///
 use dotenvy;
 use home;
+use anyhow::{Context, Result};
 use std::env::{self, home_dir};
-use std::error::Error;
 
 /// dotenv_sample
 /// use dotenvy crate to load environment variables from .env file.
-pub(crate) fn dotenv_sample() -> Result<(), Box<dyn Error>> {
+pub(crate) fn dotenv_sample() -> Result<()> {
     // Load environment variables from .env file.
     // Fails if .env file not found, not readable or invalid.
-    dotenvy::dotenv()?;
+    dotenvy::dotenv().context("Failed to load the .env file")?;
 
     // Iterate over all environment variables
     for (key, value) in env::vars() {
@@ -21,11 +21,13 @@ pub(crate) fn dotenv_sample() -> Result<(), Box<dyn Error>> {
     }
 
     // 获取 HOME 环境变量
-    let home = dotenvy::var("HOME")?;
+    let home = dotenvy::var("HOME").context("HOME is not set")?;
     println!("HOME: {}", home);
 
     // 获取 CARGO_MANIFEST_DIR 环境变量
-    let cargo_home = env::var("CARGO_MANIFEST_DIR")?;
+    // 仅在 `cargo run` 下由 cargo 设置；直接运行二进制时会返回错误
+    let cargo_home = env::var("CARGO_MANIFEST_DIR")
+        .context("CARGO_MANIFEST_DIR is only set when run through cargo")?;
     println!("CARGO_MANIFEST_DIR: {}", cargo_home);
 
     // 获取 Cargo.toml 所在的目录
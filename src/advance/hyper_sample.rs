@@ -61,7 +61,6 @@ fn empty() -> BoxBody<Bytes, hyper::Error> {
         .boxed()
 }
 
-#[tokio::main]
 async fn hyper_hello_sample() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
 
@@ -100,8 +99,8 @@ mod tests {
     use super::*;
 
     #[ignore]
-    #[test]
-    fn test_hyper_sample() {
-        hyper_hello_sample();
+    #[tokio::test]
+    async fn test_hyper_sample() {
+        hyper_hello_sample().await.unwrap();
     }
 }
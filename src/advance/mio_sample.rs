@@ -232,6 +232,11 @@ fn run_client(address: &str, message: &str) -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+/// Echo server entry point for `hello serve advance mio-server`
+pub(crate) fn mio_server_main() {
+    run_server("127.0.0.1:8081").unwrap();
+}
+
 fn mio_sample_main(is_server: bool) -> Result<(), Box<dyn std::error::Error>> {
     // Example of running the server with a default address
     let address = "127.0.0.1:8080";
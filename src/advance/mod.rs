@@ -86,7 +86,8 @@ mod macros_sample;
 /// cow sample
 mod cow_sample;
 
-use crate::cli::registry::{SampleType, Topic};
+use crate::cli::doctor;
+use crate::cli::registry::{Category, Dependency, SampleType, Topic};
 
 pub(crate) fn advance_sample() {
     //JSON 序列化及反序列化解析
@@ -120,20 +121,41 @@ pub(crate) fn advance_sample() {
     // ollama_sample::ollama_sample();
 
     //sqlx
-    sqlx_sample::sqlx_sqlite_example().unwrap();
+    crate::cli::runtime::runtime()
+        .block_on(sqlx_sample::sqlx_sqlite_example())
+        .unwrap();
 }
 
 // Register all advance topics with inventory
+// Register the advance category with inventory
+inventory::submit! {
+    Category::new(
+        "advance",
+        "Rust 进阶 (Advance)",
+        20,
+        "进阶模式（异步、数据库、Web 等）",
+    )
+    .with_english(
+        "Rust Advanced",
+        "Advanced patterns (async, databases, web, ...)",
+    )
+}
+
 inventory::submit! {
     Topic::new(
         "json",
         "JSON 序列化及反序列化",
         "advance",
-        json_sample::json_process_sample,
+        || {
+            json_sample::json_process_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/json.md"),
+        Some("advance/data/json.md"),
         SampleType::Function,
     )
+    .with_english("JSON serialization and deserialization")
+    .with_sample_source("src/advance/json_sample.rs", "json_process_sample")
 }
 
 inventory::submit! {
@@ -141,11 +163,16 @@ inventory::submit! {
         "tempfile",
         "临时文件和目录创建",
         "advance",
-        tempfile_sample::tempfile_sample,
+        || {
+            tempfile_sample::tempfile_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/tempfile.md"),
+        Some("advance/system/tempfile.md"),
         SampleType::Function,
     )
+    .with_english("Creating temporary files and directories")
+    .with_sample_source("src/advance/tempfile_sample.rs", "tempfile_sample")
 }
 
 inventory::submit! {
@@ -153,11 +180,16 @@ inventory::submit! {
         "memmap",
         "内存映射文件操作",
         "advance",
-        memmap_sample::memmap_file_sample,
+        || {
+            memmap_sample::memmap_file_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/memmap.md"),
+        Some("advance/system/memmap.md"),
         SampleType::Function,
     )
+    .with_english("Memory-mapped file I/O")
+    .with_sample_source("src/advance/memmap_sample.rs", "memmap_file_sample")
 }
 
 inventory::submit! {
@@ -165,11 +197,17 @@ inventory::submit! {
         "futures",
         "异步 Future 基础",
         "advance",
-        futures_sample::futures_block_sample,
+        || {
+            futures_sample::futures_block_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/futures.md"),
+        Some("advance/async/futures.md"),
         SampleType::Function,
     )
+    .with_english("Async Future basics")
+    .with_sample_source("src/advance/futures_sample.rs", "futures_block_sample")
+    .with_prerequisites(&["basic/threads"])
 }
 
 inventory::submit! {
@@ -177,11 +215,16 @@ inventory::submit! {
         "bytes",
         "字节缓冲区、base64、位操作",
         "advance",
-        bytes_sample::bytes_create,
+        || {
+            bytes_sample::bytes_create();
+            Ok(())
+        },
         &[],
-        Some("advance/bytes.md"),
+        Some("advance/system/bytes.md"),
         SampleType::Function,
     )
+    .with_english("Byte buffers, base64, bit operations")
+    .with_sample_source("src/advance/bytes_sample.rs", "bytes_create")
 }
 
 inventory::submit! {
@@ -189,11 +232,16 @@ inventory::submit! {
         "include-dir",
         "编译时文件嵌入",
         "advance",
-        includedir_sample::include_dir_sample,
+        || {
+            includedir_sample::include_dir_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/include-dir.md"),
+        Some("advance/system/includedir.md"),
         SampleType::Function,
     )
+    .with_english("Embedding files at compile time")
+    .with_sample_source("src/advance/includedir_sample.rs", "include_dir_sample")
 }
 
 inventory::submit! {
@@ -201,11 +249,14 @@ inventory::submit! {
         "dotenv",
         "环境变量加载",
         "advance",
-        || dotenv_sample::dotenv_sample().unwrap(),
+        dotenv_sample::dotenv_sample,
         &[],
-        Some("advance/dotenv.md"),
+        Some("advance/system/dotenv.md"),
         SampleType::Function,
     )
+    .with_english("Loading environment variables")
+    .with_sample_source("src/advance/dotenv_sample.rs", "dotenv_sample")
+    .with_volatile_output()
 }
 
 inventory::submit! {
@@ -213,11 +264,16 @@ inventory::submit! {
         "rkyv",
         "零拷贝序列化",
         "advance",
-        rkyv_sample::rkyv_basic_serialize_sample,
+        || {
+            rkyv_sample::rkyv_basic_serialize_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/rkyv.md"),
+        Some("advance/data/rkyv.md"),
         SampleType::Function,
     )
+    .with_english("Zero-copy serialization")
+    .with_sample_source("src/advance/rkyv_sample.rs", "rkyv_basic_serialize_sample")
 }
 
 inventory::submit! {
@@ -225,11 +281,13 @@ inventory::submit! {
         "csv",
         "CSV 解析",
         "advance",
-        || csv_sample::csv_sample().unwrap(),
+        csv_sample::csv_sample,
         &[],
-        Some("advance/csv.md"),
+        Some("advance/data/csv.md"),
         SampleType::Function,
     )
+    .with_english("CSV parsing")
+    .with_sample_source("src/advance/csv_sample.rs", "csv_sample")
 }
 
 inventory::submit! {
@@ -237,11 +295,16 @@ inventory::submit! {
         "getset",
         "派生宏生成 getter/setter",
         "advance",
-        getset_sample::getset_sample,
+        || {
+            getset_sample::getset_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/getset.md"),
+        Some("advance/testing/getset.md"),
         SampleType::Function,
     )
+    .with_english("Deriving getters/setters with macros")
+    .with_sample_source("src/advance/getset_sample.rs", "getset_sample")
 }
 
 inventory::submit! {
@@ -249,11 +312,17 @@ inventory::submit! {
         "sysinfo",
         "系统信息收集",
         "advance",
-        sysinfo_sample::sysinfo_sample,
+        || {
+            sysinfo_sample::sysinfo_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/sysinfo.md"),
+        Some("advance/system/sysinfo.md"),
         SampleType::Function,
     )
+    .with_english("Collecting system information")
+    .with_sample_source("src/advance/sysinfo_sample.rs", "sysinfo_sample")
+    .with_volatile_output()
 }
 
 inventory::submit! {
@@ -261,11 +330,17 @@ inventory::submit! {
         "rayon",
         "数据并行化",
         "advance",
-        rayon_sample::rayon_sample,
+        || {
+            rayon_sample::rayon_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/rayon.md"),
+        Some("advance/async/rayon.md"),
         SampleType::Function,
     )
+    .with_english("Data parallelism")
+    .with_sample_source("src/advance/rayon_sample.rs", "rayon_sample")
+    .with_prerequisites(&["basic/threads"])
 }
 
 inventory::submit! {
@@ -273,11 +348,17 @@ inventory::submit! {
         "cycle-rc",
         "引用循环处理",
         "advance",
-        cyclerc_sample::cycle_weak_sample,
+        || {
+            cyclerc_sample::cycle_weak_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/cycle-rc.md"),
+        Some("advance/async/cyclerc.md"),
         SampleType::Function,
     )
+    .with_english("Handling reference cycles")
+    .with_sample_source("src/advance/cyclerc_sample.rs", "cycle_weak_sample")
+    .with_prerequisites(&["basic/ownership", "basic/pointer"])
 }
 
 inventory::submit! {
@@ -285,11 +366,16 @@ inventory::submit! {
         "type-alias",
         "类型别名模式",
         "advance",
-        typealias_sample::typealias_sample,
+        || {
+            typealias_sample::typealias_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/type-alias.md"),
+        Some("advance/testing/typealias.md"),
         SampleType::Function,
     )
+    .with_english("Type alias patterns")
+    .with_sample_source("src/advance/typealias_sample.rs", "typealias_sample")
 }
 
 inventory::submit! {
@@ -297,11 +383,13 @@ inventory::submit! {
         "process",
         "进程管理",
         "advance",
-        || process_sample::process_getpid_sample().unwrap(),
+        || Ok(process_sample::process_getpid_sample()?),
         &[],
-        Some("advance/process.md"),
+        Some("advance/system/process.md"),
         SampleType::Function,
     )
+    .with_english("Process management")
+    .with_sample_source("src/advance/process_sample.rs", "process_getpid_sample")
 }
 
 inventory::submit! {
@@ -309,11 +397,16 @@ inventory::submit! {
         "macros",
         "声明式和过程宏",
         "advance",
-        macros_sample::declare_macros_hello_sample,
+        || {
+            macros_sample::declare_macros_hello_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/macros.md"),
+        Some("advance/testing/macros.md"),
         SampleType::Function,
     )
+    .with_english("Declarative and procedural macros")
+    .with_sample_source("src/advance/macros_sample.rs", "declare_macros_hello_sample")
 }
 
 inventory::submit! {
@@ -321,23 +414,47 @@ inventory::submit! {
         "cow",
         "写时克隆模式",
         "advance",
-        cow_sample::cow_reader_sample,
+        || {
+            cow_sample::cow_reader_sample();
+            Ok(())
+        },
         &[],
-        Some("advance/cow.md"),
+        Some("advance/system/cow.md"),
         SampleType::Function,
     )
+    .with_english("Clone-on-write patterns")
+    .with_sample_source("src/advance/cow_sample.rs", "cow_reader_sample")
 }
 
 inventory::submit! {
-    Topic::new(
+    Topic::new_async(
         "sqlx",
         "异步数据库查询 (SQLite)",
         "advance",
-        || sqlx_sample::sqlx_sqlite_example().unwrap(),
-        &["SQLite"],
-        Some("advance/sqlx.md"),
+        || Box::pin(async { Ok(sqlx_sample::sqlx_sqlite_example().await?) }),
+        &[Dependency::Sqlite],
+        Some("advance/database/sqlx.md"),
         SampleType::Function,
     )
+    .with_english("Async database queries (SQLite)")
+    .with_sample_source("src/advance/sqlx_sample.rs", "sqlx_sqlite_example")
+    .with_timeout(30)
+}
+
+inventory::submit! {
+    Topic::new_async(
+        "sqlx-mysql",
+        "异步数据库查询 (MySQL)",
+        "advance",
+        || Box::pin(async { Ok(sqlx_sample::sqlx_mysql_example().await?) }),
+        &[Dependency::MySql],
+        Some("advance/database/sqlx.md"),
+        SampleType::Function,
+    )
+    .with_english("Async database queries (MySQL)")
+    .with_sample_source("src/advance/sqlx_sample.rs", "sqlx_mysql_example")
+    .with_prerequisites(&["sqlx"])
+    .with_timeout(30)
 }
 
 inventory::submit! {
@@ -345,21 +462,89 @@ inventory::submit! {
         "diesel",
         "Diesel ORM (SQLite)",
         "advance",
-        diesel_sample::diesel_sample,
-        &["SQLite"],
-        Some("advance/diesel.md"),
+        || {
+            diesel_sample::diesel_sample();
+            Ok(())
+        },
+        &[Dependency::Sqlite],
+        Some("advance/database/diesel.md"),
         SampleType::Function,
     )
+    .with_english("Diesel ORM (SQLite)")
+    .with_sample_source("src/advance/diesel_sample.rs", "diesel_sample")
 }
 
 inventory::submit! {
-    Topic::new(
+    Topic::new_async(
         "ollama",
         "Ollama LLM 集成",
         "advance",
-        || ollama_sample::ollama_chat_sample().unwrap(),
-        &["Ollama"],
-        Some("advance/ollama.md"),
+        || {
+            Box::pin(async {
+                let url = doctor::http_url(&doctor::endpoint(Dependency::Ollama));
+                ollama_sample::ollama_chat_sample(&url).await
+            })
+        },
+        &[Dependency::Ollama],
+        Some("advance/web/ollama.md"),
+        SampleType::Function,
+    )
+    .with_english("Ollama LLM integration")
+    .with_sample_source("src/advance/ollama_sample.rs", "ollama_chat_sample")
+    .with_timeout(300)
+}
+
+inventory::submit! {
+    Topic::new_async(
+        "tokio-server",
+        "tokio 异步 TCP 回显服务",
+        "advance",
+        || Box::pin(tokio_sample::tokio_server_main()),
+        &[],
+        Some("advance/async/tokio.md"),
+        SampleType::Server,
+    )
+    .with_english("tokio async TCP echo server")
+    .with_sample_source("src/advance/tokio_sample.rs", "tokio_server_main")
+    .with_port(8080)
+    .with_prerequisites(&["futures"])
+}
+
+inventory::submit! {
+    Topic::new_async(
+        "tokio-sync",
+        "tokio 通道、读写锁与 spawn_blocking",
+        "advance",
+        || {
+            Box::pin(async {
+                tokio_sample::tokio_sync_sample().await;
+                Ok(())
+            })
+        },
+        &[],
+        Some("advance/async/tokio.md"),
         SampleType::Function,
     )
+    .with_english("tokio channels, RwLock and spawn_blocking")
+    .with_sample_source("src/advance/tokio_sample.rs", "tokio_sync_sample")
+    .with_volatile_output()
+    .with_timeout(30)
+}
+
+inventory::submit! {
+    Topic::new(
+        "mio-server",
+        "mio 事件驱动 TCP 回显服务",
+        "advance",
+        || {
+            mio_sample::mio_server_main();
+            Ok(())
+        },
+        &[],
+        Some("advance/async/mio.md"),
+        SampleType::Server,
+    )
+    .with_english("mio event-driven TCP echo server")
+    .with_sample_source("src/advance/mio_sample.rs", "mio_server_main")
+    .with_port(8081)
 }
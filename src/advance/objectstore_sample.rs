@@ -10,7 +10,6 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::io::AsyncReadExt;
 
-#[tokio::main]
 async fn objectstore_simple() -> Result<()> {
     // 1. Initialize an ObjectStore (using LocalFileSystem for this example)
     // 获取临时目录
@@ -100,8 +99,8 @@ mod tests {
     use super::*;
 
     #[ignore]
-    #[test]
-    fn test_objectstore_simple() {
-        objectstore_simple().unwrap();
+    #[tokio::test]
+    async fn test_objectstore_simple() {
+        objectstore_simple().await.unwrap();
     }
 }
@@ -7,11 +7,15 @@ use ollama_rs::{
 };
 use tokio::io;
 
+/// 连接 `url` 上的 Ollama 服务，例如 "http://127.0.0.1:11434"
+fn client(url: &str) -> io::Result<Ollama> {
+    Ollama::try_new(url).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
 /// 基础聊天机器人示例
-/// 使用默认 Ollama 服务 (localhost:11434)
-#[tokio::main]
-pub async fn ollama_chat_sample() -> io::Result<()> {
-    let ollama = Ollama::default();
+/// 使用 `url` 上的 Ollama 服务（默认 http://127.0.0.1:11434）
+pub async fn ollama_chat_sample(url: &str) -> anyhow::Result<()> {
+    let ollama = client(url)?;
     let model = "llama3.2:latest".to_string();
     let prompt = "Why is the sky blue? Explain in simple terms.".to_string();
 
@@ -27,9 +31,8 @@ pub async fn ollama_chat_sample() -> io::Result<()> {
             println!("总耗时：{:?}ns", res.total_duration);
             println!("评估计数：{}", res.eval_count.unwrap_or(0));
         }
-        Err(e) => {
-            eprintln!("生成失败：{}", e);
-        }
+        // 把错误交给调用方，由 CLI 打印完整的错误链
+        Err(e) => return Err(anyhow::Error::new(e).context("生成失败")),
     }
 
     Ok(())
@@ -37,9 +40,8 @@ pub async fn ollama_chat_sample() -> io::Result<()> {
 
 /// 多轮对话示例
 /// 展示如何在对话中保持上下文
-#[tokio::main]
-pub async fn ollama_conversation_sample() -> io::Result<()> {
-    let ollama = Ollama::default();
+pub async fn ollama_conversation_sample(url: &str) -> io::Result<()> {
+    let ollama = client(url)?;
     let model = "llama3.2:latest".to_string();
 
     // 第一轮对话
@@ -69,9 +71,8 @@ pub async fn ollama_conversation_sample() -> io::Result<()> {
 
 /// 带图片的多模态示例
 /// 使用支持视觉的模型分析图片
-#[tokio::main]
-pub async fn ollama_vision_sample() -> io::Result<()> {
-    let ollama = Ollama::default();
+pub async fn ollama_vision_sample(url: &str) -> io::Result<()> {
+    let ollama = client(url)?;
     let model = "llava:latest".to_string();
 
     // 注意：需要实际图片文件路径
@@ -88,11 +89,10 @@ pub async fn ollama_vision_sample() -> io::Result<()> {
 
 /// 流式响应示例
 /// 逐 token 输出，适合长文本生成
-#[tokio::main]
-pub async fn ollama_stream_sample() -> io::Result<()> {
+pub async fn ollama_stream_sample(url: &str) -> io::Result<()> {
     use tokio_stream::StreamExt;
 
-    let ollama = Ollama::default();
+    let ollama = client(url)?;
     let model = "llama3.2:latest".to_string();
     let prompt = "Write a short poem about programming in Rust.".to_string();
 
@@ -124,9 +124,8 @@ pub async fn ollama_stream_sample() -> io::Result<()> {
 
 /// 错误处理示例
 /// 展示如何处理各种 Ollama 错误
-#[tokio::main]
-pub async fn ollama_error_handling_sample() -> io::Result<()> {
-    let ollama = Ollama::default();
+pub async fn ollama_error_handling_sample(url: &str) -> io::Result<()> {
+    let ollama = client(url)?;
 
     // 测试不存在的模型
     let model = "nonexistent_model".to_string();
@@ -158,20 +157,27 @@ pub async fn ollama_error_handling_sample() -> io::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::doctor;
+    use crate::cli::registry::Dependency;
+
+    /// Ollama 服务地址，与 `hello doctor` 探测的一致
+    fn url() -> String {
+        doctor::http_url(&doctor::endpoint(Dependency::Ollama))
+    }
 
     #[ignore]
-    #[test]
-    fn test_ollama_chat() {
+    #[tokio::test]
+    async fn test_ollama_chat() {
         // 需要本地运行 Ollama 服务
-        let result = ollama_chat_sample();
+        let result = ollama_chat_sample(&url()).await;
         assert!(result.is_ok());
     }
 
     #[ignore]
-    #[test]
-    fn test_ollama_error_handling() {
+    #[tokio::test]
+    async fn test_ollama_error_handling() {
         // 测试错误处理
-        let result = ollama_error_handling_sample();
+        let result = ollama_error_handling_sample(&url()).await;
         assert!(result.is_ok());
     }
 }
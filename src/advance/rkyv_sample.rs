@@ -197,16 +197,6 @@ pub fn rkyv_validation_sample() {
     println!("✓ 数据验证成功\n");
 }
 
-/// 主函数：运行所有示例
-#[tokio::main]
-async fn main() {
-    rkyv_basic_serialize_sample();
-    rkyv_custom_allocator_sample();
-    rkyv_nested_struct_sample();
-    rkyv_performance_sample();
-    rkyv_validation_sample();
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -18,7 +18,6 @@ struct User {
     email: String,
 }
 /// sqlx_sqlite_example
-#[tokio::main(flavor = "current_thread")]
 pub(crate) async fn sqlx_sqlite_example() -> Result<(), sqlx::Error> {
     // 从环境变量中获取数据库 URL
     let db_url = "sqlite::memory:";
@@ -82,7 +81,6 @@ pub(crate) async fn sqlx_sqlite_example() -> Result<(), sqlx::Error> {
 }
 
 /// sqlx_mysql_example
-#[tokio::main(flavor = "current_thread")]
 pub(crate) async fn sqlx_mysql_example() -> Result<(), sqlx::Error> {
     dotenvy::dotenv();
 
@@ -136,7 +134,6 @@ pub(crate) async fn sqlx_mysql_example() -> Result<(), sqlx::Error> {
 }
 
 /// sqlx_mysql_example
-#[tokio::main(flavor = "current_thread")]
 pub(crate) async fn sqlx_mysql_transaction_example() -> Result<(), sqlx::Error> {
     dotenvy::dotenv();
 
@@ -167,7 +164,6 @@ pub(crate) async fn sqlx_mysql_transaction_example() -> Result<(), sqlx::Error>
 }
 
 /// sqlx_mysql_example
-#[tokio::main(flavor = "current_thread")]
 pub(crate) async fn sqlx_mysql_transaction_template_example() -> Result<(), sqlx::Error> {
     Ok(())
 }
@@ -183,26 +179,26 @@ mod tests {
     // 注意这个惯用法：在 tests 模块中，从外部作用域导入所有名字。
     use super::*;
 
-    #[test]
-    fn test_sqlx_sqlite_example() {
-        sqlx_sqlite_example();
+    #[tokio::test]
+    async fn test_sqlx_sqlite_example() {
+        sqlx_sqlite_example().await.unwrap();
     }
 
     #[ignore = "reason: mysql connection is not available in CI"]
-    #[test]
-    fn test_sqlx_mysql_example() {
-        sqlx_mysql_example();
+    #[tokio::test]
+    async fn test_sqlx_mysql_example() {
+        sqlx_mysql_example().await.unwrap();
     }
 
     #[ignore = "reason: mysql connection is not available in CI"]
-    #[test]
-    fn test_sqlx_mysql_transaction_example() {
-        sqlx_mysql_transaction_example();
+    #[tokio::test]
+    async fn test_sqlx_mysql_transaction_example() {
+        sqlx_mysql_transaction_example().await.unwrap();
     }
 
     #[ignore = "reason: mysql connection is not available in CI"]
-    #[test]
-    fn test_sqlx_mysql_transaction_template_example() {
-        sqlx_mysql_transaction_template_example();
+    #[tokio::test]
+    async fn test_sqlx_mysql_transaction_template_example() {
+        sqlx_mysql_transaction_template_example().await.unwrap();
     }
 }
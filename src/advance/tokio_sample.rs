@@ -10,7 +10,7 @@ use tokio::sync::{mpsc, oneshot, RwLock};
 use tokio::task;
 
 // #[tokio::main]
-pub(crate) async fn tokio_server_main() -> Result<(), Box<dyn std::error::Error>> {
+pub(crate) async fn tokio_server_main() -> anyhow::Result<()> {
     println!(" tokio_server_main ...");
 
     let listener = TcpListener::bind("127.0.0.1:8080").await?;
@@ -88,7 +88,6 @@ pub(crate) async fn tokio_client_main() -> io::Result<()> {
 }
 
 /// tokio client 示例
-#[tokio::main]
 pub(crate) async fn tokio_client_sample() -> io::Result<()> {
     let mut running = AtomicBool::new(true);
 
@@ -117,9 +116,16 @@ pub(crate) async fn tokio_client_sample() -> io::Result<()> {
     Ok(())
 }
 
+/// tokio 同步原语示例：依次运行通道、读写锁与 spawn_blocking 示例
+pub(crate) async fn tokio_sync_sample() {
+    tokio_mpsc_sample().await;
+    tokio_oneshot_sample().await;
+    tokio_rwlock_basic_sample().await;
+    tokio_task_blocking_example().await;
+}
+
 /// tokio::sync::mpsc 示例
 /// 通过创建单个异步任务，实现并发处理
-#[tokio::main]
 async fn tokio_mpsc_sample() {
     // 创建一个异步通道，并指定缓冲区大小（例如 100）
     let (tx, mut rx) = mpsc::channel(100);
@@ -145,7 +151,6 @@ async fn tokio_mpsc_sample() {
 
 /// 多任务并发处理示例
 /// 通过创建多个异步任务，实现并发处理
-#[tokio::main]
 async fn tokio_mpsc_multitask_sample() {
     // 创建一个异步通道，并指定缓冲区大小（例如 100）
     let (tx, mut rx) = mpsc::channel(10);
@@ -179,7 +184,6 @@ async fn tokio_mpsc_multitask_sample() {
 
 /// tokio::sync::oneshot 示例
 /// 通过创建单个异步任务，实现并发处理
-#[tokio::main]
 async fn tokio_oneshot_sample() {
     // 创建一个异步通道，并指定缓冲区大小（例如 100）
     let (tx, mut rx) = oneshot::channel();
@@ -203,7 +207,6 @@ async fn tokio_oneshot_sample() {
 }
 
 /// tokio::sync::RwLock 示例
-#[tokio::main]
 async fn tokio_rwlock_basic_sample() {
     // 创建一个 RwLock 包装的共享数据
     let data = Arc::new(RwLock::new(0));
@@ -250,7 +253,6 @@ async fn tokio_rwlock_basic_sample() {
 
 /// tokio::sync::RwLock 示例 , 读写锁的复杂示例，使用rand生成随机数,
 /// 模拟多个线程同时读写数据并发操作。
-#[tokio::main]
 async fn tokio_rwlock_complex_sample() {
     let data = Arc::new(RwLock::new(0));
     let num_tasks = 10;
@@ -295,7 +297,6 @@ async fn tokio_rwlock_complex_sample() {
 }
 
 /// tokio::sync::RwLock 示例 , 读写锁的复杂示例，使用atomic进行原子操作计数取模，进行随机打散模拟读写操作。
-#[tokio::main]
 async fn tokio_rwlock_complex_atomic_sample() {
     let data = Arc::new(RwLock::new(0));
     let num_tasks = 10;
@@ -335,7 +336,6 @@ async fn tokio_rwlock_complex_atomic_sample() {
 }
 
 /// tokio::task::spawn random numbers and print them in a separate task
-#[tokio::main]
 async fn tokio_random_sample() {
     // 在闭包外部创建 rng，这是错误的！
     // let mut rng = rand::thread_rng; // 移到闭包内部！！！
@@ -368,7 +368,6 @@ async fn tokio_random_sample() {
 }
 
 /// tokio::task::spawn_blocking 示例. 使用Parking_lot库的RwLock来实现线程安全的读写操作.
-#[tokio::main]
 async fn tokio_task_blocking_example() {
     // 创建一个 RwLock 包装的共享数据结构体
     let data = Arc::new(parking_lot::RwLock::new(0));
@@ -461,49 +460,49 @@ mod tests {
         tokio_client_main();
     }
 
-    #[test]
-    fn test_fetures_tokio_mpsc() {
-        tokio_mpsc_sample();
+    #[tokio::test]
+    async fn test_fetures_tokio_mpsc() {
+        tokio_mpsc_sample().await;
     }
 
-    #[test]
-    fn test_fetures_tokio_mpsc_multitask() {
-        tokio_mpsc_multitask_sample();
+    #[tokio::test]
+    async fn test_fetures_tokio_mpsc_multitask() {
+        tokio_mpsc_multitask_sample().await;
     }
 
-    #[test]
-    fn test_fetures_tokio_mpsc_multitask2() {
-        tokio_mpsc_multitask_sample();
+    #[tokio::test]
+    async fn test_fetures_tokio_mpsc_multitask2() {
+        tokio_mpsc_multitask_sample().await;
     }
 
-    #[test]
-    fn test_fetures_tokio_oneshot() {
-        tokio_oneshot_sample();
+    #[tokio::test]
+    async fn test_fetures_tokio_oneshot() {
+        tokio_oneshot_sample().await;
     }
 
-    #[test]
-    fn test_features_rwlock_basic() {
-        tokio_rwlock_basic_sample();
+    #[tokio::test]
+    async fn test_features_rwlock_basic() {
+        tokio_rwlock_basic_sample().await;
     }
 
-    #[test]
-    fn test_features_rwlock_complex() {
-        tokio_rwlock_complex_sample();
+    #[tokio::test]
+    async fn test_features_rwlock_complex() {
+        tokio_rwlock_complex_sample().await;
     }
 
-    #[test]
-    fn test_features_rwlock_complex_atomic() {
-        tokio_rwlock_complex_atomic_sample();
+    #[tokio::test]
+    async fn test_features_rwlock_complex_atomic() {
+        tokio_rwlock_complex_atomic_sample().await;
     }
 
-    #[test]
-    fn test_features_random_sample() {
-        tokio_random_sample();
+    #[tokio::test]
+    async fn test_features_random_sample() {
+        tokio_random_sample().await;
     }
 
     /// .
-    #[test]
-    fn test_features_task_blocking_example() {
-        tokio_task_blocking_example();
+    #[tokio::test]
+    async fn test_features_task_blocking_example() {
+        tokio_task_blocking_example().await;
     }
 }
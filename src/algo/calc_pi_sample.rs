@@ -2,6 +2,8 @@ use bigdecimal::BigDecimal;
 use num_bigfloat::BigFloat;
 use rayon::prelude::*;
 
+use crate::cli::registry::ArgValues;
+
 /// 使用莱布尼茨公式计算 PI
 /// - Parameter steps: 迭代次数
 fn calculate_pi(steps: usize) -> f64 {
@@ -21,13 +23,36 @@ fn calculate_pi(steps: usize) -> f64 {
     pi_over_four * 4.0
 }
 
+/// 默认迭代次数（`hello algo calc-pi --steps`）
+pub const DEFAULT_STEPS: &str = "1e7";
+
+/// 可选的计算方法（`hello algo calc-pi --method`）
+pub const METHODS: &[&str] = &["leibniz", "functional", "parallel", "bbp"];
+
+/// 使用默认参数计算 PI：莱布尼茨公式迭代 1000 万次
 pub fn calculate_pi_sample() {
-    // 注意：100亿次迭代在 Rust 中运行很快，但仍需一些时间
-    let iterations = 10_000_000_000;
-    let result = calculate_pi(iterations);
+    calculate_pi_with(10_000_000, "leibniz");
+}
+
+/// 命令行入口：`hello algo calc-pi --steps 1e9 --method parallel`
+pub fn calculate_pi_args(args: &ArgValues) -> anyhow::Result<()> {
+    calculate_pi_with(args.integer("steps") as usize, args.text("method"));
+    Ok(())
+}
 
-    println!("迭代 {} 次的结果: {}", iterations, result);
+/// 使用 `method` 迭代 `steps` 次计算 PI，并与标准值比较
+fn calculate_pi_with(steps: usize, method: &str) {
+    // 注意：迭代次数越多越精确，但 100 亿次迭代也需要一些时间
+    let result = match method {
+        "functional" => calculate_pi_functional(steps),
+        "parallel" => calculate_pi_parallel(steps),
+        "bbp" => calculate_pi_bbp(steps),
+        _ => calculate_pi(steps),
+    };
+
+    println!("使用 {} 方法迭代 {} 次的结果: {}", method, steps, result);
     println!("系统标准 PI 值: {}", std::f64::consts::PI);
+    println!("误差: {:e}", (result - std::f64::consts::PI).abs());
 }
 
 ///使用函数方式计算Pi值
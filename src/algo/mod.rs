@@ -6,3 +6,53 @@
 //链表数据结构
 mod calc_pi_sample;
 mod linked_list;
+
+use crate::cli::registry::{Category, SampleType, Topic, TopicArg};
+
+// Register the algo category with inventory
+inventory::submit! {
+    Category::new(
+        "algo",
+        "算法 (Algorithms)",
+        40,
+        "算法实现（链表、圆周率计算等）",
+    )
+    .with_english(
+        "Algorithms",
+        "Algorithm implementations (linked-list, pi calculation, ...)",
+    )
+}
+
+// Register all algo topics with inventory
+inventory::submit! {
+    Topic::new(
+        "calc-pi",
+        "计算圆周率：莱布尼茨级数、函数式、Rayon 并行、BBP 公式",
+        "algo",
+        || {
+            calc_pi_sample::calculate_pi_sample();
+            Ok(())
+        },
+        &[],
+        Some("algo/algo.md"),
+        SampleType::Function,
+    )
+    .with_english("Computing pi: Leibniz series, functional, Rayon parallel, BBP formula")
+    .with_sample_source("src/algo/calc_pi_sample.rs", "calculate_pi_sample")
+    .with_args(
+        &[
+            TopicArg::integer(
+                "steps",
+                calc_pi_sample::DEFAULT_STEPS,
+                "Number of series terms to sum",
+            ),
+            TopicArg::choice(
+                "method",
+                calc_pi_sample::METHODS,
+                "leibniz",
+                "Series or strategy used to compute PI",
+            ),
+        ],
+        calc_pi_sample::calculate_pi_args,
+    )
+}
@@ -3,20 +3,41 @@
 //! This module registers sample topics from the `awesome` workspace crate
 //! for the hello CLI system using inventory-based compile-time registration.
 
-use crate::cli::registry::{SampleType, Topic};
+use crate::cli::doctor;
+use crate::cli::registry::{Category, Dependency, SampleType, Topic, TopicArg};
 use awesome::mq;
 use awesome::services;
 
+// Register the awesome category with inventory
+inventory::submit! {
+    Category::new(
+        "awesome",
+        "生产级样例 (Awesome)",
+        30,
+        "生产级样例（gRPC、依赖注入、模板等）",
+    )
+    .with_english(
+        "Production-Grade Samples",
+        "Production-grade samples (gRPC, DI, templates, ...)",
+    )
+}
+
 inventory::submit! {
     Topic::new(
         "inventory",
         "编译时插件注册",
         "awesome",
-        services::inventory_sample::inventory_main,
+        || {
+            services::inventory_sample::inventory_main();
+            Ok(())
+        },
         &[],
-        Some("awesome/inventory.md"),
+        Some("awesome/plugin.md"),
         SampleType::Function,
     )
+    .with_english("Compile-time plugin registration")
+    .with_sample_source("crates/awesome/src/services/inventory_sample.rs", "inventory_main")
+    .with_volatile_output()
 }
 
 inventory::submit! {
@@ -24,11 +45,13 @@ inventory::submit! {
         "di-concrete",
         "依赖注入：具体类型",
         "awesome",
-        || services::concrete_injection_sample::dependency_injection_concrete_sample().unwrap(),
+        services::concrete_injection_sample::dependency_injection_concrete_sample,
         &[],
-        Some("awesome/di.md"),
+        Some("awesome/dependency_injection.md"),
         SampleType::Function,
     )
+    .with_english("Dependency injection: concrete types")
+    .with_sample_source("crates/awesome/src/services/concrete_injection_sample.rs", "dependency_injection_concrete_sample")
 }
 
 inventory::submit! {
@@ -36,11 +59,16 @@ inventory::submit! {
         "di-arc",
         "依赖注入：Arc trait 对象",
         "awesome",
-        services::dynmaic_injection_arc_sample::container_injection_main,
+        || {
+            services::dynmaic_injection_arc_sample::container_injection_main();
+            Ok(())
+        },
         &[],
-        Some("awesome/di.md"),
+        Some("awesome/dependency_injection.md"),
         SampleType::Function,
     )
+    .with_english("Dependency injection: Arc trait objects")
+    .with_sample_source("crates/awesome/src/services/dynmaic_injection_arc_sample.rs", "container_injection_main")
 }
 
 inventory::submit! {
@@ -48,11 +76,16 @@ inventory::submit! {
         "di-box",
         "依赖注入：Box trait 对象",
         "awesome",
-        services::dynmaic_injection_box_sample::container_injection_main,
+        || {
+            services::dynmaic_injection_box_sample::container_injection_main();
+            Ok(())
+        },
         &[],
-        Some("awesome/di.md"),
+        Some("awesome/dependency_injection.md"),
         SampleType::Function,
     )
+    .with_english("Dependency injection: Box trait objects")
+    .with_sample_source("crates/awesome/src/services/dynmaic_injection_box_sample.rs", "container_injection_main")
 }
 
 inventory::submit! {
@@ -60,23 +93,31 @@ inventory::submit! {
         "service-locator",
         "服务定位器模式",
         "awesome",
-        services::service_container_sample::service_container_main,
+        || {
+            services::service_container_sample::service_container_main();
+            Ok(())
+        },
         &[],
-        Some("awesome/di.md"),
+        Some("awesome/dependency_injection.md"),
         SampleType::Function,
     )
+    .with_english("Service locator pattern")
+    .with_sample_source("crates/awesome/src/services/service_container_sample.rs", "service_container_main")
 }
 
 inventory::submit! {
-    Topic::new(
+    Topic::new_async(
         "consul",
         "Consul 服务发现",
         "awesome",
-        || services::consul_sample::main_consul().unwrap(),
-        &["Consul"],
-        Some("awesome/consul.md"),
+        || Box::pin(services::consul_sample::main_consul()),
+        &[Dependency::Consul],
+        Some("awesome/services.md"),
         SampleType::Function,
     )
+    .with_english("Consul service discovery")
+    .with_sample_source("crates/awesome/src/services/consul_sample.rs", "main_consul")
+    .with_timeout(60)
 }
 
 inventory::submit! {
@@ -84,9 +125,69 @@ inventory::submit! {
         "mqtt",
         "MQTT 消息队列",
         "awesome",
-        mq::rumqtt_sample::mqtt_sync_sample,
-        &["MQTT"],
+        || {
+            let broker = doctor::endpoint(Dependency::Mqtt);
+            mq::rumqtt_sample::mqtt_sync_sample(doctor::mqtt_address(&broker));
+            Ok(())
+        },
+        &[Dependency::Mqtt],
         Some("awesome/mqtt.md"),
         SampleType::Function,
     )
+    .with_english("MQTT message queue")
+    .with_sample_source("crates/awesome/src/mq/rumqtt_sample.rs", "mqtt_sync_sample")
+}
+
+inventory::submit! {
+    Topic::new_async(
+        "grpc-greeter",
+        "tonic gRPC Greeter 服务",
+        "awesome",
+        || Box::pin(services::tonic_hello_server::hello_server("127.0.0.1", 50051)),
+        &[],
+        Some("advance/web/grpc.md"),
+        SampleType::Server,
+    )
+    .with_english("tonic gRPC Greeter service")
+    .with_sample_source("crates/awesome/src/services/tonic_hello_server.rs", "hello_server")
+    .with_port(50051)
+    .with_async_args(
+        &[
+            TopicArg::text("host", "127.0.0.1", "Address to listen on"),
+            TopicArg::port("port", "50051", "Port to listen on"),
+        ],
+        |args| {
+            Box::pin(async move {
+                let port = args.port("port").into();
+                services::tonic_hello_server::hello_server(args.text("host"), port).await
+            })
+        },
+    )
+}
+
+inventory::submit! {
+    Topic::new_async(
+        "grpc-store",
+        "tonic gRPC 库存服务 (含 reflection)",
+        "awesome",
+        || Box::pin(services::tonic_store_server::store_server("127.0.0.1", 50052)),
+        &[],
+        Some("advance/web/grpc.md"),
+        SampleType::Server,
+    )
+    .with_english("tonic gRPC inventory service (with reflection)")
+    .with_sample_source("crates/awesome/src/services/tonic_store_server.rs", "store_server")
+    .with_port(50052)
+    .with_async_args(
+        &[
+            TopicArg::text("host", "127.0.0.1", "Address to listen on"),
+            TopicArg::port("port", "50052", "Port to listen on"),
+        ],
+        |args| {
+            Box::pin(async move {
+                let port = args.port("port").into();
+                services::tonic_store_server::store_server(args.text("host"), port).await
+            })
+        },
+    )
 }
@@ -17,7 +17,21 @@ pub mod tracing_sample;
 pub mod traits_sample;
 pub mod visiable_sample;
 
-use crate::cli::registry::{SampleType, Topic};
+use crate::cli::registry::{Category, Exercise, SampleType, Topic};
+
+// Register the basic category with inventory
+inventory::submit! {
+    Category::new(
+        "basic",
+        "Rust 基础 (Basic)",
+        10,
+        "Rust 基础知识（所有权、泛型、线程等）",
+    )
+    .with_english(
+        "Rust Basics",
+        "Rust fundamentals (ownership, generics, threads, ...)",
+    )
+}
 
 // Register all basic topics with inventory
 inventory::submit! {
@@ -25,11 +39,16 @@ inventory::submit! {
         "expression",
         "变量绑定、可变性、基础表达式",
         "basic",
-        expression_sample::variable_bind,
+        || {
+            expression_sample::variable_bind();
+            Ok(())
+        },
         &[],
         Some("basic/expression.md"),
         SampleType::Function,
     )
+    .with_english("Variable bindings, mutability and basic expressions")
+    .with_sample_source("src/basic/expression_sample.rs", "variable_bind")
 }
 
 inventory::submit! {
@@ -37,11 +56,25 @@ inventory::submit! {
         "ownership",
         "所有权规则、移动语义、借用与引用",
         "basic",
-        ownership_sample::ownership_sample,
+        || {
+            ownership_sample::ownership_sample();
+            Ok(())
+        },
         &[],
         Some("basic/ownership.md"),
         SampleType::Function,
     )
+    .with_english("Ownership rules, move semantics, borrowing and references")
+    .with_sample_source("src/basic/ownership_sample.rs", "ownership_sample")
+    .with_prerequisites(&["expression", "datatype"])
+}
+
+inventory::submit! {
+    Exercise::new(
+        "basic/ownership",
+        include_str!("../../exercises/basic/ownership/src/lib.rs"),
+        include_str!("../../exercises/basic/ownership/tests/check.rs"),
+    )
 }
 
 inventory::submit! {
@@ -49,11 +82,17 @@ inventory::submit! {
         "datatype",
         "整数、浮点数、布尔值、字符、集合、日期/时间",
         "basic",
-        datatype_sample::string_sample,
+        || {
+            datatype_sample::string_sample();
+            Ok(())
+        },
         &[],
         Some("basic/datatype.md"),
         SampleType::Function,
     )
+    .with_english("Integers, floats, booleans, chars, collections, date/time")
+    .with_sample_source("src/basic/datatype_sample.rs", "string_sample")
+    .with_prerequisites(&["expression"])
 }
 
 inventory::submit! {
@@ -61,11 +100,17 @@ inventory::submit! {
         "generic",
         "泛型函数、单态化、trait 约束",
         "basic",
-        generic_sample::add_generic_sample,
+        || {
+            generic_sample::add_generic_sample();
+            Ok(())
+        },
         &[],
         Some("basic/generic.md"),
         SampleType::Function,
     )
+    .with_english("Generic functions, monomorphization, trait bounds")
+    .with_sample_source("src/basic/generic_sample.rs", "add_generic_sample")
+    .with_prerequisites(&["traits"])
 }
 
 inventory::submit! {
@@ -73,11 +118,17 @@ inventory::submit! {
         "threads",
         "线程创建、通道、Mutex、Arc",
         "basic",
-        threads_sample::create_thread_sample,
+        || {
+            threads_sample::create_thread_sample();
+            Ok(())
+        },
         &[],
         Some("basic/threads.md"),
         SampleType::Function,
     )
+    .with_english("Spawning threads, channels, Mutex, Arc")
+    .with_sample_source("src/basic/threads_sample.rs", "create_thread_sample")
+    .with_prerequisites(&["closure"])
 }
 
 inventory::submit! {
@@ -85,11 +136,16 @@ inventory::submit! {
         "module",
         "模块组织、可见性、use 语句",
         "basic",
-        module_sample::function,
+        || {
+            module_sample::function();
+            Ok(())
+        },
         &[],
         Some("basic/module.md"),
         SampleType::Function,
     )
+    .with_english("Module organization, visibility, use declarations")
+    .with_sample_source("src/basic/module_sample.rs", "function")
 }
 
 inventory::submit! {
@@ -97,11 +153,17 @@ inventory::submit! {
         "logger",
         "日志记录和追踪",
         "basic",
-        logger_sample::logger_print,
+        || {
+            logger_sample::logger_print();
+            Ok(())
+        },
         &[],
         Some("basic/logger.md"),
         SampleType::Function,
     )
+    .with_english("Logging and tracing")
+    .with_sample_source("src/basic/logger_sample.rs", "logger_print")
+    .with_volatile_output()
 }
 
 inventory::submit! {
@@ -109,11 +171,16 @@ inventory::submit! {
         "tracing",
         "使用 tracing 进行结构化日志",
         "basic",
-        tracing_sample::tracing_demo,
+        || {
+            tracing_sample::tracing_demo();
+            Ok(())
+        },
         &[],
         Some("basic/tracing.md"),
         SampleType::Function,
     )
+    .with_english("Structured logging with tracing")
+    .with_sample_source("src/basic/tracing_sample.rs", "tracing_demo")
 }
 
 inventory::submit! {
@@ -121,11 +188,25 @@ inventory::submit! {
         "closure",
         "闭包语法、环境捕获、Fn/FnMut/FnOnce",
         "basic",
-        closure_sample::closure_sample,
+        || {
+            closure_sample::closure_sample();
+            Ok(())
+        },
         &[],
         Some("basic/closure.md"),
         SampleType::Function,
     )
+    .with_english("Closure syntax, capturing the environment, Fn/FnMut/FnOnce")
+    .with_sample_source("src/basic/closure_sample.rs", "closure_sample")
+    .with_prerequisites(&["ownership"])
+}
+
+inventory::submit! {
+    Exercise::new(
+        "basic/closure",
+        include_str!("../../exercises/basic/closure/src/lib.rs"),
+        include_str!("../../exercises/basic/closure/tests/check.rs"),
+    )
 }
 
 inventory::submit! {
@@ -133,11 +214,25 @@ inventory::submit! {
         "traits",
         "Trait 定义、实现、多态",
         "basic",
-        traits_sample::traits_simple_sample,
+        || {
+            traits_sample::traits_simple_sample();
+            Ok(())
+        },
         &[],
         Some("basic/trait.md"),
         SampleType::Function,
     )
+    .with_english("Defining and implementing traits, polymorphism")
+    .with_sample_source("src/basic/traits_sample.rs", "traits_simple_sample")
+    .with_prerequisites(&["rectangle"])
+}
+
+inventory::submit! {
+    Exercise::new(
+        "basic/traits",
+        include_str!("../../exercises/basic/traits/src/lib.rs"),
+        include_str!("../../exercises/basic/traits/tests/check.rs"),
+    )
 }
 
 inventory::submit! {
@@ -145,11 +240,16 @@ inventory::submit! {
         "pointer",
         "裸指针和原始指针操作",
         "basic",
-        pointer_sample::raw_pointer_sample,
+        || {
+            pointer_sample::raw_pointer_sample();
+            Ok(())
+        },
         &[],
         Some("basic/pointer.md"),
         SampleType::Function,
     )
+    .with_english("Raw pointers and pointer operations")
+    .with_sample_source("src/basic/pointer_sample.rs", "raw_pointer_sample")
 }
 
 inventory::submit! {
@@ -157,11 +257,17 @@ inventory::submit! {
         "rectangle",
         "结构体生命周期示例",
         "basic",
-        rectangle::rectangle_example,
+        || {
+            rectangle::rectangle_example();
+            Ok(())
+        },
         &[],
         Some("basic/struct.md"),
         SampleType::Function,
     )
+    .with_english("Struct lifetimes by example")
+    .with_sample_source("src/basic/rectangle.rs", "rectangle_example")
+    .with_prerequisites(&["ownership"])
 }
 
 inventory::submit! {
@@ -169,11 +275,16 @@ inventory::submit! {
         "cfg-if",
         "条件编译和平台检测",
         "basic",
-        cfg_if_sample::cfg_if_sample,
+        || {
+            cfg_if_sample::cfg_if_sample();
+            Ok(())
+        },
         &[],
-        Some("basic/cfg-if.md"),
+        Some("basic/cfg_if.md"),
         SampleType::Function,
     )
+    .with_english("Conditional compilation and platform detection")
+    .with_sample_source("src/basic/cfg_if_sample.rs", "cfg_if_sample")
 }
 
 inventory::submit! {
@@ -181,9 +292,14 @@ inventory::submit! {
         "visiable",
         "可见性和访问控制",
         "basic",
-        visiable_sample::visiable_sample,
+        || {
+            visiable_sample::visiable_sample();
+            Ok(())
+        },
         &[],
         Some("basic/visiable.md"),
         SampleType::Function,
     )
+    .with_english("Visibility and access control")
+    .with_sample_source("src/basic/visiable_sample.rs", "visiable_sample")
 }
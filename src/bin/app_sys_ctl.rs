@@ -20,7 +20,7 @@ use std::process;
 use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::pin;
-use tokio::signal::unix::{signal, SignalKind};
+use awesome::services::framework::signals::SignalListener;
 // use hyper::{Body, Request, Response};
 use env_logger;
 use log::{error, info, trace};
@@ -108,21 +108,6 @@ async fn start_app() -> Result<(), Box<dyn std::error::Error>> {
         Ok::<_, Infallible>(Response::new(Full::new(Bytes::from("Hello, World!"))))
     };
 
-    async fn shutdown_signal() {
-        // Wait for the CTRL+C signal
-        let ctrl_c = async {
-            tokio::signal::ctrl_c()
-                .await
-                .expect("failed to install CTRL+C signal handler");
-        };
-
-        tokio::select! {
-            _=ctrl_c => {
-                info!("Received Ctrl+C, initiating graceful shutdown...");
-            }
-        }
-    }
-
     // 启动 HTTP 服务器
 
     let listener = TcpListener::bind(addr).await?;
@@ -132,14 +117,9 @@ async fn start_app() -> Result<(), Box<dyn std::error::Error>> {
     let mut http = http1::Builder::new();
     // the graceful watcher
     let graceful = hyper_util::server::graceful::GracefulShutdown::new();
-    // when this signal completes, start shutdown
-    let mut signal = std::pin::pin!(shutdown_signal());
 
-    // 监听 SIGINT (Ctrl+C)
-    // let mut sigint = tokio::signal::unix::signal(SignalKind::interrupt())?;
-
-    // 监听 SIGTERM (kill 默认发送的信号)
-    let mut sigterm = tokio::signal::unix::signal(SignalKind::terminate())?;
+    // 监听 SIGINT (Ctrl+C)、SIGTERM (kill 默认发送的信号) 和 SIGHUP
+    let mut signals = SignalListener::new()?;
 
     // Our server accept loop
     loop {
@@ -156,18 +136,15 @@ async fn start_app() -> Result<(), Box<dyn std::error::Error>> {
                 });
             },
 
-            _ = &mut signal => {
-                info!("Received Ctrl+C, initiating graceful shutdown...");
+            signal = signals.recv() => {
+                if !signal.is_shutdown() {
+                    info!("Received {}, nothing to reload", signal);
+                    continue;
+                }
+                info!("Received {}, initiating graceful shutdown...", signal);
                 drop(listener);
-                info!("graceful shutdown signal received");
                 // stop the accept loop
                 break;
-            },
-            _ = sigterm.recv() => {
-                info!("Received SIGTERM (kill), initiating graceful shutdown...");
-
-                drop(listener);
-                break;
             }
         }
     }
@@ -175,10 +152,25 @@ async fn start_app() -> Result<(), Box<dyn std::error::Error>> {
     // Now start the shutdown and wait for them to complete
     // Optional: start a timeout to limit how long to wait.
 
+    // A second SIGINT/SIGTERM stops waiting for the connections
+    let second_signal = async {
+        loop {
+            let signal = signals.recv().await;
+            if signal.is_shutdown() {
+                break signal;
+            }
+        }
+    };
+
     tokio::select! {
         _ = graceful.shutdown() => {
             eprintln!("all connections gracefully closed");
         },
+        signal = second_signal => {
+            error!("Received a second {}, exiting without waiting for connections", signal);
+            remove_pid()?;
+            process::exit(signal.exit_code());
+        },
         // _ = tokio::time::sleep(std::time::Duration::from_secs(5)) => {
         //     eprintln!("timed out wait for all connections to close");
         // },
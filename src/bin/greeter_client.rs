@@ -1,6 +1,6 @@
 use anyhow::Result; // Use anyhow for top-level main function error handling
 use awesome::services::framework::{
-    config::{BaseServiceConfig, RegistryConfig},
+    config::{BaseServiceConfig, RegistryBackend, RegistryConfig},
     lifecycle::{ApplicationFramework, RunnableService, ServiceStatus},
 };
 use awesome::services::greeter_consume;
@@ -42,12 +42,18 @@ async fn main() -> Result<()> {
             registry_url: "http://192.168.2.6:8500/v1/".to_string(),
             service_ip: "192.168.2.7".to_string(),
         },
+        // RegistryBackend::File { path: "registry.json".into() } runs without Consul
+        registry: RegistryBackend::Consul,
     };
 
     // --- 3. Define Greeter Service Specific Configuration ---
 
-    greeter_consume::start_consume("http://192.168.2.7:50052".to_string())
-        .await
+    greeter_consume::start_consume(
+        &base_config.consul.registry_url,
+        &base_config.service_name,
+        None,
+    )
+    .await
         .expect("Failed to run greeter consume client");
 
     info!("Press Ctrl+C to initiate graceful shutdown...");
@@ -1,10 +1,13 @@
 use anyhow::Result; // Use anyhow for top-level main function error handling
 use awesome::services::framework::{
-    config::{BaseServiceConfig, RegistryConfig},
-    lifecycle::{ApplicationFramework, RunnableService, ServiceStatus},
+    config::{BaseServiceConfig, RegistryBackend, RegistryConfig},
+    error::FrameworkError,
+    lifecycle::{ApplicationFramework, RunnableService},
 };
-use awesome::services::greeter_service::{GreeterApplicationService, GreeterServiceConfig};
-use tokio::time::{sleep, Duration};
+use awesome::services::greeter_service::{
+    GreeterApplicationService, GreeterServiceConfig, DEFAULT_GREETING,
+};
+use tokio::time::Duration;
 use tracing::{error, info, span, Level};
 use tracing_subscriber::fmt;
 use tracing_subscriber::prelude::*;
@@ -12,6 +15,41 @@ use tracing_subscriber::FmtSubscriber;
 use tracing_subscriber::{filter::LevelFilter, EnvFilter}; // For `with_filter` and `EnvFilter` // For setting log level
                                                           // use tracing_subscriber::EnvFilter;
 
+/// Environment variable naming a JSON file with the `GreeterServiceConfig`
+/// (read at startup and again on every SIGHUP)
+const CONFIG_ENV: &str = "GREETER_CONFIG";
+
+/// Built-in configuration, used when `GREETER_CONFIG` is not set
+fn default_config() -> GreeterServiceConfig {
+    let base_config = BaseServiceConfig {
+        service_id_prefix: "greeter-app".to_string(),
+        service_name: "my-greeter-service".to_string(),
+        consul: RegistryConfig {
+            registry_url: "http://192.168.2.6:8500/v1/".to_string(),
+            service_ip: "192.168.2.7".to_string(),
+        },
+        // RegistryBackend::File { path: "registry.json".into() } runs without Consul
+        registry: RegistryBackend::Consul,
+    };
+    GreeterServiceConfig {
+        base_config,
+        grpc_port: 50052,
+        http_health_port: 8081,
+        greeting: DEFAULT_GREETING.to_string(),
+    }
+}
+
+/// Load the configuration from the file named by `GREETER_CONFIG`, or the built-in one
+fn load_config() -> Result<GreeterServiceConfig, FrameworkError> {
+    let Some(path) = std::env::var_os(CONFIG_ENV) else {
+        return Ok(default_config());
+    };
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| FrameworkError::Config(format!("Failed to read {}: {}", path.display(), e)))?;
+    serde_json::from_str(&text)
+        .map_err(|e| FrameworkError::Config(format!("Invalid config in {}: {}", path.display(), e)))
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // 1. Initialize the tracing subscriber
@@ -34,63 +72,37 @@ async fn main() -> Result<()> {
     let _span_ = span!(Level::TRACE, "greeter_server_startup").entered();
     info!("Starting application framework...");
 
-    // --- 2. Define Base Configuration ---
-    // In a real app, you might load this from a file (e.g., JSON, YAML)
-    let base_config = BaseServiceConfig {
-        service_id_prefix: "greeter-app".to_string(),
-        service_name: "my-greeter-service".to_string(),
-        consul: RegistryConfig {
-            registry_url: "http://192.168.2.6:8500/v1/".to_string(),
-            service_ip: "192.168.2.7".to_string(),
-        },
-    };
-
-    // --- 3. Define Greeter Service Specific Configuration ---
-    let greeter_config = GreeterServiceConfig {
-        base_config,
-        grpc_port: 50052,
-        http_health_port: 8081,
-    };
+    // --- 2. Load the Configuration ---
+    // From the JSON file named by GREETER_CONFIG, or the built-in defaults
+    let greeter_config = load_config()?;
 
-    // --- 4. Initialize and Start the Service via the Framework ---
+    // --- 3. Initialize and Start the Service via the Framework ---
     let mut app_framework = ApplicationFramework::<GreeterApplicationService>::new(greeter_config)
         .expect("Failed to initialize ApplicationFramework");
 
+    // Wait until the listeners are bound and the service is registered
+    if let Err(e) = app_framework.wait_ready(Duration::from_secs(10)).await {
+        error!("Service did not become ready: {}", e);
+        return Err(e.into());
+    }
     info!(
-        "Application service '{}' started in background.",
+        "Application service '{}' is ready.",
         app_framework.service().instance_id()
     );
 
-    // --- 5. Main Application Loop (Simulates ongoing work and status monitoring) ---
-    for i in 0..100 {
-        sleep(Duration::from_secs(3)).await;
-        let current_status = app_framework.get_status().await;
-        info!(
-            "Main loop ({}s): Current service status: {:?}",
-            i * 3,
-            current_status
-        );
-
-        if matches!(current_status, ServiceStatus::Failed(_)) {
-            error!("Service entered a failed state. Initiating immediate exit.");
-            break; // Service failed, exit loop
-        }
-    }
-
-    // --- 6. Handle OS Signals for Graceful Shutdown ---
-    info!("Press Ctrl+C to initiate graceful shutdown...");
-    tokio::signal::ctrl_c()
+    // --- 4. Serve Until SIGINT/SIGTERM, Then Stop Gracefully ---
+    // SIGHUP re-reads the config file and hands it to the service (a new greeting
+    // applies at once); a second SIGINT/SIGTERM exits at once.
+    info!("Press Ctrl+C (or send SIGTERM) to initiate graceful shutdown...");
+    match app_framework
+        .run_until_signal(Duration::from_secs(10), load_config)
         .await
-        .expect("Failed to listen for Ctrl+C");
-    info!("Ctrl+C received. Initiating graceful shutdown via framework.");
-
-    // --- 7. Stop the Service Gracefully ---
-    match app_framework.stop().await {
+    {
         Ok(_) => info!("Application service gracefully stopped."),
         Err(e) => error!("Application service stopped with error: {}", e),
     }
 
-    // --- 8. Final Status Check ---
+    // --- 5. Final Status Check ---
     let final_status = app_framework.get_status().await;
     info!("Final service status: {:?}", final_status);
 
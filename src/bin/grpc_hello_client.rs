@@ -6,10 +6,11 @@ struct Args {
     #[clap(default_value = "http://127.0.0.1:9001", long)]
     url: String,
 }
-fn main() {
+#[tokio::main]
+async fn main() {
     println!("Hello, Tonic Hello client!");
     let opts = Args::parse();
-    if let Err(e) = tonic_hello_client::hello_client(opts.url) {
+    if let Err(e) = tonic_hello_client::hello_client(opts.url).await {
         eprintln!("Error: {}", e);
         std::process::exit(1);
     }
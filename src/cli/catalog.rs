@@ -0,0 +1,407 @@
+//! Machine-readable export of the topic registry
+//!
+//! `hello list --format json|markdown|csv` prints every registered topic as
+//! data. The same Markdown catalog is the book's `topics.md` chapter, linked
+//! from a generated block of `SUMMARY.md`; `hello list --summary check`
+//! fails when either file or the tutorial links disagree with the registry.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::cli::docs_embed;
+use crate::cli::registry::{self, ArgKind, SampleType, Topic, TopicArg};
+
+/// Default directory of the book sources
+pub const DEFAULT_DOCS_DIR: &str = "docs/src";
+
+/// Generated catalog chapter, relative to the docs directory
+pub const CATALOG_PAGE: &str = "topics.md";
+
+/// First line of the generated block in `SUMMARY.md`
+const SUMMARY_BEGIN: &str =
+    "<!-- topics:begin (generated by `hello list --summary write`, do not edit) -->";
+
+/// Last line of the generated block in `SUMMARY.md`
+const SUMMARY_END: &str = "<!-- topics:end -->";
+
+/// Output format of `hello list`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable overview of the categories
+    Table,
+    /// JSON array of topics
+    Json,
+    /// Markdown tables, one per category
+    Markdown,
+    /// CSV with a header row
+    Csv,
+}
+
+/// What `hello list --summary` does with the book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SummaryMode {
+    /// Regenerate the catalog chapter and the topic block of SUMMARY.md
+    Write,
+    /// Fail if the book disagrees with the registry
+    Check,
+}
+
+/// One topic of the catalog
+#[derive(Debug, Clone, Serialize)]
+pub struct Entry {
+    /// Topic name (CLI subcommand)
+    pub name: &'static str,
+    /// Category name
+    pub category: &'static str,
+    /// One-line description
+    pub description: &'static str,
+    /// English description, if translated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description_en: Option<&'static str>,
+    /// Names of the external services the sample needs
+    pub dependencies: Vec<&'static str>,
+    /// Options the sample accepts
+    pub args: Vec<ArgEntry>,
+    /// Tutorial path relative to `docs/src`
+    pub doc_path: Option<&'static str>,
+    /// "function" or "server"
+    pub sample_type: &'static str,
+    /// Whether the sample is an async function run on the shared runtime
+    #[serde(rename = "async")]
+    pub is_async: bool,
+    /// Where the topic is registered, as "file:line"
+    pub source: String,
+}
+
+/// One option of a topic in the catalog
+#[derive(Debug, Clone, Serialize)]
+pub struct ArgEntry {
+    /// Long option name without `--`
+    pub name: &'static str,
+    /// "integer", "port", "text" or "choice"
+    pub kind: &'static str,
+    /// Accepted values of a "choice" option
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<&'static str>,
+    /// Value used when the option is not given
+    pub default: &'static str,
+    /// One-line help
+    pub help: &'static str,
+}
+
+impl ArgEntry {
+    fn new(arg: &TopicArg) -> Self {
+        let (kind, choices) = match arg.kind {
+            ArgKind::Integer => ("integer", Vec::new()),
+            ArgKind::Port => ("port", Vec::new()),
+            ArgKind::Text => ("text", Vec::new()),
+            ArgKind::Choice(choices) => ("choice", choices.to_vec()),
+        };
+        Self {
+            name: arg.name,
+            kind,
+            choices,
+            default: arg.default,
+            help: arg.help,
+        }
+    }
+
+    /// Option with its default, e.g. "--steps 1e7"
+    fn usage(&self) -> String {
+        format!("--{} {}", self.name, self.default)
+    }
+}
+
+impl Entry {
+    fn new(topic: &Topic) -> Self {
+        Self {
+            name: topic.name,
+            category: topic.category,
+            description: topic.description,
+            description_en: topic.description_en,
+            dependencies: topic.dependencies.iter().map(|d| d.name()).collect(),
+            args: topic.args.iter().map(ArgEntry::new).collect(),
+            doc_path: topic.doc_path,
+            sample_type: match topic.sample_type {
+                SampleType::Function => "function",
+                SampleType::Server => "server",
+            },
+            is_async: topic.is_async(),
+            source: topic.source_location(),
+        }
+    }
+
+    /// Options with their defaults, e.g. ["--steps 1e7", "--method leibniz"]
+    fn usages(&self) -> Vec<String> {
+        self.args.iter().map(ArgEntry::usage).collect()
+    }
+}
+
+/// Get the catalog of all topics in registry order
+pub fn entries() -> Vec<Entry> {
+    registry::get_sorted_topics()
+        .into_iter()
+        .map(Entry::new)
+        .collect()
+}
+
+/// Render the catalog as pretty-printed JSON
+pub fn to_json(entries: &[Entry]) -> String {
+    serde_json::to_string_pretty(entries).expect("catalog entries serialize")
+}
+
+/// Render the catalog as CSV; dependencies and options are joined by ";"
+pub fn to_csv(entries: &[Entry]) -> String {
+    let mut csv =
+        String::from("name,category,description,dependencies,args,doc_path,sample_type,source\n");
+    for entry in entries {
+        let fields = [
+            entry.name,
+            entry.category,
+            entry.description,
+            &entry.dependencies.join(";"),
+            &entry.usages().join(";"),
+            entry.doc_path.unwrap_or_default(),
+            entry.sample_type,
+            &entry.source,
+        ];
+        let row: Vec<_> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Render the catalog as Markdown, one table per category
+///
+/// Tutorial links are relative to `docs/src`, so the output is a valid book
+/// chapter. The book leaves out `source`, which moves with every edit.
+pub fn to_markdown(entries: &[Entry], source: bool) -> String {
+    let mut markdown = String::new();
+    for category in registry::get_all_categories() {
+        let topics: Vec<_> = entries
+            .iter()
+            .filter(|entry| entry.category == category.name)
+            .collect();
+        if topics.is_empty() {
+            continue;
+        }
+
+        markdown.push_str(&format!("## {}\n\n", category.display_name));
+        if source {
+            markdown.push_str("| 主题 | 说明 | 依赖 | 参数 | 教程 | 类型 | 源码 |\n");
+            markdown.push_str("| --- | --- | --- | --- | --- | --- | --- |\n");
+        } else {
+            markdown.push_str("| 主题 | 说明 | 依赖 | 参数 | 教程 | 类型 |\n");
+            markdown.push_str("| --- | --- | --- | --- | --- | --- |\n");
+        }
+        for entry in topics {
+            let doc = entry
+                .doc_path
+                .map(|path| format!("[{}]({})", path, path))
+                .unwrap_or_default();
+            markdown.push_str(&format!(
+                "| `hello {} {}` | {} | {} | {} | {} | {} |",
+                entry.category,
+                entry.name,
+                markdown_cell(entry.description),
+                entry.dependencies.join(", "),
+                entry
+                    .usages()
+                    .iter()
+                    .map(|usage| format!("`{}`", markdown_cell(usage)))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                doc,
+                entry.sample_type,
+            ));
+            if source {
+                markdown.push_str(&format!(" `{}` |", entry.source));
+            }
+            markdown.push('\n');
+        }
+        markdown.push('\n');
+    }
+    markdown
+}
+
+/// Content of the generated catalog chapter
+pub fn catalog_page(entries: &[Entry]) -> String {
+    format!(
+        "# 示例目录\n\n\
+         <!-- generated by `hello list --summary write`, do not edit -->\n\n\
+         本页列出 `hello` 命令行中注册的全部 {} 个示例，由注册表自动生成。\n\n{}",
+        entries.len(),
+        to_markdown(entries, false).trim_end()
+    ) + "\n"
+}
+
+/// Generated block of `SUMMARY.md`, including the marker lines
+pub fn summary_block(entries: &[Entry]) -> String {
+    format!(
+        "{}\n- [示例目录](./{}) - {} 个可运行示例\n{}",
+        SUMMARY_BEGIN,
+        CATALOG_PAGE,
+        entries.len(),
+        SUMMARY_END
+    )
+}
+
+/// Replace the generated block of `summary`, or insert it before the "附录" part
+fn update_summary(summary: &str, block: &str) -> String {
+    if let Some(start) = summary.find(SUMMARY_BEGIN)
+        && let Some(end) = summary[start..].find(SUMMARY_END)
+    {
+        let end = start + end + SUMMARY_END.len();
+        return format!("{}{}{}", &summary[..start], block, &summary[end..]);
+    }
+    match summary.find("\n# 附录") {
+        Some(position) => format!(
+            "{}\n# 示例索引\n\n{}\n{}",
+            &summary[..position],
+            block,
+            &summary[position..]
+        ),
+        None => format!("{}\n# 示例索引\n\n{}\n", summary.trim_end(), block),
+    }
+}
+
+/// Regenerate the catalog chapter and the topic block of `SUMMARY.md` in `docs_dir`
+///
+/// # Returns
+/// Paths of the files whose content changed
+pub fn write_summary(docs_dir: &Path) -> Result<Vec<String>> {
+    let entries = entries();
+    let summary_path = docs_dir.join("SUMMARY.md");
+    let summary = fs::read_to_string(&summary_path)
+        .with_context(|| format!("Failed to read {}", summary_path.display()))?;
+
+    let mut changed = Vec::new();
+    let files = [
+        (
+            summary_path.clone(),
+            update_summary(&summary, &summary_block(&entries)),
+        ),
+        (docs_dir.join(CATALOG_PAGE), catalog_page(&entries)),
+    ];
+    for (path, content) in files {
+        if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+            fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            changed.push(path.display().to_string());
+        }
+    }
+    Ok(changed)
+}
+
+/// Compare the book in `docs_dir` with the registry
+///
+/// # Returns
+/// One message per disagreement (empty if the book is up to date)
+pub fn check_summary(docs_dir: &Path) -> Result<Vec<String>> {
+    let entries = entries();
+    let summary_path = docs_dir.join("SUMMARY.md");
+    let summary = fs::read_to_string(&summary_path)
+        .with_context(|| format!("Failed to read {}", summary_path.display()))?;
+
+    let mut problems = Vec::new();
+    if !summary.contains(&summary_block(&entries)) {
+        problems.push(format!(
+            "{}: topic block is missing or outdated",
+            summary_path.display()
+        ));
+    }
+    let page_path = docs_dir.join(CATALOG_PAGE);
+    if fs::read_to_string(&page_path).ok() != Some(catalog_page(&entries)) {
+        problems.push(format!(
+            "{}: catalog is missing or outdated",
+            page_path.display()
+        ));
+    }
+
+    let links: Vec<_> = docs_embed::summary_links(&summary).collect();
+    for entry in &entries {
+        let Some(doc_path) = entry.doc_path else {
+            continue;
+        };
+        if !docs_dir.join(doc_path).is_file() {
+            problems.push(format!(
+                "{}/{}: tutorial {} does not exist",
+                entry.category, entry.name, doc_path
+            ));
+        } else if !links.contains(&doc_path) {
+            problems.push(format!(
+                "{}/{}: tutorial {} is not linked from SUMMARY.md",
+                entry.category, entry.name, doc_path
+            ));
+        }
+    }
+    Ok(problems)
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escape a value for a Markdown table cell
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_quotes_separators() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_entries_cover_registry() {
+        let entries = entries();
+        assert_eq!(entries.len(), registry::get_all_topics().count());
+        assert!(entries.iter().all(|entry| entry.source.contains(".rs:")));
+
+        let csv = to_csv(&entries);
+        assert_eq!(csv.lines().count(), entries.len() + 1);
+    }
+
+    #[test]
+    fn test_update_summary_replaces_block() {
+        let block = format!("{}\n- [new](./topics.md)\n{}", SUMMARY_BEGIN, SUMMARY_END);
+        let summary = format!(
+            "# Summary\n\n{}\n- [old](./topics.md)\n{}\n\n# 附录\n",
+            SUMMARY_BEGIN, SUMMARY_END
+        );
+        let updated = update_summary(&summary, &block);
+        assert!(updated.contains("[new]"));
+        assert!(!updated.contains("[old]"));
+        assert_eq!(update_summary(&updated, &block), updated);
+
+        let inserted = update_summary("# Summary\n\n# 附录\n\n- [FAQ](./faq.md)\n", &block);
+        assert!(inserted.find(SUMMARY_BEGIN) < inserted.find("# 附录"));
+    }
+
+    #[test]
+    fn test_book_matches_registry() {
+        let docs_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_DOCS_DIR);
+        let problems = check_summary(&docs_dir).unwrap();
+        assert!(
+            problems.is_empty(),
+            "run `hello list --summary write`:\n{}",
+            problems.join("\n")
+        );
+    }
+}
@@ -0,0 +1,47 @@
+//! Locating the repository checkout at runtime
+//!
+//! Some commands need files that ship with the repository rather than with
+//! the binary: the example projects, the golden-output snapshots and the
+//! input files the samples read from `data/`. Their location is never baked
+//! in at build time, so an installed `hello` keeps working when run from a
+//! checkout or when the binary sits inside one (`target/debug/hello`).
+
+use std::path::{Path, PathBuf};
+
+/// Find `relative` in the current directory, then next to the `hello`
+/// executable and in its parent directories
+///
+/// # Arguments
+/// * `relative` - Path to look for, relative to each candidate directory
+/// * `accept` - Whether a candidate path is the one we want
+///
+/// # Returns
+/// The first accepted candidate, as an absolute path
+pub fn find(relative: &str, accept: impl Fn(&Path) -> bool) -> Option<PathBuf> {
+    let current = std::env::current_dir().ok();
+    let beside_exe = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    current
+        .into_iter()
+        .chain(
+            beside_exe
+                .into_iter()
+                .flat_map(|dir| dir.ancestors().map(Path::to_path_buf).collect::<Vec<_>>()),
+        )
+        .map(|dir| dir.join(relative))
+        .find(|candidate| accept(candidate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_returns_absolute_paths() {
+        // cargo runs the tests from the checkout
+        let found = find("Cargo.toml", Path::is_file).unwrap();
+        assert!(found.is_absolute());
+        assert!(find("no-such-file-anywhere", Path::exists).is_none());
+    }
+}
@@ -0,0 +1,205 @@
+//! Shell completion scripts for `hello completions <shell>`
+//!
+//! clap only completes what the command declares, and topic names are not
+//! declared: they come from the inventory registry. The script is therefore
+//! generated from a copy of the command whose topic arguments list the
+//! registered names (with their descriptions) as possible values. Parsing
+//! still accepts any name, so unknown topics keep their suggestions.
+
+use std::io::{self, Write};
+
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::Command;
+use clap_complete::Shell;
+
+use crate::cli::projects;
+use crate::cli::registry::{self, SampleType, Topic};
+
+/// Subcommands whose first argument is a category name
+const CATEGORY_ARGS: &[&str] = &["verify", "run-all", "serve", "source"];
+
+/// Registry names completed for one argument of a subcommand
+struct Completion {
+    subcommand: &'static str,
+    arg: &'static str,
+    /// Names with their descriptions
+    values: Vec<(&'static str, &'static str)>,
+}
+
+/// Write the completion script for `shell` to `out`
+///
+/// clap's fish and PowerShell scripts leave positional values out, so the
+/// names of the first positional argument are added to those by hand.
+///
+/// # Arguments
+/// * `command` - The full `hello` command, including the category subcommands
+pub fn generate(shell: Shell, command: Command, out: &mut dyn Write) -> io::Result<()> {
+    let completions = completions();
+    let mut command = with_values(command, &completions);
+    let name = command.get_name().to_string();
+
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut command, &name, &mut script);
+    let mut script = String::from_utf8_lossy(&script).into_owned();
+    // `hello serve|source <category> <topic>`: only the category is positional-first
+    let first_positional = completions.iter().filter(|completion| {
+        completion.arg != "topic" || !CATEGORY_ARGS.contains(&completion.subcommand)
+    });
+    match shell {
+        Shell::Fish => script.push_str(&fish_values(&name, first_positional)),
+        Shell::PowerShell => script = with_powershell_values(script, &name, first_positional),
+        _ => {}
+    }
+    out.write_all(script.as_bytes())
+}
+
+/// One `complete` line per name, offered once the subcommand is typed
+fn fish_values<'a>(name: &str, completions: impl Iterator<Item = &'a Completion>) -> String {
+    let mut lines = String::new();
+    for completion in completions {
+        for (value, help) in &completion.values {
+            lines.push_str(&format!(
+                "complete -c {} -n \"__fish_seen_subcommand_from {}\" -f -a \"{}\" -d '{}'\n",
+                name,
+                completion.subcommand,
+                value,
+                help.replace('\\', "\\\\").replace('\'', "\\'")
+            ));
+        }
+    }
+    lines
+}
+
+/// Add one `CompletionResult` per name to the case of its subcommand
+fn with_powershell_values<'a>(
+    mut script: String,
+    name: &str,
+    completions: impl Iterator<Item = &'a Completion>,
+) -> String {
+    for completion in completions {
+        let case = format!("'{};{}' {{\n", name, completion.subcommand);
+        let results: String = completion
+            .values
+            .iter()
+            .map(|(value, help)| {
+                format!(
+                    "            [CompletionResult]::new('{0}', '{0}', \
+                     [CompletionResultType]::ParameterValue, '{1}')\n",
+                    value,
+                    help.replace('\'', "''")
+                )
+            })
+            .collect();
+        script = script.replacen(&case, &format!("{}{}", case, results), 1);
+    }
+    script
+}
+
+/// Names completed for each argument taking a category or topic
+fn completions() -> Vec<Completion> {
+    let categories: Vec<_> = registry::get_all_categories()
+        .into_iter()
+        .map(|category| (category.name, category.localized_description()))
+        .collect();
+    let topic = |topic: &Topic| (topic.name, topic.localized_description());
+
+    let mut completions: Vec<_> = registry::get_all_categories()
+        .into_iter()
+        .map(|category| Completion {
+            subcommand: category.name,
+            arg: "topic",
+            values: registry::get_topics_by_category(category.name)
+                .map(topic)
+                .collect(),
+        })
+        .collect();
+    completions.extend(CATEGORY_ARGS.iter().map(|&subcommand| Completion {
+        subcommand,
+        arg: "category",
+        values: categories.clone(),
+    }));
+    completions.push(Completion {
+        subcommand: "serve",
+        arg: "topic",
+        values: registry::get_all_topics()
+            .filter(|topic| matches!(topic.sample_type, SampleType::Server))
+            .map(topic)
+            .collect(),
+    });
+    completions.push(Completion {
+        subcommand: "projects",
+        arg: "project",
+        values: projects::PROJECTS
+            .iter()
+            .map(|project| (project.name, project.description.get()))
+            .collect(),
+    });
+    completions
+}
+
+/// Declare the names in `completions` as possible values of the arguments taking them
+///
+/// Parsing uses the command without them, so any name is accepted there.
+fn with_values(command: Command, completions: &[Completion]) -> Command {
+    completions.iter().fold(command, |command, completion| {
+        let values = completion
+            .values
+            .iter()
+            .map(|&(value, help)| PossibleValue::new(value).help(help));
+        command.mut_subcommand(completion.subcommand, |sub| {
+            sub.mut_arg(completion.arg, |arg| {
+                arg.value_parser(PossibleValuesParser::new(values))
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{with_categories, Commands};
+    use clap::Subcommand;
+
+    fn hello() -> Command {
+        with_categories(Commands::augment_subcommands(Command::new("hello")))
+    }
+
+    #[test]
+    fn test_script_completes_topic_names() {
+        let mut script = Vec::new();
+        generate(Shell::Bash, hello(), &mut script).unwrap();
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("ownership"));
+        assert!(script.contains("calc-pi"));
+    }
+
+    #[test]
+    fn test_fish_script_completes_topic_names() {
+        let mut script = Vec::new();
+        generate(Shell::Fish, hello(), &mut script).unwrap();
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains(
+            "complete -c hello -n \"__fish_seen_subcommand_from algo\" -f -a \"calc-pi\""
+        ));
+    }
+
+    #[test]
+    fn test_topic_values_come_from_registry() {
+        let command = with_values(hello(), &completions());
+        let basic = command.find_subcommand("basic").unwrap();
+        let topic = basic
+            .get_arguments()
+            .find(|arg| arg.get_id() == "topic")
+            .unwrap();
+        let names: Vec<_> = topic
+            .get_possible_values()
+            .iter()
+            .map(|value| value.get_name().to_string())
+            .collect();
+        assert_eq!(
+            names.len(),
+            registry::get_topics_by_category("basic").count()
+        );
+        assert!(names.iter().any(|name| name == "ownership"));
+    }
+}
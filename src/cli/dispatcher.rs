@@ -2,55 +2,160 @@
 //!
 //! Routes subcommands to sample functions and handles execution
 
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use clap::Command;
+use clap_complete::Shell;
+
+use crate::cli::catalog::{self, Format, SummaryMode};
+use crate::cli::completions;
 use crate::cli::docs_embed;
-use crate::cli::{registry, Commands};
+use crate::cli::docs_server;
+use crate::cli::doctor;
+use crate::cli::error::{self as topic_error, TopicError};
+use crate::cli::exercise::{self, Outcome as ExerciseOutcome};
+use crate::cli::i18n::{self, t};
+use crate::cli::manpage;
+use crate::cli::projects;
+use crate::cli::quiz::{self, Kind, Question};
+use crate::cli::render::Renderer;
+use crate::cli::run_all::{self, Outcome as RunOutcome};
+use crate::cli::runtime::{self, Completion};
+use crate::cli::search::{self, HitSource};
+use crate::cli::serve;
+use crate::cli::source;
+use crate::cli::state::Progress;
+use crate::cli::verify::{self, DiffLine, Outcome};
+use crate::cli::{registry, CategoryArgs};
+
+/// Run a category subcommand: execute the requested topic or list the category
+///
+/// Exits the process on an unknown topic or a failed sample, with the exit
+/// code of the failure (see [`crate::cli::error`]).
+///
+/// # Arguments
+/// * `category` - The category whose subcommand was invoked
+/// * `args` - Parsed arguments of the category subcommand
+pub fn run_category(category: &registry::Category, args: &CategoryArgs) {
+    match &args.topic {
+        Some(topic_name) => {
+            if let Some(topic) = registry::get_topic(category.name, topic_name) {
+                if let Err(e) = execute_topic(topic, args) {
+                    eprintln!("{}", e.render());
+                    std::process::exit(e.exit_code());
+                }
+            } else {
+                handle_unknown_topic(category.name, topic_name);
+                std::process::exit(topic_error::EXIT_USAGE);
+            }
+        }
+        None => list_category(category.name),
+    }
+}
 
 /// Execute a topic's sample function
 ///
 /// # Arguments
 /// * `topic` - The topic to execute
-/// * `no_docs` - Whether to skip tutorial display
-/// * `force` - Whether to skip dependency checks
-pub fn execute_topic(topic: &registry::Topic, no_docs: bool, force: bool) -> Result<(), String> {
-    // Check dependencies unless --force is set
-    if !force && topic.has_dependencies() {
+/// * `args` - Run options: `--no-docs`, `--force`, `--section`, `--no-pager`,
+///   `--timeout` and the options declared by the topic
+///
+/// # Errors
+/// A [`TopicError`] for invalid options, unavailable services, a failed or
+/// panicking sample, a timeout or Ctrl-C (which only stops a server cleanly)
+pub fn execute_topic(topic: &registry::Topic, args: &CategoryArgs) -> Result<(), TopicError> {
+    let values = topic
+        .resolve_args(&args.topic_args)
+        .map_err(TopicError::InvalidArgs)?;
+
+    // Probe external services unless --force is set
+    if !args.force && topic.has_dependencies() {
+        let unavailable: Vec<_> = doctor::probe_all(topic.dependencies)
+            .into_iter()
+            .filter_map(|(dependency, status)| match status {
+                doctor::Status::Available(_) => None,
+                doctor::Status::Unavailable(reason) => Some((dependency, reason)),
+            })
+            .collect();
+        if !unavailable.is_empty() {
+            eprintln!("{}", t!(SKIPPING_UNAVAILABLE, topic.name));
+            for (dependency, reason) in &unavailable {
+                eprintln!("   {} — {}", dependency, reason);
+                if let Some(env) = doctor::endpoint_env(*dependency) {
+                    eprintln!("     {}", t!(ENDPOINT_FROM, env));
+                }
+            }
+            eprintln!();
+            eprintln!("   {}", t!(HINT_FORCE));
+            eprintln!();
+            return Err(TopicError::DependencyUnavailable {
+                topic: topic.key(),
+                dependencies: unavailable.iter().map(|(d, _)| d.name()).collect(),
+            });
+        }
+    }
+
+    let mut progress = Progress::load();
+
+    // Display tutorial content unless --no-docs is set
+    if !args.no_docs
+        && let Some(doc_path) = topic.doc_path
+        && docs_embed::display_tutorial(doc_path, args.section.as_deref(), !args.no_pager)
+    {
+        progress.record_docs_read(topic);
+        println!();
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("{}", t!(RUNNING_SAMPLE));
+        println!();
+    }
+
+    // Save the run before executing so a panicking sample still counts
+    progress.record_run(topic);
+    progress.save();
+
+    // --timeout overrides the topic's own limit; 0 disables it
+    let timeout = match args.timeout {
+        Some(0) => None,
+        Some(seconds) => Some(Duration::from_secs(seconds)),
+        None => topic.timeout,
+    };
+
+    if let registry::SampleType::Server = topic.sample_type {
+        // Servers run until interrupted; `hello serve` marks them completed
+        eprintln!("{}", t!(SERVER_NOTE, topic.name));
         eprintln!(
-            "⚠ Warning: Topic '{}' requires external services: {}.",
-            topic.name,
-            topic.dependencies.join(", ")
+            "   {}",
+            t!(HINT_SERVE_IN_BACKGROUND, topic.category, topic.name)
         );
-        eprintln!("   These services may not be available.");
-        eprintln!();
-        eprintln!("   Run with --force to skip this check.");
         eprintln!();
     }
 
-    // Check if this is a server-type sample
-    match topic.sample_type {
-        registry::SampleType::Server => {
-            eprintln!("⚠ Note: Topic '{}' is a long-running server.", topic.name);
-            eprintln!("   It requires a separate terminal.");
-            eprintln!();
-        }
-        registry::SampleType::Function => {
-            // Display tutorial content unless --no-docs is set
-            if !no_docs {
-                if let Some(doc_path) = topic.doc_path {
-                    if docs_embed::display_tutorial(doc_path) {
-                        println!();
-                        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-                        println!("▶ Running sample:");
-                        println!();
-                    }
-                }
+    let completion = topic
+        .run(values, timeout)
+        .map_err(|source| TopicError::Failed {
+            topic: topic.key(),
+            source,
+        })?;
+    match completion {
+        Completion::Finished => {
+            if let registry::SampleType::Function = topic.sample_type {
+                progress.record_completed(topic);
+                progress.save();
             }
-
-            // Execute the sample function
-            (topic.func)();
+            Ok(())
+        }
+        Completion::TimedOut(timeout) => Err(TopicError::TimedOut {
+            topic: topic.key(),
+            timeout,
+        }),
+        // Ctrl-C is how a server is meant to stop
+        Completion::Cancelled if matches!(topic.sample_type, registry::SampleType::Server) => {
+            Ok(())
         }
+        Completion::Cancelled => Err(TopicError::Cancelled { topic: topic.key() }),
     }
-
-    Ok(())
 }
 
 /// List all topics in a category
@@ -61,27 +166,35 @@ pub fn list_category(category: &str) {
     let topics: Vec<_> = registry::get_topics_by_category(category).collect();
 
     if topics.is_empty() {
-        eprintln!("No topics found in category '{}'.", category);
+        eprintln!("{}", t!(NO_TOPICS_IN_CATEGORY, category));
         return;
     }
 
+    println!();
+    let display_name =
+        registry::get_category(category).map_or(category, |c| c.localized_display_name());
+    println!("{}", t!(CATEGORY_TOPICS, display_name, category));
     println!();
     println!(
-        "{} topics — Run: hello {} <topic>",
-        get_category_display_name(category),
-        category
+        "{}  {}  {}",
+        i18n::pad(t!(HEADER_TOPIC), 25),
+        i18n::pad(t!(HEADER_DESCRIPTION), 45),
+        t!(HEADER_DEPENDENCIES)
     );
-    println!();
-    println!("{:<25}  {:<45}  {}", "TOPIC", "DESCRIPTION", "DEPENDENCIES");
     println!("{:-<25}  {:-<45}  {:-<15}", "", "", "");
 
     for topic in topics {
         let deps = if topic.dependencies.is_empty() {
-            "无".to_string()
+            t!(NO_DEPENDENCIES).to_string()
         } else {
-            topic.dependencies.join(", ")
+            topic.dependency_names()
         };
-        println!("{:<25}  {:<45}  {}", topic.name, topic.description, deps);
+        println!(
+            "{:<25}  {}  {}",
+            topic.name,
+            i18n::pad(topic.localized_description(), 45),
+            deps
+        );
     }
     println!();
 }
@@ -89,29 +202,1197 @@ pub fn list_category(category: &str) {
 /// List all categories and topics
 pub fn list_all() {
     println!();
-    println!("Hello Rust — Interactive Learning CLI");
+    println!("{}", t!(APP_TITLE));
+    println!();
+    println!("{}", t!(CATEGORIES));
+    println!();
+
+    for category in registry::get_all_categories() {
+        let topic_count = registry::get_topics_by_category(category.name).count();
+        println!(
+            "  {:<12}  {}  {}",
+            format!("{}:", category.name),
+            t!(TOPIC_COUNT, format!("{:>3}", topic_count)),
+            category.localized_display_name()
+        );
+    }
+    println!();
+    println!("{}", t!(HINT_CATEGORY_HELP));
+    println!("{}", t!(HINT_RUN_TOPIC));
+    println!();
+}
+
+/// List the catalog in `format`; `Table` shows the category overview
+pub fn list(format: Format) {
+    let entries = catalog::entries();
+    match format {
+        Format::Table => list_all(),
+        Format::Json => println!("{}", catalog::to_json(&entries)),
+        Format::Markdown => print!("{}", catalog::to_markdown(&entries, true)),
+        Format::Csv => print!("{}", catalog::to_csv(&entries)),
+    }
+}
+
+/// Regenerate or validate the book's topic catalog against the registry
+///
+/// # Arguments
+/// * `mode` - Write the generated files, or only check them
+/// * `docs_dir` - Directory of the book sources (holding `SUMMARY.md`)
+///
+/// # Returns
+/// `true` if the files were written or the book agrees with the registry
+pub fn sync_summary(mode: SummaryMode, docs_dir: &Path) -> bool {
+    match mode {
+        SummaryMode::Write => match catalog::write_summary(docs_dir) {
+            Ok(changed) if changed.is_empty() => {
+                println!("{}", t!(CATALOG_UP_TO_DATE));
+                true
+            }
+            Ok(changed) => {
+                for path in changed {
+                    println!("{}", t!(UPDATED_FILE, path));
+                }
+                true
+            }
+            Err(e) => {
+                eprintln!("{}", t!(ERROR, format!("{:#}", e)));
+                false
+            }
+        },
+        SummaryMode::Check => match catalog::check_summary(docs_dir) {
+            Ok(problems) if problems.is_empty() => {
+                println!("{}", t!(BOOK_AGREES));
+                true
+            }
+            Ok(problems) => {
+                eprintln!("{}", t!(BOOK_DISAGREES));
+                for problem in problems {
+                    eprintln!("   {}", problem);
+                }
+                eprintln!();
+                eprintln!("   {}", t!(HINT_SUMMARY_WRITE));
+                false
+            }
+            Err(e) => {
+                eprintln!("{}", t!(ERROR, format!("{:#}", e)));
+                false
+            }
+        },
+    }
+}
+
+/// Width of the completion bars printed by `hello progress`
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// Show per-category completion and recently run topics
+pub fn show_progress() {
+    let progress = Progress::load();
+
     println!();
-    println!("Categories:");
+    println!("{}", t!(PROGRESS_TITLE, Progress::path().display()));
     println!();
 
+    let mut total = 0;
+    let mut total_completed = 0;
     for category in registry::get_all_categories() {
-        let topic_count = registry::get_topics_by_category(category).count();
-        println!("  {:<12}  {} topics", format!("{}:", category), topic_count);
+        let topics: Vec<_> = registry::get_topics_by_category(category.name).collect();
+        if topics.is_empty() {
+            continue;
+        }
+        let completed = topics.iter().filter(|t| progress.is_completed(t)).count();
+        total += topics.len();
+        total_completed += completed;
+        println!("  {}", progress_bar(category.name, completed, topics.len()));
+    }
+    println!("  {:-<12}", "");
+    println!(
+        "  {}",
+        progress_bar(t!(PROGRESS_TOTAL), total_completed, total)
+    );
+    println!();
+
+    let mut recent: Vec<_> = progress
+        .topics
+        .iter()
+        .filter_map(|(key, p)| p.last_run.map(|last_run| (last_run, key, p)))
+        .collect();
+    recent.sort_by(|a, b| b.0.cmp(&a.0));
+    if !recent.is_empty() {
+        println!("{}", t!(RECENTLY_RUN));
+        for (last_run, key, p) in recent.into_iter().take(5) {
+            println!(
+                "  {:<25}  {}  {}{}{}",
+                key,
+                last_run.format("%Y-%m-%d %H:%M"),
+                if p.completed {
+                    t!(STATUS_COMPLETED)
+                } else {
+                    t!(STATUS_UNFINISHED)
+                },
+                if p.docs_read {
+                    t!(STATUS_DOCS_READ)
+                } else {
+                    ""
+                },
+                if p.exercise_passed {
+                    t!(STATUS_EXERCISE_PASSED)
+                } else {
+                    ""
+                }
+            );
+        }
+        println!();
+    }
+    let questions = quiz::all_questions();
+    let quiz_correct = questions
+        .iter()
+        .filter(|q| progress.quiz_correct(&q.id))
+        .count();
+    let quiz_answered = questions
+        .iter()
+        .filter(|q| progress.quiz.contains_key(&q.id))
+        .count();
+    println!(
+        "{}",
+        t!(QUIZ_PROGRESS, quiz_correct, questions.len(), quiz_answered)
+    );
+    println!();
+    println!("{}", t!(HINT_NEXT));
+    println!();
+}
+
+/// Format one `hello progress` line: label, bar, count and percentage
+fn progress_bar(label: &str, completed: usize, total: usize) -> String {
+    let filled = (completed * PROGRESS_BAR_WIDTH)
+        .checked_div(total)
+        .unwrap_or(0);
+    let percent = (completed * 100).checked_div(total).unwrap_or(0);
+    format!(
+        "{}  [{}{}]  {:>3}/{:<3}  {:>3}%",
+        i18n::pad(label, 12),
+        "█".repeat(filled),
+        "░".repeat(PROGRESS_BAR_WIDTH - filled),
+        completed,
+        total,
+        percent
+    )
+}
+
+/// Clear all recorded progress
+pub fn reset_progress() {
+    Progress::default().save();
+    println!("{}", t!(PROGRESS_RESET));
+}
+
+/// Show (and optionally run) the next unfinished topic in learning order
+///
+/// # Arguments
+/// * `run` - Whether to run the topic right away
+/// * `no_docs` - Whether to skip tutorial display when running
+pub fn show_next(run: bool, no_docs: bool) {
+    let progress = Progress::load();
+    let Some(topic) = registry::learning_path()
+        .into_iter()
+        .find(|topic| !progress.is_completed(topic))
+    else {
+        println!();
+        println!("{}", t!(ALL_COMPLETED));
+        println!();
+        return;
+    };
+
+    println!();
+    println!(
+        "{}",
+        t!(NEXT_TOPIC, topic.key(), topic.localized_description())
+    );
+    let prerequisites = topic.prerequisite_topics();
+    if !prerequisites.is_empty() {
+        let keys: Vec<_> = prerequisites.iter().map(|t| t.key()).collect();
+        println!("  {}", t!(BUILDS_ON, keys.join(", ")));
+    }
+    println!();
+
+    if run {
+        let args = CategoryArgs {
+            no_docs,
+            ..CategoryArgs::default()
+        };
+        if let Err(e) = execute_topic(topic, &args) {
+            eprintln!("{}", e.render());
+            std::process::exit(e.exit_code());
+        }
+    } else {
+        println!("{}", t!(RUN_COMMAND, topic.category, topic.name));
+        println!();
+    }
+}
+
+/// Scaffold, build and check the exercise of a topic, or list all exercises
+///
+/// # Arguments
+/// * `topic` - Topic name or "category/name" (list exercises if `None`)
+/// * `dir` - Directory holding the exercise projects
+/// * `watch` - Re-check whenever a project file changes
+/// * `reset` - Overwrite the learner's `src/lib.rs` with the starter code
+///
+/// # Returns
+/// `true` if the exercise passed (or exercises were listed)
+pub fn exercise(topic: Option<&str>, dir: &Path, watch: bool, reset: bool) -> bool {
+    let Some(spec) = topic else {
+        list_exercises(dir);
+        return true;
+    };
+    let Some(topic) = registry::find_topic(spec) else {
+        eprintln!();
+        eprintln!("{}", t!(UNKNOWN_TOPIC, spec));
+        eprintln!();
+        return false;
+    };
+    let Some(exercise) = registry::get_exercise(topic) else {
+        eprintln!();
+        eprintln!("{}", t!(NO_EXERCISE, topic.key()));
+        eprintln!("{}", t!(HINT_LIST_EXERCISES));
+        eprintln!();
+        return false;
+    };
+
+    let project = exercise::project_dir(dir, topic);
+    match exercise::scaffold(exercise, &project, reset) {
+        Ok(true) => {
+            println!();
+            println!("{}", t!(EXERCISE_CREATED, project.display()));
+            println!("  {}", t!(EXERCISE_EDIT_HINT));
+            println!("  hello exercise {} --watch", topic.name);
+        }
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("{}", t!(ERROR, format!("{:#}", e)));
+            return false;
+        }
+    }
+
+    loop {
+        println!();
+        println!("{}", t!(EXERCISE_CHECKING, topic.key(), project.display()));
+        let passed = match exercise::check(exercise, &project) {
+            Ok(ExerciseOutcome::Passed) => {
+                println!("{}", t!(EXERCISE_PASSED));
+                let mut progress = Progress::load();
+                progress.record_exercise_passed(topic);
+                progress.save();
+                true
+            }
+            Ok(ExerciseOutcome::BuildFailed(diagnostics)) => {
+                println!("{}", t!(EXERCISE_BUILD_FAILED));
+                println!();
+                print!("{}", diagnostics);
+                false
+            }
+            Ok(ExerciseOutcome::TestsFailed(output)) => {
+                println!("{}", t!(EXERCISE_TESTS_FAILED));
+                println!();
+                print!("{}", output);
+                false
+            }
+            Err(e) => {
+                eprintln!("{}", t!(ERROR, format!("{:#}", e)));
+                false
+            }
+        };
+
+        if !watch {
+            return passed;
+        }
+        println!();
+        println!("{}", t!(EXERCISE_WATCHING, project.display()));
+        exercise::wait_for_change(&project);
+    }
+}
+
+/// List topics that ship an exercise and whether it passed
+fn list_exercises(dir: &Path) {
+    let progress = Progress::load();
+
+    println!();
+    println!("{}", t!(EXERCISES_HEADER));
+    println!();
+    for exercise in registry::get_all_exercises() {
+        let Some(topic) = exercise.topic() else {
+            continue;
+        };
+        let passed = progress.get(topic).is_some_and(|p| p.exercise_passed);
+        println!(
+            "  {} {:<25}  {}",
+            if passed { "✔" } else { " " },
+            topic.key(),
+            topic.localized_description()
+        );
+    }
+    println!();
+    println!("{}", t!(EXERCISE_PROJECTS_DIR, dir.display()));
+    println!();
+}
+
+/// Print the highlighted function a topic runs, or export it with `export`
+///
+/// # Arguments
+/// * `export` - Directory to write the standalone cargo project under
+///
+/// # Returns
+/// `false` if the topic is unknown, has no sample source or exporting failed
+pub fn source(category: &str, topic: &str, export: Option<&Path>) -> bool {
+    if registry::get_category(category).is_none() {
+        handle_unknown_category(category);
+        return false;
+    }
+    let Some(topic) = registry::get_topic(category, topic) else {
+        handle_unknown_topic(category, topic);
+        return false;
+    };
+    let Some(sample) = topic.sample_source else {
+        eprintln!("{}", t!(NO_SAMPLE_SOURCE, topic.key()));
+        return false;
+    };
+
+    let Some(dir) = export else {
+        let Some(function) = source::find_function(&sample) else {
+            eprintln!("{}", t!(SOURCE_NOT_FOUND, sample.function, sample.file));
+            return false;
+        };
+        println!();
+        println!(
+            "{}  {}:{}-{}",
+            topic.key(),
+            sample.file,
+            function.span.start,
+            function.span.end
+        );
+        let renderer = Renderer::for_stdout(topic.doc_path.unwrap_or(""));
+        print!(
+            "{}",
+            renderer.render(&format!("```rust\n{}\n```", function.code))
+        );
+        println!();
+        println!("{}", t!(HINT_EXPORT_SOURCE, topic.category, topic.name));
+        println!();
+        return true;
+    };
+
+    match source::export(topic, dir) {
+        Ok(export) => {
+            println!("{}", t!(SOURCE_EXPORTED, export.dir.display()));
+            println!("  cd {} && cargo run", export.dir.display());
+            if !export.dependencies.is_empty() {
+                println!(
+                    "{}",
+                    t!(SOURCE_DEPENDENCIES, export.dependencies.join(", "))
+                );
+            }
+            if !export.local_dependencies.is_empty() {
+                println!(
+                    "{}",
+                    t!(
+                        SOURCE_LOCAL_DEPENDENCIES,
+                        export.local_dependencies.join(", ")
+                    )
+                );
+            }
+            if !export.unresolved.is_empty() {
+                println!("{}", t!(SOURCE_UNRESOLVED, export.unresolved.join(", ")));
+            }
+            if export.needs_arguments {
+                println!("{}", t!(SOURCE_NEEDS_ARGUMENTS, sample.function));
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!("{}", t!(ERROR, format!("{:#}", e)));
+            false
+        }
+    }
+}
+
+/// List the example projects with their tutorials
+pub fn list_projects(dir: &Path) {
+    println!();
+    println!("{}", t!(PROJECTS_HEADER));
+    println!();
+    for project in projects::PROJECTS {
+        let tutorial_only = if project.dir.is_none() {
+            t!(PROJECT_TUTORIAL_ONLY)
+        } else {
+            ""
+        };
+        println!(
+            "  {} {}{}",
+            i18n::pad(project.name, 14),
+            project.description.get(),
+            tutorial_only
+        );
+        println!(
+            "  {} {}",
+            i18n::pad("", 14),
+            t!(PROJECT_TUTORIAL, format!("docs/src/{}", project.tutorial))
+        );
+    }
+    println!();
+    println!("{}", t!(PROJECTS_DIR, dir.display()));
+    println!();
+}
+
+/// Build and run an example project, or its tests, forwarding `args`
+///
+/// # Arguments
+/// * `name` - Project name, as listed by `hello projects --list`
+/// * `dir` - Directory holding the example projects
+///
+/// # Returns
+/// `true` if cargo (and so the project or its tests) succeeded
+pub fn project(name: &str, action: projects::Action, args: &[String], dir: &Path) -> bool {
+    let Some(project) = projects::find(name) else {
+        eprintln!();
+        eprintln!("{}", t!(UNKNOWN_PROJECT, name));
+        eprintln!("{}", t!(HINT_LIST_PROJECTS));
+        eprintln!();
+        return false;
+    };
+    let Some(project_dir) = project.project_dir(dir) else {
+        eprintln!();
+        eprintln!(
+            "{}",
+            t!(
+                PROJECT_NO_CODE,
+                project.name,
+                format!("docs/src/{}", project.tutorial)
+            )
+        );
+        eprintln!();
+        return false;
+    };
+
+    match projects::run(&project_dir, action, args) {
+        Ok(status) if status.success() => true,
+        Ok(status) => {
+            eprintln!("{}", t!(PROJECT_FAILED, project.name, status));
+            false
+        }
+        Err(e) => {
+            eprintln!("{}", t!(ERROR, format!("{:#}", e)));
+            false
+        }
+    }
+}
+
+/// Time a linked sample may run while checking a quiz answer
+const QUIZ_SAMPLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Ask quiz questions interactively and record the answers
+///
+/// Questions not yet answered correctly come first.
+///
+/// # Arguments
+/// * `target` - Category name, topic name or "category/name" (all if `None`)
+/// * `count` - Maximum number of questions to ask
+///
+/// # Returns
+/// `false` if `target` is unknown
+pub fn quiz(target: Option<&str>, count: usize) -> bool {
+    let mut questions = quiz::all_questions();
+    match target {
+        None => {}
+        Some(category) if registry::get_category(category).is_some() => {
+            questions.retain(|q| q.in_category(category));
+        }
+        Some(spec) => match registry::find_topic(spec) {
+            Some(topic) => questions.retain(|q| q.belongs_to(topic)),
+            None => {
+                eprintln!();
+                eprintln!("{}", t!(UNKNOWN_TARGET, spec));
+                let names: Vec<&str> = registry::get_all_categories()
+                    .into_iter()
+                    .map(|c| c.name)
+                    .chain(registry::get_all_topics().map(|t| t.name))
+                    .collect();
+                let suggestions = search::suggest(spec, names);
+                if !suggestions.is_empty() {
+                    eprintln!("{}", t!(DID_YOU_MEAN, suggestions.join(", ")));
+                }
+                eprintln!();
+                return false;
+            }
+        },
+    }
+
+    println!();
+    if questions.is_empty() {
+        println!("{}", t!(NO_QUIZ_QUESTIONS, target.unwrap_or(t!(QUIZ_ALL))));
+        println!();
+        return true;
+    }
+
+    let mut progress = Progress::load();
+    questions.sort_by_key(|q| progress.quiz_correct(&q.id));
+    questions.truncate(count);
+
+    let total = questions.len();
+    let mut input = io::stdin().lock();
+    let (mut answered, mut correct) = (0usize, 0usize);
+    for (index, question) in questions.iter().enumerate() {
+        println!(
+            "{}",
+            t!(
+                QUESTION_HEADER,
+                index + 1,
+                total,
+                question.doc_path,
+                question.label
+            )
+        );
+        println!();
+
+        let renderer = Renderer::for_stdout(question.doc_path);
+        print!("{}", renderer.render(&question.prompt));
+        for (letter, text) in &question.options {
+            println!("  {}) {}", letter, renderer.inline(text));
+        }
+        println!();
+
+        let kind = checked_kind(question);
+        let Some(is_correct) = ask(&mut input, &kind, &renderer, question) else {
+            println!();
+            break;
+        };
+        answered += 1;
+        if is_correct {
+            correct += 1;
+            println!("{}", t!(ANSWER_CORRECT));
+        } else if kind == Kind::Open {
+            println!("{}", t!(ANSWER_KEEP_PRACTICING));
+        } else {
+            println!(
+                "{}",
+                t!(
+                    ANSWER_INCORRECT,
+                    renderer.inline(&answer_text(question, &kind))
+                )
+            );
+        }
+        if kind != question.kind {
+            println!("  {}", t!(QUIZ_BANK_DISAGREES, question.answer));
+        }
+        if !question.explanation.is_empty() {
+            println!();
+            print!("{}", renderer.render(&question.explanation));
+        }
+        println!();
+
+        progress.record_quiz_answer(&question.id, is_correct);
+        progress.save();
+    }
+
+    let percent = (correct * 100).checked_div(answered).unwrap_or(0);
+    println!("{}", t!(QUIZ_SCORE, correct, answered, percent));
+    println!();
+    true
+}
+
+/// Grading of `question`, with the answer of a predict-the-output question
+/// taken from the real output of its linked sample
+fn checked_kind(question: &Question) -> Kind {
+    let (Kind::MultipleChoice(documented), Some(topic)) = (&question.kind, question.sample_topic())
+    else {
+        return question.kind.clone();
+    };
+    if topic.has_dependencies() {
+        return question.kind.clone();
+    }
+
+    println!("  {}", t!(CHECKING_ANSWER, topic.category, topic.name));
+    println!();
+    match verify::run_topic(topic, QUIZ_SAMPLE_TIMEOUT) {
+        Ok(captured) if !captured.timed_out => Kind::MultipleChoice(
+            question
+                .option_in_output(&captured.stdout)
+                .unwrap_or(*documented),
+        ),
+        _ => question.kind.clone(),
+    }
+}
+
+/// Read and grade one answer; open questions reveal the answer and are self-graded
+///
+/// # Returns
+/// Whether the answer was correct, or `None` on `q` or end of input
+fn ask(
+    input: &mut impl BufRead,
+    kind: &Kind,
+    renderer: &Renderer,
+    question: &Question,
+) -> Option<bool> {
+    match kind {
+        Kind::MultipleChoice(expected) => loop {
+            let answer = read_answer(input, t!(PROMPT_CHOICE))?;
+            match answer.to_uppercase().chars().next() {
+                Some(letter @ 'A'..='D') if answer.chars().count() == 1 => {
+                    return Some(letter == *expected);
+                }
+                _ => println!("{}", t!(PROMPT_CHOICE_RETRY)),
+            }
+        },
+        Kind::TrueFalse(expected) => loop {
+            let answer = read_answer(input, t!(PROMPT_TRUE_FALSE))?;
+            match answer.to_lowercase().as_str() {
+                "y" | "yes" | "t" | "true" | "是" | "对" | "会" | "能" => {
+                    return Some(*expected);
+                }
+                "n" | "no" | "f" | "false" | "否" | "错" | "不" | "不会" | "不能" => {
+                    return Some(!*expected);
+                }
+                _ => println!("{}", t!(PROMPT_YES_NO_RETRY)),
+            }
+        },
+        Kind::Open => {
+            read_answer(input, t!(PROMPT_REVEAL))?;
+            println!();
+            print!("{}", renderer.render(&t!(ANSWER_REVEALED, question.answer)));
+            println!();
+            loop {
+                let answer = read_answer(input, t!(PROMPT_SELF_GRADE))?;
+                match answer.to_lowercase().as_str() {
+                    "y" | "yes" => return Some(true),
+                    "n" | "no" => return Some(false),
+                    _ => println!("{}", t!(PROMPT_YES_NO_RETRY)),
+                }
+            }
+        }
+    }
+}
+
+/// Print `prompt` and read a trimmed line; `None` on `q` or end of input
+fn read_answer(input: &mut impl BufRead, prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    let _ = io::stdout().flush();
+    let mut line = String::new();
+    match input.read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => {
+            let answer = line.trim().to_string();
+            (!answer.eq_ignore_ascii_case("q")).then_some(answer)
+        }
+    }
+}
+
+/// Answer text shown after a wrong answer
+fn answer_text(question: &Question, kind: &Kind) -> String {
+    match kind {
+        Kind::MultipleChoice(letter) => match question.option(*letter) {
+            Some(text) => format!("{}) {}", letter, text),
+            None => letter.to_string(),
+        },
+        _ => question.answer.clone(),
+    }
+}
+
+/// Probe every external service and print a status table
+///
+/// # Returns
+/// `true` if every service is available
+pub fn doctor() -> bool {
+    println!();
+    println!(
+        "{}",
+        t!(CHECKING_SERVICES, format!("{:?}", doctor::PROBE_TIMEOUT))
+    );
+    println!();
+    println!(
+        "{}  {}  {}  {}",
+        i18n::pad(t!(HEADER_SERVICE), 8),
+        i18n::pad(t!(HEADER_ENDPOINT), 40),
+        i18n::pad(t!(HEADER_TOPICS), 6),
+        t!(HEADER_DETAIL)
+    );
+    println!("{:-<8}  {:-<40}  {:-<6}  {:-<30}", "", "", "", "");
+
+    let results = doctor::probe_all(&registry::Dependency::ALL);
+    for (dependency, status) in &results {
+        let topics = registry::get_all_topics()
+            .filter(|t| t.dependencies.contains(dependency))
+            .count();
+        let (mark, detail) = match status {
+            doctor::Status::Available(detail) => ("✔", detail),
+            doctor::Status::Unavailable(reason) => ("✘", reason),
+        };
+        println!(
+            "{:<8}  {:<40}  {:>6}  {} {}",
+            dependency.name(),
+            doctor::display_endpoint(&doctor::endpoint(*dependency)),
+            topics,
+            mark,
+            detail
+        );
     }
     println!();
-    println!("Run `hello <category> --help` to list topics in a category.");
-    println!("Run `hello <category> <topic>` to run a specific sample.");
+
+    let unavailable: Vec<_> = results
+        .iter()
+        .filter(|(_, status)| !status.is_available())
+        .map(|(dependency, _)| *dependency)
+        .collect();
+    if unavailable.is_empty() {
+        println!("{}", t!(ALL_SERVICES_AVAILABLE));
+    } else {
+        println!("{}", t!(UNAVAILABLE_SKIPPED));
+        println!("{}", t!(HINT_ENDPOINT_VARS));
+        for dependency in &unavailable {
+            if let Some(env) = doctor::endpoint_env(*dependency) {
+                println!("  {:<16}  {}", env, dependency);
+            }
+        }
+    }
     println!();
+    unavailable.is_empty()
 }
 
-/// Get display name for a category
-fn get_category_display_name(category: &str) -> String {
-    match category {
-        "basic" => "Rust 基础 (Basic)".to_string(),
-        "advance" => "Rust 进阶 (Advance)".to_string(),
-        "awesome" => "生产级样例 (Awesome)".to_string(),
-        "algo" => "算法 (Algorithms)".to_string(),
-        _ => category.to_string(),
+/// Start a server topic in the background and wait until it is ready
+///
+/// # Arguments
+/// * `category` - Category of the server topic
+/// * `topic` - Name of the server topic
+/// * `port` - Port to listen on instead of the topic's default, for topics with a `--port` option
+/// * `timeout` - Time the server may take to accept connections
+///
+/// # Returns
+/// `true` if the server is running
+pub fn serve(category: &str, topic: &str, port: Option<u16>, timeout: Duration) -> bool {
+    if registry::get_category(category).is_none() {
+        handle_unknown_category(category);
+        return false;
+    }
+    let Some(topic) = registry::get_topic(category, topic) else {
+        handle_unknown_topic(category, topic);
+        return false;
+    };
+
+    if let Some(record) = serve::running(topic) {
+        println!("{}", t!(SERVER_ALREADY_RUNNING, record.topic, record.pid));
+        println!("  {}", t!(SERVER_LOG, record.log_file.display()));
+        return true;
+    }
+
+    let given: Vec<_> = port
+        .map(|port| ("port".to_string(), port.to_string()))
+        .into_iter()
+        .collect();
+    let values = match topic.resolve_args(&given) {
+        Ok(values) => values,
+        Err(message) => {
+            eprintln!("{}", t!(ERROR, message));
+            return false;
+        }
+    };
+
+    println!("{}", t!(SERVER_STARTING, topic.key()));
+    match serve::start(topic, &values, timeout) {
+        Ok(record) => {
+            let mut progress = Progress::load();
+            progress.record_completed(topic);
+            progress.save();
+
+            match record.port {
+                Some(port) => println!("{}", t!(SERVER_READY, record.topic, port)),
+                None => println!("{}", t!(SERVER_STARTED, record.topic)),
+            }
+            println!("  {}", t!(SERVER_PID, record.pid));
+            println!("  {}", t!(SERVER_LOG, record.log_file.display()));
+            println!();
+            println!("{}", t!(HINT_STOP, topic.name));
+            true
+        }
+        Err(e) => {
+            eprintln!("{}", t!(ERROR, format!("{:#}", e)));
+            false
+        }
+    }
+}
+
+/// Serve the tutorials as HTML on localhost until Ctrl-C is pressed
+///
+/// # Returns
+/// `true` if the server stopped with Ctrl-C
+pub fn docs_serve(port: u16) -> bool {
+    let listener = match runtime::runtime().block_on(docs_server::bind(port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("{}", t!(DOCS_BIND_FAILED, port, e));
+            return false;
+        }
+    };
+    if let Ok(addr) = listener.local_addr() {
+        println!("{}", t!(DOCS_SERVING, addr));
+    }
+    match runtime::run(docs_server::serve(listener), None) {
+        Ok(_) => {
+            println!();
+            println!("{}", t!(DOCS_STOPPED));
+            true
+        }
+        Err(e) => {
+            eprintln!("{}", t!(ERROR, format!("{:#}", e)));
+            false
+        }
+    }
+}
+
+/// List server topics running in the background
+///
+/// Records of servers that have exited are reported once and removed.
+pub fn ps() {
+    let records = serve::records();
+    let (running, exited): (Vec<_>, Vec<_>) = records.iter().partition(|r| r.is_alive());
+
+    println!();
+    if running.is_empty() {
+        println!("{}", t!(NO_SERVERS_HINT));
+    } else {
+        println!(
+            "{}  {:>7}  {:>5}  {}  {}",
+            i18n::pad(t!(HEADER_TOPIC), 25),
+            t!(HEADER_PID),
+            t!(HEADER_PORT),
+            i18n::pad(t!(HEADER_STARTED), 16),
+            t!(HEADER_LOG)
+        );
+        println!(
+            "{:-<25}  {:->7}  {:->5}  {:-<16}  {:-<15}",
+            "", "", "", "", ""
+        );
+        for record in &running {
+            println!(
+                "{:<25}  {:>7}  {:>5}  {:<16}  {}",
+                record.topic,
+                record.pid,
+                record
+                    .port
+                    .map_or_else(|| "-".to_string(), |p| p.to_string()),
+                record.started.format("%Y-%m-%d %H:%M"),
+                record.log_file.display()
+            );
+        }
+    }
+    for record in exited {
+        println!(
+            "{}",
+            t!(
+                SERVER_EXITED,
+                record.topic,
+                record.pid,
+                record.log_file.display()
+            )
+        );
+        let _ = record.remove();
+    }
+    println!();
+}
+
+/// Stop background servers
+///
+/// # Arguments
+/// * `topic` - Server to stop, as "name" or "category/name" (all if `None`)
+/// * `grace` - Time to wait after SIGTERM before sending SIGKILL
+///
+/// # Returns
+/// `true` if every matching server was stopped
+pub fn stop(topic: Option<&str>, grace: Duration) -> bool {
+    let records: Vec<_> = serve::records()
+        .into_iter()
+        .filter(|record| topic.is_none_or(|t| record.matches(t)))
+        .collect();
+
+    if records.is_empty() {
+        match topic {
+            Some(topic) => eprintln!("{}", t!(NO_SERVER_MATCHES, topic)),
+            None => println!("{}", t!(NO_SERVERS)),
+        }
+        return topic.is_none();
+    }
+
+    let mut stopped = true;
+    for record in records {
+        match serve::stop(&record, grace) {
+            Ok(false) => println!("{}", t!(SERVER_STOPPED, record.topic, record.pid)),
+            Ok(true) => println!(
+                "{}",
+                t!(
+                    SERVER_KILLED,
+                    record.topic,
+                    record.pid,
+                    format!("{:?}", grace)
+                )
+            ),
+            Err(e) => {
+                stopped = false;
+                eprintln!("✘ {} — {:#}", record.topic, e);
+            }
+        }
+    }
+    stopped
+}
+
+/// Maximum number of changed lines printed per mismatching topic
+const MAX_DIFF_LINES: usize = 20;
+
+/// Verify Function topics against their golden-output snapshots
+///
+/// # Arguments
+/// * `category` - Only verify this category (all categories if `None`)
+/// * `bless` - Write the current output as the new snapshots
+/// * `snapshot_dir` - Directory holding the snapshot files
+/// * `timeout` - Time a topic may run before it is killed
+///
+/// # Returns
+/// `true` if every topic matched (or was blessed)
+pub fn verify(category: Option<&str>, bless: bool, snapshot_dir: &Path, timeout: Duration) -> bool {
+    if let Some(category) = category
+        && registry::get_category(category).is_none()
+    {
+        handle_unknown_category(category);
+        return false;
+    }
+
+    let topics = verify::verifiable_topics(category);
+    println!();
+    println!("{}", t!(VERIFYING, topics.len(), snapshot_dir.display()));
+    println!();
+
+    let (mut passed, mut blessed, mut failed, mut missing) = (0, 0, 0, 0);
+    for topic in topics {
+        let captured = match verify::run_topic(topic, timeout) {
+            Ok(captured) => captured,
+            Err(e) => {
+                failed += 1;
+                println!("  ✘ {} — {:#}", topic.key(), e);
+                continue;
+            }
+        };
+        if captured.timed_out {
+            failed += 1;
+            println!(
+                "  ✘ {} — {}",
+                topic.key(),
+                t!(TIMED_OUT_AFTER, format!("{:?}", timeout))
+            );
+            continue;
+        }
+
+        match verify::check_snapshot(snapshot_dir, topic, &captured, bless) {
+            Ok(Outcome::Passed) => {
+                passed += 1;
+                println!("  ✔ {}", topic.key());
+            }
+            Ok(Outcome::Blessed) => {
+                blessed += 1;
+                println!("  ★ {} — {}", topic.key(), t!(SNAPSHOT_UPDATED));
+            }
+            Ok(Outcome::Missing(path)) => {
+                missing += 1;
+                println!("  ? {} — {}", topic.key(), t!(SNAPSHOT_MISSING, path));
+            }
+            Ok(Outcome::Mismatch(diff)) => {
+                failed += 1;
+                println!("  ✘ {} — {}", topic.key(), t!(SNAPSHOT_MISMATCH));
+                let changed = diff.iter().filter_map(|line| match line {
+                    DiffLine::Same(_) => None,
+                    DiffLine::Removed(text) => Some(format!("      - {}", text)),
+                    DiffLine::Added(text) => Some(format!("      + {}", text)),
+                });
+                for line in changed.take(MAX_DIFF_LINES) {
+                    println!("{}", line);
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                println!("  ✘ {} — {:#}", topic.key(), e);
+            }
+        }
+    }
+
+    println!();
+    println!("{}", t!(VERIFY_SUMMARY, passed, blessed, failed, missing));
+    if failed + missing > 0 && !bless {
+        println!("{}", t!(HINT_BLESS));
+    }
+    println!();
+    failed + missing == 0
+}
+
+/// Run every topic of `category` (or all) in a child process and write reports
+///
+/// Server topics and topics with unavailable services are skipped. Returns
+/// `false` when a topic failed or timed out, or the reports could not be written.
+///
+/// # Arguments
+/// * `category` - Only run topics of this category
+/// * `report_dir` - Directory for the JUnit XML and JSON reports
+/// * `timeout` - Time a topic may run before it is killed
+pub fn run_all(category: Option<&str>, report_dir: &Path, timeout: Duration) -> bool {
+    if let Some(category) = category
+        && registry::get_category(category).is_none()
+    {
+        handle_unknown_category(category);
+        return false;
+    }
+
+    let topics = run_all::topics(category);
+    let probes = run_all::probe_dependencies(&topics);
+    println!();
+    println!("{}", t!(RUNNING_TOPICS, topics.len(), timeout.as_secs()));
+    println!();
+
+    let mut reports = Vec::with_capacity(topics.len());
+    for topic in topics {
+        let report = match run_all::skip_reason(topic, &probes) {
+            Some(reason) => run_all::skipped(topic, reason),
+            None => run_all::run_topic(topic, timeout),
+        };
+        match &report.outcome {
+            RunOutcome::Passed => println!("  ✔ {} ({:.2}s)", topic.key(), report.seconds),
+            RunOutcome::Failed(message) => println!("  ✘ {} — {}", topic.key(), message),
+            RunOutcome::TimedOut => println!(
+                "  ✘ {} — {}",
+                topic.key(),
+                t!(TIMED_OUT_AFTER, format!("{:?}", timeout))
+            ),
+            RunOutcome::Skipped(reason) => println!("  ⏭ {} — {}", topic.key(), reason),
+        }
+        reports.push(report);
+    }
+
+    let totals = run_all::Totals::of(&reports);
+    println!();
+    println!(
+        "{}",
+        t!(
+            RUN_ALL_SUMMARY,
+            totals.passed,
+            totals.failed,
+            totals.timed_out,
+            totals.skipped
+        )
+    );
+    let written = match run_all::write_reports(report_dir, &reports) {
+        Ok((junit, json)) => {
+            println!("{}", t!(REPORTS_WRITTEN, junit.display(), json.display()));
+            true
+        }
+        Err(e) => {
+            eprintln!("{}", t!(ERROR, format!("{:#}", e)));
+            false
+        }
+    };
+    println!();
+    written && totals.failures() == 0
+}
+
+/// Print the completion script of `command` for `shell`
+pub fn completions(shell: Shell, command: Command) -> bool {
+    if let Err(e) = completions::generate(shell, command, &mut io::stdout()) {
+        eprintln!("{}", t!(ERROR, e));
+        return false;
+    }
+    true
+}
+
+/// Print the `hello` man page, or write every page to `out_dir`
+///
+/// # Returns
+/// * `true` - The pages were written
+/// * `false` - Writing failed (reported on stderr)
+pub fn man(command: Command, out_dir: Option<&Path>) -> bool {
+    let written = match out_dir {
+        Some(dir) => manpage::write_all(command, dir).map(|paths| {
+            for path in paths {
+                println!("{}", t!(MAN_WRITTEN, path.display()));
+            }
+        }),
+        None => manpage::render_main(command, &mut io::stdout()),
+    };
+    if let Err(e) = written {
+        eprintln!("{}", t!(ERROR, e));
+        return false;
+    }
+    true
+}
+
+/// Search topics and tutorials and print ranked results
+///
+/// # Arguments
+/// * `query` - Whitespace-separated search terms
+/// * `limit` - Maximum number of tutorial sections to show
+pub fn search(query: &str, limit: usize) {
+    let hits = search::search(query);
+    let (topics, docs): (Vec<_>, Vec<_>) = hits
+        .iter()
+        .partition(|hit| matches!(hit.source, HitSource::Topic(_)));
+
+    println!();
+    println!("{}", t!(SEARCH_RESULTS, query));
+    println!();
+
+    if !topics.is_empty() {
+        println!("{}", t!(SEARCH_TOPICS));
+        for hit in &topics {
+            if let HitSource::Topic(topic) = hit.source {
+                println!(
+                    "  {:<30}  {}",
+                    format!("hello {} {}", topic.category, topic.name),
+                    topic.localized_description()
+                );
+            }
+        }
+        println!();
+    }
+
+    if !docs.is_empty() {
+        println!("{}", t!(SEARCH_TUTORIALS));
+        for hit in docs.iter().take(limit) {
+            if let HitSource::Doc {
+                path,
+                heading,
+                snippet,
+            } = &hit.source
+            {
+                println!("  {} § {}", path, heading);
+                println!("      {}", snippet);
+                let runnable = registry::get_all_topics().find(|t| t.doc_path == Some(*path));
+                if let Some(topic) = runnable {
+                    println!("      → hello {} {}", topic.category, topic.name);
+                }
+            }
+        }
+        if docs.len() > limit {
+            println!("  {}", t!(SEARCH_MORE, docs.len() - limit));
+        }
+        println!();
+    }
+
+    // Offer close topic names when nothing matched by name
+    let names: Vec<&str> = registry::get_all_topics().map(|t| t.name).collect();
+    let suggestions: Vec<&str> = query
+        .split_whitespace()
+        .flat_map(|term| search::suggest(term, names.iter().copied()))
+        .filter(|name| {
+            !topics
+                .iter()
+                .any(|hit| matches!(hit.source, HitSource::Topic(t) if t.name == *name))
+        })
+        .collect();
+    if !suggestions.is_empty() {
+        println!("{}", t!(DID_YOU_MEAN, suggestions.join(", ")));
+        println!();
+    } else if hits.is_empty() {
+        println!("{}", t!(SEARCH_NO_RESULTS));
+        println!();
     }
 }
 
@@ -126,12 +1407,17 @@ pub fn handle_unknown_topic(category: &str, topic: &str) {
         .collect();
 
     eprintln!();
-    eprintln!("Error: Unknown topic '{}' in '{}'.", topic, category);
+    eprintln!("{}", t!(UNKNOWN_TOPIC_IN, topic, category));
     eprintln!();
-    eprintln!("Available topics:");
+
+    let suggestions = search::suggest(topic, available.iter().copied());
+    if !suggestions.is_empty() {
+        eprintln!("{}", t!(DID_YOU_MEAN, suggestions.join(", ")));
+        eprintln!();
+    }
+
+    eprintln!("{}", t!(AVAILABLE_TOPICS));
     for name in available {
-        // Simple suggestion: show all available topics
-        // Could implement fuzzy matching here
         eprintln!("  {}", name);
     }
     eprintln!();
@@ -143,11 +1429,11 @@ pub fn handle_unknown_topic(category: &str, topic: &str) {
 /// * `category` - The category name that was not found
 pub fn handle_unknown_category(category: &str) {
     eprintln!();
-    eprintln!("Error: Unknown category '{}'.", category);
+    eprintln!("{}", t!(UNKNOWN_CATEGORY, category));
     eprintln!();
-    eprintln!("Available categories:");
+    eprintln!("{}", t!(AVAILABLE_CATEGORIES));
     for cat in registry::get_all_categories() {
-        eprintln!("  {}", cat);
+        eprintln!("  {:<12}  {}", cat.name, cat.localized_display_name());
     }
     eprintln!();
 }
@@ -2,8 +2,15 @@
 //!
 //! Compiles docs/src/ markdown files into the binary at compile time.
 //! No runtime file I/O required.
+//!
+//! Tutorials are written in Chinese; a variant for a language sits next to its
+//! original with the language before the extension (`algo/algo.en.md`).
+
+use clap::ValueEnum;
+use include_dir::{include_dir, Dir, File};
 
-use include_dir::{include_dir, Dir};
+use crate::cli::i18n::{self, t, Lang};
+use crate::cli::render;
 
 /// Embed the entire docs/src/ directory at compile time
 pub const DOCS_DIR: Dir = include_dir!("docs/src");
@@ -22,22 +29,98 @@ pub fn get_tutorial_content(doc_path: &str) -> Option<&'static str> {
         .map(|f| f.contents_utf8().unwrap_or(""))
 }
 
-/// Display tutorial content with formatting
+/// Get the path of the variant of a tutorial in `lang`
+///
+/// # Returns
+/// `doc_path` with the language inserted before `.md` (`basic/ownership.en.md`)
+pub fn variant_path(doc_path: &str, lang: Lang) -> Option<String> {
+    doc_path
+        .strip_suffix(".md")
+        .map(|stem| format!("{}.{}.md", stem, lang.suffix()))
+}
+
+/// Get a tutorial in the output language, falling back to the original
+///
+/// The original (`ownership.md`) is written in Chinese; `ownership.en.md` and
+/// `ownership.zh.md` are variants for a language.
+///
+/// # Returns
+/// The path of the file found and its content
+pub fn get_localized_content(doc_path: &str) -> Option<(String, &'static str)> {
+    variant_path(doc_path, i18n::lang())
+        .and_then(|path| get_tutorial_content(&path).map(|content| (path, content)))
+        .or_else(|| get_tutorial_content(doc_path).map(|content| (doc_path.to_string(), content)))
+}
+
+/// Check whether `path` is a language variant rather than an original tutorial
+fn is_variant(path: &str) -> bool {
+    path.strip_suffix(".md")
+        .and_then(|stem| stem.rsplit_once('.'))
+        .is_some_and(|(_, suffix)| Lang::from_str(suffix, true).is_ok())
+}
+
+/// Display tutorial content rendered for the terminal
 ///
 /// # Arguments
-/// * `doc_path` - Relative path from docs/src/
+/// * `doc_path` - Relative path from docs/src/ of the original (its variant is shown if any)
+/// * `section` - Only show the section under the first heading containing this text
+/// * `pager` - Pipe long output through `$PAGER` when stdout is a terminal
 ///
 /// # Returns
 /// * `true` - Tutorial was found and displayed
-/// * `false` - Tutorial not found
-pub fn display_tutorial(doc_path: &str) -> bool {
-    if let Some(content) = get_tutorial_content(doc_path) {
-        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-        println!("{}", content);
-        true
+/// * `false` - Tutorial (or the requested section) not found
+pub fn display_tutorial(doc_path: &str, section: Option<&str>, pager: bool) -> bool {
+    let Some((doc_path, content)) = get_localized_content(doc_path) else {
+        return false;
+    };
+
+    let markdown = match section {
+        Some(heading) => match render::extract_section(content, heading) {
+            Some(markdown) => markdown,
+            None => {
+                eprintln!("{}", t!(SECTION_NOT_FOUND, heading, doc_path));
+                eprintln!("   {}", t!(AVAILABLE_SECTIONS));
+                for heading in render::headings(content) {
+                    eprintln!("     {}", heading);
+                }
+                eprintln!();
+                return false;
+            }
+        },
+        None => content.to_string(),
+    };
+
+    let text = format!(
+        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n{}",
+        render::Renderer::for_stdout(&doc_path).render(&markdown)
+    );
+    if pager {
+        render::page(&text);
     } else {
-        false
+        print!("{}", text);
     }
+    true
+}
+
+/// Get the position of a tutorial in the book's table of contents
+///
+/// # Arguments
+/// * `doc_path` - Relative path from docs/src/ (e.g., "basic/ownership.md")
+///
+/// # Returns
+/// * `Some(index)` - Index of the first `SUMMARY.md` link to `doc_path`
+/// * `None` - The tutorial is not linked from `SUMMARY.md`
+pub fn summary_position(doc_path: &str) -> Option<usize> {
+    summary_links(get_tutorial_content("SUMMARY.md")?).position(|link| link == doc_path)
+}
+
+/// Get the link targets of a `SUMMARY.md`, in order and without a leading "./"
+pub fn summary_links(summary: &str) -> impl Iterator<Item = &str> {
+    summary.lines().filter_map(|line| {
+        let start = line.find("](")? + 2;
+        let end = start + line[start..].find(')')?;
+        Some(line[start..end].trim_start_matches("./"))
+    })
 }
 
 /// List all markdown files in a category directory
@@ -64,3 +147,57 @@ pub fn list_category_docs(category: &str) -> Vec<String> {
 
     docs
 }
+
+/// List every embedded markdown file, recursing into subdirectories
+///
+/// Language variants are left out, so every tutorial is listed once.
+///
+/// # Returns
+/// Files in directory order; paths are relative to docs/src/
+pub fn all_markdown_files() -> Vec<&'static File<'static>> {
+    fn collect(dir: &'static Dir<'static>, files: &mut Vec<&'static File<'static>>) {
+        for entry in dir.entries() {
+            if let Some(file) = entry.as_file() {
+                if file.path().extension().is_some_and(|ext| ext == "md")
+                    && !file.path().to_str().is_some_and(is_variant)
+                {
+                    files.push(file);
+                }
+            } else if let Some(subdir) = entry.as_dir() {
+                collect(subdir, files);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    collect(&DOCS_DIR, &mut files);
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_path() {
+        assert_eq!(
+            variant_path("algo/algo.md", Lang::En).as_deref(),
+            Some("algo/algo.en.md")
+        );
+        assert_eq!(
+            variant_path("algo/algo.md", Lang::Zh).as_deref(),
+            Some("algo/algo.zh.md")
+        );
+        assert!(is_variant("algo/algo.en.md"));
+        assert!(!is_variant("algo/algo.md"));
+        assert!(!is_variant("basic/cfg-if.md"));
+    }
+
+    #[test]
+    fn test_variants_are_not_listed() {
+        assert!(get_tutorial_content("algo/algo.en.md").is_some());
+        assert!(all_markdown_files()
+            .iter()
+            .all(|file| !is_variant(file.path().to_str().unwrap())));
+    }
+}
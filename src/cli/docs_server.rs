@@ -0,0 +1,547 @@
+//! Local documentation server for `hello docs serve`
+//!
+//! Serves the embedded book ([`docs_embed::DOCS_DIR`]) as HTML rendered with
+//! pulldown-cmark, with `SUMMARY.md` as the navigation. `/search` answers
+//! with the ranking of `hello search`, and a page documenting a Function
+//! topic gets a "Run this sample" button: the topic runs in a child `hello`
+//! process whose output lines are streamed back as server-sent events.
+//!
+//! Starting a run is POST-only and refused unless `Host` (and `Origin`, when
+//! present) name this server on localhost, so other web pages open in the
+//! browser cannot start samples, even through DNS rebinding.
+
+use std::convert::Infallible;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::process::Stdio;
+
+use anyhow::{Context, Result};
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use pulldown_cmark::{html, CowStr, Event as MdEvent, Options, Parser, Tag};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::net::TcpListener;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt;
+
+use crate::cli::docs_embed::{self, DOCS_DIR};
+use crate::cli::i18n::{self, t};
+use crate::cli::registry::{self, SampleType, Topic};
+use crate::cli::search::{self, HitSource};
+
+/// Port served when `--port` is not given
+pub const DEFAULT_PORT: u16 = 3000;
+
+/// Maximum number of search results returned
+const SEARCH_LIMIT: usize = 20;
+
+/// Page layout; `{{...}}` placeholders are filled by [`render_page`]
+const PAGE: &str = include_str!("docs_page.html");
+
+/// Query of `/search`
+#[derive(Debug, Deserialize)]
+struct SearchQuery {
+    #[serde(default)]
+    q: String,
+}
+
+/// One result of `/search`
+#[derive(Debug, Serialize)]
+struct SearchResult {
+    title: String,
+    url: String,
+    snippet: String,
+}
+
+/// Bind the server to `port` on localhost
+pub async fn bind(port: u16) -> std::io::Result<TcpListener> {
+    TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port))).await
+}
+
+/// Serve the book on `listener` until the future is dropped
+pub async fn serve(listener: TcpListener) -> Result<()> {
+    let port = listener.local_addr()?.port();
+    axum::serve(listener, router(port))
+        .await
+        .context("Documentation server failed")
+}
+
+/// Routes of the documentation server listening on `port`
+pub fn router(port: u16) -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/search", get(search_docs))
+        .route("/run/{category}/{topic}", post(run_topic))
+        .route("/{*path}", get(file))
+        .with_state(port)
+}
+
+/// Check that a request comes from a page of this server
+///
+/// `Host` must be `localhost:port` or `127.0.0.1:port`, which a rebound
+/// domain name cannot fake, and `Origin`, sent by browsers with every POST,
+/// must be the matching `http://` origin. Clients without `Origin` (curl) pass.
+fn is_local_request(headers: &HeaderMap, port: u16) -> bool {
+    let local = |value: &str| {
+        ["localhost", "127.0.0.1"]
+            .iter()
+            .any(|host| value == format!("{}:{}", host, port))
+    };
+    let header = |name| headers.get(name).map(|value| value.to_str().unwrap_or(""));
+    let host_ok = header(header::HOST).is_some_and(local);
+    let origin_ok = header(header::ORIGIN)
+        .is_none_or(|origin| origin.strip_prefix("http://").is_some_and(local));
+    host_ok && origin_ok
+}
+
+/// Redirect to the first chapter of the book
+async fn index() -> Redirect {
+    let first = docs_embed::get_tutorial_content("SUMMARY.md")
+        .and_then(|summary| docs_embed::summary_links(summary).next())
+        .unwrap_or("README.md");
+    Redirect::to(&format!("/{}", html_path(first)))
+}
+
+/// Serve `path.html` rendered from `path.md`, or any other embedded file as is
+async fn file(Path(path): Path<String>) -> Response {
+    if let Some(stem) = path.strip_suffix(".html")
+        && let Some(page) = render_page(&format!("{}.md", stem))
+    {
+        return Html(page).into_response();
+    }
+    match DOCS_DIR.get_file(&path) {
+        Some(file) => (
+            [(header::CONTENT_TYPE, content_type(&path))],
+            file.contents(),
+        )
+            .into_response(),
+        None => (StatusCode::NOT_FOUND, t!(DOCS_NOT_FOUND, path)).into_response(),
+    }
+}
+
+/// Search topics and tutorials, as `hello search` does
+async fn search_docs(Query(query): Query<SearchQuery>) -> Json<Vec<SearchResult>> {
+    let results = search::search(&query.q)
+        .into_iter()
+        .filter_map(|hit| match hit.source {
+            HitSource::Topic(topic) => Some(SearchResult {
+                title: topic.key(),
+                url: format!("/{}", html_path(topic.doc_path?)),
+                snippet: topic.localized_description().to_string(),
+            }),
+            HitSource::Doc {
+                path,
+                heading,
+                snippet,
+            } => Some(SearchResult {
+                title: heading,
+                url: format!("/{}", html_path(path)),
+                snippet,
+            }),
+        })
+        .take(SEARCH_LIMIT)
+        .collect();
+    Json(results)
+}
+
+/// Run a Function topic in a child `hello` process and stream its output
+///
+/// Events: `stdout` and `stderr` carry one line each, `exit` the exit status.
+/// The child is killed when the client disconnects. Requests not coming from
+/// a page of this server are refused (see [`is_local_request`]).
+async fn run_topic(
+    State(port): State<u16>,
+    headers: HeaderMap,
+    Path((category, name)): Path<(String, String)>,
+) -> Response {
+    if !is_local_request(&headers, port) {
+        let host = headers
+            .get(header::ORIGIN)
+            .or_else(|| headers.get(header::HOST))
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("?");
+        return (StatusCode::FORBIDDEN, t!(DOCS_FORBIDDEN, host)).into_response();
+    }
+    let Some(topic) = registry::get_topic(&category, &name) else {
+        return (StatusCode::NOT_FOUND, t!(UNKNOWN_TOPIC_IN, name, category)).into_response();
+    };
+    if matches!(topic.sample_type, SampleType::Server) {
+        let message = t!(
+            DOCS_SERVER_TOPIC,
+            topic.category,
+            topic.name,
+            topic.category,
+            topic.name
+        );
+        return (StatusCode::BAD_REQUEST, message).into_response();
+    }
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    let child = Command::new(exe)
+        .args([topic.category, topic.name, "--no-docs"])
+        .args(["--lang", i18n::lang().suffix()])
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+
+    let (sender, receiver) = mpsc::channel(64);
+    let stdout = tokio::spawn(forward_lines(child.stdout.take(), "stdout", sender.clone()));
+    let stderr = tokio::spawn(forward_lines(child.stderr.take(), "stderr", sender.clone()));
+    tokio::spawn(async move {
+        let status = tokio::select! {
+            status = child.wait() => status,
+            () = sender.closed() => return,
+        };
+        let _ = tokio::join!(stdout, stderr);
+        let status = match status {
+            Ok(status) => status.to_string(),
+            Err(e) => e.to_string(),
+        };
+        let _ = sender
+            .send(Event::default().event("exit").data(status))
+            .await;
+    });
+
+    let events = ReceiverStream::new(receiver).map(Ok::<_, Infallible>);
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Send each line of `output` as an event named `kind`
+async fn forward_lines(
+    output: Option<impl AsyncRead + Unpin>,
+    kind: &'static str,
+    sender: mpsc::Sender<Event>,
+) {
+    let Some(output) = output else {
+        return;
+    };
+    let mut lines = BufReader::new(output).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if sender
+            .send(Event::default().event(kind).data(line))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Render the tutorial `doc_path` as a full page with navigation and run buttons
+///
+/// # Returns
+/// `None` if there is no such tutorial
+pub fn render_page(doc_path: &str) -> Option<String> {
+    let (_, markdown) = docs_embed::get_localized_content(doc_path)?;
+    let title = markdown
+        .lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .unwrap_or(doc_path);
+    let nav = docs_embed::get_tutorial_content("SUMMARY.md")
+        .map(|summary| render_markdown(summary, "SUMMARY.md"))
+        .unwrap_or_default();
+
+    let runs: String = registry::get_all_topics()
+        .filter(|topic| topic.doc_path == Some(doc_path))
+        .filter(|topic| matches!(topic.sample_type, SampleType::Function))
+        .map(run_button)
+        .collect();
+
+    Some(
+        PAGE.replace("{{lang}}", i18n::lang().suffix())
+            .replace("{{title}}", &escape(title))
+            .replace("{{search}}", t!(DOCS_SEARCH))
+            .replace("{{nav}}", &nav)
+            .replace("{{runs}}", &runs)
+            .replace("{{content}}", &render_markdown(markdown, doc_path)),
+    )
+}
+
+/// The "Run this sample" block of a topic
+fn run_button(topic: &Topic) -> String {
+    format!(
+        "<div class=\"run\" data-url=\"/run/{}/{}\"><button>{}</button> {}<pre hidden></pre></div>\n",
+        topic.category,
+        topic.name,
+        escape(&t!(DOCS_RUN_SAMPLE, format!("hello {} {}", topic.category, topic.name))),
+        escape(topic.localized_description())
+    )
+}
+
+/// Render the markdown of the book file `doc_path` to HTML, pointing links to
+/// other chapters at their pages
+///
+/// Relative links are resolved against `doc_path`, so the hrefs are absolute
+/// and work from pages at any depth. mdbook-admonish fences become
+/// `<div class="admonish KIND">` blocks.
+pub fn render_markdown(markdown: &str, doc_path: &str) -> String {
+    let markdown = admonitions_to_html(markdown);
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+    let events = Parser::new_ext(&markdown, options).map(|event| match event {
+        MdEvent::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => MdEvent::Start(Tag::Link {
+            link_type,
+            dest_url: page_link(dest_url, doc_path),
+            title,
+            id,
+        }),
+        event => event,
+    });
+    let mut out = String::new();
+    html::push_html(&mut out, events);
+    out
+}
+
+/// Replace a relative link to a `.md` file in `doc_path` by the absolute link to its page
+fn page_link<'a>(url: CowStr<'a>, doc_path: &str) -> CowStr<'a> {
+    if url.contains("://") || url.starts_with('#') || url.starts_with('/') {
+        return url;
+    }
+    let (path, anchor) = url
+        .split_once('#')
+        .map_or((&*url, None), |(p, a)| (p, Some(a)));
+    let Some(stem) = path.strip_suffix(".md") else {
+        return url;
+    };
+
+    // Resolve `./` and `../` against the directory of `doc_path`
+    let mut segments: Vec<&str> = doc_path.split('/').collect();
+    segments.pop();
+    for segment in stem.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let page = format!("/{}.html", segments.join("/"));
+    match anchor {
+        Some(anchor) => format!("{}#{}", page, anchor).into(),
+        None => page.into(),
+    }
+}
+
+/// Turn ```` ```admonish kind "title" ```` fences into HTML blocks around markdown
+fn admonitions_to_html(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut in_admonition = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if !in_admonition && let Some(info) = trimmed.strip_prefix("```admonish") {
+            let info = info.trim();
+            let (kind, title) = info.split_once(' ').unwrap_or((info, ""));
+            let kind = if kind.is_empty() { "note" } else { kind };
+            out.push_str(&format!("<div class=\"admonish {}\">\n", escape(kind)));
+            let title = title.trim().trim_matches('"');
+            if !title.is_empty() {
+                out.push_str(&format!(
+                    "<p class=\"admonish-title\">{}</p>\n",
+                    escape(title)
+                ));
+            }
+            out.push('\n');
+            in_admonition = true;
+        } else if in_admonition && trimmed.starts_with("```") {
+            out.push_str("\n</div>\n");
+            in_admonition = false;
+        } else {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// URL path of the page of a tutorial (`basic/ownership.md` → `basic/ownership.html`)
+fn html_path(doc_path: &str) -> String {
+    match doc_path.strip_suffix(".md") {
+        Some(stem) => format!("{}.html", stem),
+        None => doc_path.to_string(),
+    }
+}
+
+/// Content type of an embedded file, by extension
+fn content_type(path: &str) -> &'static str {
+    match path.rsplit('.').next() {
+        Some("html") => "text/html; charset=utf-8",
+        Some("md" | "txt") => "text/plain; charset=utf-8",
+        Some("css") => "text/css",
+        Some("js") => "text/javascript",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Escape text for HTML content and attribute values
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+
+    #[test]
+    fn test_links_to_chapters_point_to_pages() {
+        let html = render_markdown(
+            "[a](./basic/ownership.md) [b](trait.md#objects) [c](https://example.com/x.md)",
+            "SUMMARY.md",
+        );
+        assert!(html.contains("href=\"/basic/ownership.html\""));
+        assert!(html.contains("href=\"/trait.html#objects\""));
+        assert!(html.contains("href=\"https://example.com/x.md\""));
+
+        // Links of a nested chapter are relative to its directory
+        let html = render_markdown("[a](trait.md) [b](../algo/algo.md)", "basic/ownership.md");
+        assert!(html.contains("href=\"/basic/trait.html\""));
+        assert!(html.contains("href=\"/algo/algo.html\""));
+    }
+
+    #[test]
+    fn test_admonish_fence_becomes_block() {
+        let html = render_markdown("```admonish warning \"小心\"\n**bold**\n```\n", "README.md");
+        assert!(html.contains("<div class=\"admonish warning\">"));
+        assert!(html.contains("<p class=\"admonish-title\">小心</p>"));
+        assert!(html.contains("<strong>bold</strong>"));
+    }
+
+    #[test]
+    fn test_page_has_navigation_and_run_button() {
+        let page = render_page("basic/ownership.md").unwrap();
+        assert!(page.contains("href=\"/basic/expression.html\""));
+        assert!(page.contains("data-url=\"/run/basic/ownership\""));
+        assert!(!page.contains("{{"));
+        assert!(render_page("missing.md").is_none());
+    }
+
+    /// Send `method path` with `headers` to the server at `addr` and read the response
+    async fn request(addr: SocketAddr, method: &str, path: &str, headers: &str) -> String {
+        let request = format!(
+            "{} {} HTTP/1.1\r\n{}Content-Length: 0\r\nConnection: close\r\n\r\n",
+            method, path, headers
+        );
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        tokio::io::AsyncReadExt::read_to_string(&mut stream, &mut response)
+            .await
+            .unwrap();
+        response
+    }
+
+    #[test]
+    fn test_local_request_check() {
+        let headers = |pairs: &[(header::HeaderName, &str)]| {
+            pairs
+                .iter()
+                .map(|(name, value)| (name.clone(), value.parse().unwrap()))
+                .collect::<HeaderMap>()
+        };
+        let local = headers(&[(header::HOST, "localhost:3000")]);
+        assert!(is_local_request(&local, 3000));
+        assert!(!is_local_request(&local, 3001));
+        assert!(is_local_request(
+            &headers(&[
+                (header::HOST, "127.0.0.1:3000"),
+                (header::ORIGIN, "http://127.0.0.1:3000"),
+            ]),
+            3000
+        ));
+        assert!(!is_local_request(
+            &headers(&[(header::HOST, "evil.example:3000")]),
+            3000
+        ));
+        assert!(!is_local_request(
+            &headers(&[
+                (header::HOST, "localhost:3000"),
+                (header::ORIGIN, "http://evil.example"),
+            ]),
+            3000
+        ));
+        assert!(!is_local_request(&HeaderMap::new(), 3000));
+    }
+
+    #[test]
+    fn test_server_answers_pages_and_search() {
+        let get = |addr: SocketAddr, path: &str| {
+            let path = path.to_string();
+            async move { request(addr, "GET", &path, "Host: localhost\r\n").await }
+        };
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let listener = bind(0).await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(serve(listener));
+
+            let page = get(addr, "/basic/ownership.html").await;
+            assert!(page.starts_with("HTTP/1.1 200"));
+            assert!(page.contains("<nav>"));
+
+            // Navigation links of a nested page lead to existing pages
+            let nav = &page[page.find("<nav>").unwrap()..];
+            let href = nav
+                .split("href=\"")
+                .skip(1)
+                .filter_map(|rest| rest.split('"').next())
+                .find(|href| href.ends_with(".html") && href != &"/basic/ownership.html")
+                .unwrap();
+            assert!(href.starts_with('/'));
+            let linked = get(addr, href).await;
+            assert!(linked.starts_with("HTTP/1.1 200"), "{} is broken", href);
+
+            let results = get(addr, "/search?q=ownership").await;
+            assert!(results.contains("/basic/ownership.html"));
+
+            let missing = get(addr, "/nope.html").await;
+            assert!(missing.starts_with("HTTP/1.1 404"));
+
+            // Runs are POST-only and accepted from this server's pages only
+            let run = "/run/advance/tokio-server";
+            let host = format!("Host: localhost:{}\r\n", addr.port());
+            let server = request(addr, "POST", run, &host).await;
+            assert!(server.starts_with("HTTP/1.1 400"));
+            let by_get = request(addr, "GET", run, &host).await;
+            assert!(by_get.starts_with("HTTP/1.1 405"));
+            let foreign = format!("Host: evil.example:{}\r\n", addr.port());
+            let rebound = request(addr, "POST", run, &foreign).await;
+            assert!(rebound.starts_with("HTTP/1.1 403"));
+            let cross_site = format!("{}Origin: http://evil.example\r\n", host);
+            let cross_site = request(addr, "POST", run, &cross_site).await;
+            assert!(cross_site.starts_with("HTTP/1.1 403"));
+        });
+    }
+}
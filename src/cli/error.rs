@@ -0,0 +1,178 @@
+//! Errors of running a topic and the process exit codes they map to
+//!
+//! CI scripts tell the failure kinds apart by exit code, e.g. "MySQL was
+//! down" (3) from "the sample is broken" (4).
+
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use crate::cli::i18n::{self, Lang};
+use crate::cli::messages;
+
+/// Exit code of an unknown topic or invalid topic options (same as clap usage errors)
+pub const EXIT_USAGE: i32 = 2;
+
+/// Exit code of a topic whose external services are unavailable
+pub const EXIT_DEPENDENCY_UNAVAILABLE: i32 = 3;
+
+/// Exit code of a sample that returned an error or panicked
+pub const EXIT_SAMPLE_FAILED: i32 = 4;
+
+/// Exit code of an async sample cancelled by its timeout
+pub const EXIT_TIMED_OUT: i32 = 5;
+
+/// Exit code of a sample interrupted with Ctrl-C (128 + SIGINT)
+pub const EXIT_CANCELLED: i32 = 130;
+
+/// Why a topic did not run to completion
+#[derive(Debug)]
+pub enum TopicError {
+    /// A topic option was unknown or had an invalid value
+    InvalidArgs(String),
+    /// Required services did not answer their probe
+    DependencyUnavailable {
+        topic: String,
+        dependencies: Vec<&'static str>,
+    },
+    /// The sample returned an error or panicked
+    Failed {
+        topic: String,
+        source: anyhow::Error,
+    },
+    /// The sample exceeded its timeout
+    TimedOut { topic: String, timeout: Duration },
+    /// The sample was interrupted with Ctrl-C
+    Cancelled { topic: String },
+}
+
+impl TopicError {
+    /// Process exit code for this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            TopicError::InvalidArgs(_) => EXIT_USAGE,
+            TopicError::DependencyUnavailable { .. } => EXIT_DEPENDENCY_UNAVAILABLE,
+            TopicError::Failed { .. } => EXIT_SAMPLE_FAILED,
+            TopicError::TimedOut { .. } => EXIT_TIMED_OUT,
+            TopicError::Cancelled { .. } => EXIT_CANCELLED,
+        }
+    }
+
+    /// Describe the error in `lang` (causes are not included)
+    pub fn message_in(&self, lang: Lang) -> String {
+        match self {
+            TopicError::InvalidArgs(message) => message.clone(),
+            TopicError::DependencyUnavailable {
+                topic,
+                dependencies,
+            } => i18n::fill(
+                messages::ERR_DEPENDENCY_UNAVAILABLE.get_in(lang),
+                &[topic, &dependencies.join(", ")],
+            ),
+            TopicError::Failed { topic, .. } => {
+                i18n::fill(messages::ERR_SAMPLE_FAILED.get_in(lang), &[topic])
+            }
+            TopicError::TimedOut { topic, timeout } => i18n::fill(
+                messages::ERR_TIMED_OUT.get_in(lang),
+                &[topic, &timeout.as_secs()],
+            ),
+            TopicError::Cancelled { topic } => {
+                i18n::fill(messages::ERR_CANCELLED.get_in(lang), &[topic])
+            }
+        }
+    }
+
+    /// Render the error and its chain of causes, one cause per line
+    pub fn render(&self) -> String {
+        self.render_in(i18n::lang())
+    }
+
+    /// Render the error and its chain of causes in `lang`
+    ///
+    /// The causes come from the sample and are shown as they are.
+    pub fn render_in(&self, lang: Lang) -> String {
+        let mut rendered = i18n::fill(messages::ERROR.get_in(lang), &[&self.message_in(lang)]);
+        let mut source = self.source();
+        if source.is_some() {
+            rendered.push_str("\n\n");
+            rendered.push_str(messages::CAUSED_BY.get_in(lang));
+        }
+        let mut depth = 0;
+        while let Some(cause) = source {
+            rendered.push_str(&format!("\n    {}: {}", depth, cause));
+            source = cause.source();
+            depth += 1;
+        }
+        rendered
+    }
+}
+
+impl fmt::Display for TopicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message_in(i18n::lang()))
+    }
+}
+
+impl Error for TopicError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TopicError::Failed { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_render_error_chain() {
+        let source = Err::<(), _>(std::io::Error::other("connection refused"))
+            .context("Failed to connect to MySQL at 127.0.0.1:3306")
+            .unwrap_err();
+        let error = TopicError::Failed {
+            topic: "advance/sqlx-mysql".to_string(),
+            source,
+        };
+        assert_eq!(
+            error.render_in(Lang::En),
+            "Error: sample 'advance/sqlx-mysql' failed\n\n\
+             Caused by:\n    \
+             0: Failed to connect to MySQL at 127.0.0.1:3306\n    \
+             1: connection refused"
+        );
+        assert!(error
+            .render_in(Lang::Zh)
+            .starts_with("错误：示例 'advance/sqlx-mysql' 运行失败\n\n原因：\n"));
+        assert_eq!(error.exit_code(), EXIT_SAMPLE_FAILED);
+    }
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            TopicError::DependencyUnavailable {
+                topic: "advance/sqlx-mysql".into(),
+                dependencies: vec!["MySQL"],
+            },
+            TopicError::Failed {
+                topic: "advance/dotenv".into(),
+                source: anyhow::anyhow!("boom"),
+            },
+            TopicError::TimedOut {
+                topic: "advance/ollama".into(),
+                timeout: Duration::from_secs(300),
+            },
+            TopicError::Cancelled {
+                topic: "advance/tokio-server".into(),
+            },
+        ];
+        let mut codes: Vec<_> = errors.iter().map(TopicError::exit_code).collect();
+        codes.push(EXIT_USAGE);
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len() + 1);
+        assert!(!codes.contains(&0) && !codes.contains(&1));
+    }
+}
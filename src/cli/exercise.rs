@@ -0,0 +1,219 @@
+//! Hands-on exercises: scaffold a cargo project, build it and run hidden tests
+//!
+//! An [`Exercise`] is written to `<dir>/<category>-<name>/` as a standalone
+//! cargo project (with an empty `[workspace]` so it never joins an enclosing
+//! one). Checking builds it with `cargo build --offline`, then runs the
+//! hidden tests from a temporary copy so they never show up in the
+//! learner's project.
+
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+
+use crate::cli::registry::{Exercise, Topic};
+
+/// Default directory (relative to the working directory) holding exercise projects
+pub const DEFAULT_EXERCISE_DIR: &str = "hello-exercises";
+
+/// Generated manifest of every exercise project
+const MANIFEST: &str = r#"[package]
+name = "exercise"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+
+# Keep the exercise out of any enclosing workspace
+[workspace]
+"#;
+
+/// Path of the hidden tests inside the checking copy
+const CHECK_FILE: &str = "tests/check.rs";
+
+/// Poll interval of `--watch`
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Result of checking an exercise
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    /// `cargo build` failed; holds the compiler diagnostics
+    BuildFailed(String),
+    /// The hidden tests failed; holds the test output
+    TestsFailed(String),
+    /// Every hidden test passed
+    Passed,
+}
+
+/// Directory of the project of `topic` inside `root`
+pub fn project_dir(root: &Path, topic: &Topic) -> PathBuf {
+    root.join(format!("{}-{}", topic.category, topic.name))
+}
+
+/// Write the exercise project to `dir` unless it already exists
+///
+/// With `reset`, an existing `src/lib.rs` is overwritten with the starter.
+///
+/// # Returns
+/// `true` if the starter code was written
+pub fn scaffold(exercise: &Exercise, dir: &Path, reset: bool) -> Result<bool> {
+    let lib = dir.join("src").join("lib.rs");
+    if lib.exists() && !reset {
+        return Ok(false);
+    }
+
+    fs::create_dir_all(dir.join("src"))
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    fs::write(dir.join("Cargo.toml"), MANIFEST)?;
+    fs::write(dir.join(".gitignore"), "/target\n")?;
+    fs::write(&lib, exercise.starter)
+        .with_context(|| format!("Failed to write {}", lib.display()))?;
+    Ok(true)
+}
+
+/// Build the project in `dir` and run the hidden tests against it
+pub fn check(exercise: &Exercise, dir: &Path) -> Result<Outcome> {
+    let target_dir = dir.join("target");
+
+    let build = run(&mut cargo(dir, &target_dir, &["build", "--offline"]))?;
+    if !build.status.success() {
+        return Ok(Outcome::BuildFailed(
+            String::from_utf8_lossy(&build.stderr).into_owned(),
+        ));
+    }
+
+    // Run the hidden tests from a copy, sharing the project's target directory
+    let copy = tempfile::tempdir()?;
+    fs::copy(dir.join("Cargo.toml"), copy.path().join("Cargo.toml"))?;
+    copy_dir(&dir.join("src"), &copy.path().join("src"))?;
+    fs::create_dir_all(copy.path().join("tests"))?;
+    fs::write(copy.path().join(CHECK_FILE), exercise.tests)?;
+
+    // Warnings were already the build's business; keep the report to the failures
+    let test = run(cargo(
+        copy.path(),
+        &target_dir.join("check"),
+        &["test", "--offline", "--quiet", "--test", "check"],
+    )
+    .env("RUSTFLAGS", "-A warnings")
+    .env("RUST_BACKTRACE", "0"))?;
+    if test.status.success() {
+        Ok(Outcome::Passed)
+    } else {
+        let mut output = String::from_utf8_lossy(&test.stdout).into_owned();
+        output.push_str(&String::from_utf8_lossy(&test.stderr));
+        Ok(Outcome::TestsFailed(output))
+    }
+}
+
+/// Block until a file of the project in `dir` changes (the target directory is ignored)
+pub fn wait_for_change(dir: &Path) {
+    let before = modification_times(dir);
+    loop {
+        thread::sleep(WATCH_INTERVAL);
+        if modification_times(dir) != before {
+            return;
+        }
+    }
+}
+
+/// Prepare `cargo` with `args` to run in `dir`
+fn cargo(dir: &Path, target_dir: &Path, args: &[&str]) -> Command {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let color = if std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none() {
+        "always"
+    } else {
+        "never"
+    };
+    let mut command = Command::new(cargo);
+    command
+        .args(args)
+        .args(["--color", color])
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", target_dir);
+    command
+}
+
+/// Run a prepared cargo command, capturing its output
+fn run(command: &mut Command) -> Result<Output> {
+    command
+        .output()
+        .context("Failed to run cargo; is it installed and on PATH?")
+}
+
+/// Recursively copy the directory `from` to `to`
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Modification times of every file below `dir`, skipping `target` and dot directories
+fn modification_times(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut times = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return times;
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let name = entry.file_name();
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            if name != "target" && !name.to_string_lossy().starts_with('.') {
+                times.extend(modification_times(&path));
+            }
+        } else {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok();
+            times.push((path, modified));
+        }
+    }
+    times.sort();
+    times
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::registry;
+
+    #[test]
+    fn test_scaffold_keeps_existing_work() {
+        let exercise = registry::get_all_exercises()[0];
+        let dir = tempfile::tempdir().unwrap();
+
+        assert!(scaffold(exercise, dir.path(), false).unwrap());
+        let lib = dir.path().join("src").join("lib.rs");
+        fs::write(&lib, "// my solution").unwrap();
+
+        assert!(!scaffold(exercise, dir.path(), false).unwrap());
+        assert_eq!(fs::read_to_string(&lib).unwrap(), "// my solution");
+
+        assert!(scaffold(exercise, dir.path(), true).unwrap());
+        assert_eq!(fs::read_to_string(&lib).unwrap(), exercise.starter);
+        assert!(dir.path().join("Cargo.toml").exists());
+    }
+
+    #[test]
+    fn test_modification_times_skip_target() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::create_dir_all(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join("src").join("lib.rs"), "").unwrap();
+        fs::write(dir.path().join("target").join("build.log"), "").unwrap();
+
+        let times = modification_times(dir.path());
+        assert_eq!(times.len(), 1);
+        assert!(times[0].0.ends_with("src/lib.rs"));
+    }
+}
@@ -0,0 +1,230 @@
+//! Language selection and localized text
+//!
+//! The output language comes from `--lang zh|en`, else from the `LANG`
+//! environment variable, else Chinese. Every CLI string is a [`Text`] of the
+//! message catalog in [`crate::cli::messages`]; a text without an English
+//! translation falls back to Chinese.
+
+use std::ffi::OsString;
+use std::fmt;
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+
+/// Environment variable the language is read from when `--lang` is not given
+pub const LANG_ENV: &str = "LANG";
+
+/// Output language
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Lang {
+    /// 简体中文
+    #[default]
+    Zh,
+    /// English
+    En,
+}
+
+impl Lang {
+    /// Parse a POSIX locale such as `en_US.UTF-8` or `zh_CN`
+    ///
+    /// # Returns
+    /// `None` for locales of other languages and for `C`/`POSIX`
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let language = locale
+            .split(['_', '.', '@', '-'])
+            .next()
+            .unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "zh" => Some(Lang::Zh),
+            "en" => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    /// Suffix of localized tutorial variants (`ownership.en.md`)
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Lang::Zh => "zh",
+            Lang::En => "en",
+        }
+    }
+}
+
+/// Language chosen at startup
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Choose the output language for the rest of the process
+///
+/// Must run before any text is printed; later calls have no effect.
+///
+/// # Arguments
+/// * `flag` - Value of `--lang`, which wins over `LANG`
+pub fn init(flag: Option<Lang>) -> Lang {
+    *LANG.get_or_init(|| {
+        flag.or_else(|| {
+            std::env::var(LANG_ENV)
+                .ok()
+                .and_then(|locale| Lang::from_locale(&locale))
+        })
+        .unwrap_or_default()
+    })
+}
+
+/// Get the output language (initialized from `LANG` if [`init`] was not called)
+pub fn lang() -> Lang {
+    init(None)
+}
+
+/// Find the value of `--lang` in raw command-line arguments
+///
+/// The language is needed before clap builds the localized `--help`, so the
+/// flag is scanned ahead of parsing. Invalid values are left for clap to report.
+pub fn lang_from_args(args: impl IntoIterator<Item = OsString>) -> Option<Lang> {
+    let mut args = args
+        .into_iter()
+        .map(|arg| arg.to_string_lossy().into_owned());
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        let value = match arg.strip_prefix("--lang") {
+            Some("") => args.next(),
+            Some(value) => value.strip_prefix('=').map(String::from),
+            None => None,
+        };
+        if let Some(value) = value {
+            return Lang::from_str(&value, true).ok();
+        }
+    }
+    None
+}
+
+/// A piece of user-facing text in Chinese and (optionally) English
+///
+/// `{}` placeholders are filled in order by [`Text::fill`] (or the [`t!`]
+/// macro); both languages must have the same number of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Text {
+    pub zh: &'static str,
+    /// English translation; empty if missing
+    pub en: &'static str,
+}
+
+impl Text {
+    /// Create a text from its Chinese original and English translation
+    pub const fn new(zh: &'static str, en: &'static str) -> Self {
+        Self { zh, en }
+    }
+
+    /// Get the text in `lang`, falling back to Chinese
+    pub fn get_in(&self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::En if !self.en.is_empty() => self.en,
+            _ => self.zh,
+        }
+    }
+
+    /// Get the text in the output language
+    pub fn get(&self) -> &'static str {
+        self.get_in(lang())
+    }
+
+    /// Get the text in the output language with its `{}` placeholders filled
+    pub fn fill(&self, args: &[&dyn fmt::Display]) -> String {
+        fill(self.get(), args)
+    }
+}
+
+impl fmt::Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.get())
+    }
+}
+
+/// Replace the `{}` placeholders of `template` with `args`, in order
+///
+/// Surplus placeholders are kept as they are; surplus arguments are ignored.
+pub fn fill(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(at) = rest.find("{}") {
+        filled.push_str(&rest[..at]);
+        match args.next() {
+            Some(arg) => filled.push_str(&arg.to_string()),
+            None => filled.push_str("{}"),
+        }
+        rest = &rest[at + 2..];
+    }
+    filled.push_str(rest);
+    filled
+}
+
+/// Pad `text` with spaces to `width` terminal columns (CJK characters take two)
+pub fn pad(text: &str, width: usize) -> String {
+    let columns = crate::cli::render::visible_width(text);
+    format!("{}{}", text, " ".repeat(width.saturating_sub(columns)))
+}
+
+/// Get a catalog text in the output language, filling `{}` placeholders
+///
+/// `t!(X)` is the `&'static str` of `messages::X`; `t!(X, a, b)` is a `String`.
+macro_rules! t {
+    ($key:ident) => {
+        $crate::cli::messages::$key.get()
+    };
+    ($key:ident, $($arg:expr),+ $(,)?) => {
+        $crate::cli::messages::$key.fill(&[$(&$arg),+])
+    };
+}
+pub(crate) use t;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(Lang::from_locale("en_US.UTF-8"), Some(Lang::En));
+        assert_eq!(Lang::from_locale("zh_CN.UTF-8"), Some(Lang::Zh));
+        assert_eq!(Lang::from_locale("zh-Hant"), Some(Lang::Zh));
+        assert_eq!(Lang::from_locale("C.UTF-8"), None);
+        assert_eq!(Lang::from_locale("fr_FR"), None);
+    }
+
+    #[test]
+    fn test_lang_from_args() {
+        let args = |line: &str| line.split(' ').map(OsString::from).collect::<Vec<_>>();
+        assert_eq!(lang_from_args(args("hello --lang en list")), Some(Lang::En));
+        assert_eq!(
+            lang_from_args(args("hello basic --lang=zh")),
+            Some(Lang::Zh)
+        );
+        assert_eq!(lang_from_args(args("hello list")), None);
+        assert_eq!(lang_from_args(args("hello -- --lang en")), None);
+    }
+
+    #[test]
+    fn test_text_falls_back_to_chinese() {
+        let text = Text::new("可用主题：", "");
+        assert_eq!(text.get_in(Lang::En), "可用主题：");
+        let text = Text::new("可用主题：", "Available topics:");
+        assert_eq!(text.get_in(Lang::En), "Available topics:");
+        assert_eq!(text.get_in(Lang::Zh), "可用主题：");
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(
+            fill("'{}' 不在 '{}' 中", &[&"x", &"basic"]),
+            "'x' 不在 'basic' 中"
+        );
+        assert_eq!(fill("{} of {}", &[&1]), "1 of {}");
+    }
+
+    #[test]
+    fn test_pad_counts_wide_characters() {
+        assert_eq!(pad("主题", 6), "主题  ");
+        assert_eq!(pad("topic", 6), "topic ");
+    }
+}
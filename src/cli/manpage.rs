@@ -0,0 +1,156 @@
+//! Roff man pages for `hello man`
+//!
+//! Pages are rendered by `clap_mangen` from the command definition, plus a
+//! TOPICS section listing the registered topics with their descriptions and
+//! dependencies: all topics on `hello(1)`, a category's own on `hello-<category>(1)`.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Command;
+use clap_mangen::Man;
+use roff::{bold, roman, Roff};
+
+use crate::cli::i18n::t;
+use crate::cli::registry::{self, Topic};
+
+/// Man page section of user commands
+const SECTION: &str = "1";
+
+/// Lines `roff` writes before every rendered fragment
+const PRELUDE: &[&str] = &[".ie \\n(.g .ds Aq \\(aq", ".el .ds Aq '"];
+
+/// Render the page of `command` (`hello` or a subcommand of it)
+///
+/// The command must be built, so subcommands carry their `hello-<name>` display name.
+pub fn render(command: &Command, topics: &[&Topic], out: &mut dyn Write) -> io::Result<()> {
+    let mut page = Vec::new();
+    render_sections(command, topics, &mut page)?;
+    // Each `render_*` call starts with the same prelude; keep the first one
+    let page = String::from_utf8_lossy(&page);
+    let mut seen = Vec::new();
+    for line in page.lines() {
+        if PRELUDE.contains(&line) {
+            if seen.contains(&line) {
+                continue;
+            }
+            seen.push(line);
+        }
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+fn render_sections(command: &Command, topics: &[&Topic], out: &mut dyn Write) -> io::Result<()> {
+    let man = Man::new(command.clone()).section(SECTION);
+    man.render_title(out)?;
+    man.render_name_section(out)?;
+    man.render_synopsis_section(out)?;
+    man.render_description_section(out)?;
+    if command.get_arguments().any(|arg| !arg.is_hide_set()) {
+        man.render_options_section(out)?;
+    }
+    if command.has_subcommands() {
+        man.render_subcommands_section(out)?;
+    }
+    if !topics.is_empty() {
+        topics_section(topics).to_writer(out)?;
+    }
+    if command.get_version().is_some() {
+        man.render_version_section(out)?;
+    }
+    if command.get_author().is_some() {
+        man.render_authors_section(out)?;
+    }
+    Ok(())
+}
+
+/// Render the `hello` page to `out`
+pub fn render_main(command: Command, out: &mut dyn Write) -> io::Result<()> {
+    let command = built(command);
+    let topics: Vec<_> = registry::get_all_topics().collect();
+    render(&command, &topics, out)
+}
+
+/// Write the pages of `hello` and of every subcommand to `dir`
+///
+/// # Returns
+/// Paths of the files written, `hello.1` first
+pub fn write_all(command: Command, dir: &Path) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let command = built(command);
+
+    let mut written = Vec::new();
+    let mut write = |command: &Command, topics: &[&Topic]| -> io::Result<()> {
+        let path = dir.join(format!("{}.{}", page_name(command), SECTION));
+        let mut file = fs::File::create(&path)?;
+        render(command, topics, &mut file)?;
+        written.push(path);
+        Ok(())
+    };
+
+    write(&command, &registry::get_all_topics().collect::<Vec<_>>())?;
+    for subcommand in command.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        let topics: Vec<_> = registry::get_topics_by_category(subcommand.get_name()).collect();
+        write(subcommand, &topics)?;
+    }
+    Ok(written)
+}
+
+/// Build `command` so subcommands get their `hello-<name>` display names
+fn built(command: Command) -> Command {
+    let mut command = command.disable_help_subcommand(true);
+    command.build();
+    command
+}
+
+fn page_name(command: &Command) -> &str {
+    command
+        .get_display_name()
+        .unwrap_or_else(|| command.get_name())
+}
+
+/// TOPICS section: one tagged paragraph per topic
+fn topics_section(topics: &[&Topic]) -> Roff {
+    let mut roff = Roff::new();
+    roff.control("SH", [t!(MAN_TOPICS)]);
+    for topic in topics {
+        roff.control("TP", []);
+        roff.text([bold(format!("hello {} {}", topic.category, topic.name))]);
+        roff.text([roman(topic.localized_description())]);
+        if !topic.dependencies.is_empty() {
+            roff.control("br", []);
+            roff.text([roman(t!(MAN_DEPENDENCIES, topic.dependency_names()))]);
+        }
+    }
+    roff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::with_categories;
+
+    #[test]
+    fn test_main_page_lists_topics_with_dependencies() {
+        let mut page = Vec::new();
+        render_main(with_categories(Command::new("hello")), &mut page).unwrap();
+        let page = String::from_utf8(page).unwrap();
+        assert!(page.contains("\n.TH hello 1"));
+        assert_eq!(page.matches(PRELUDE[0]).count(), 1);
+        assert!(page.contains("hello basic ownership"));
+        assert!(page.contains("MySQL"));
+    }
+
+    #[test]
+    fn test_write_all_writes_category_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let written = write_all(with_categories(Command::new("hello")), dir.path()).unwrap();
+        assert_eq!(written[0], dir.path().join("hello.1"));
+
+        let algo = fs::read_to_string(dir.path().join("hello-algo.1")).unwrap();
+        assert!(algo.contains("hello algo calc\\-pi"));
+        assert!(!algo.contains("hello basic ownership"));
+    }
+}
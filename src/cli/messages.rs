@@ -0,0 +1,238 @@
+//! Message catalog of the CLI, in Chinese and English
+//!
+//! Print a message with [`t!`](crate::cli::i18n::t): `t!(NEXT_TOPIC, key, description)`.
+//! Leave the English side empty (`""`) when a translation is missing; the
+//! Chinese text is shown instead. Error details from libraries and `anyhow`
+//! contexts are not translated.
+
+use crate::cli::i18n::Text;
+
+/// Declare catalog texts as constants, plus [`ALL`] listing them for tests
+macro_rules! messages {
+    ($($name:ident = ($zh:literal, $en:literal);)*) => {
+        $(pub const $name: Text = Text::new($zh, $en);)*
+
+        /// Every text of the catalog with its name
+        #[cfg(test)]
+        pub const ALL: &[(&str, Text)] = &[$((stringify!($name), $name)),*];
+    };
+}
+
+messages! {
+    // Errors and general
+    ERROR = ("错误：{}", "Error: {}");
+    CAUSED_BY = ("原因：", "Caused by:");
+    DID_YOU_MEAN = ("你是不是想找：{}？", "Did you mean: {}?");
+    UNKNOWN_TOPIC_IN = ("错误：未知主题 '{}'（分类 '{}'）。", "Error: Unknown topic '{}' in '{}'.");
+    UNKNOWN_TOPIC = ("错误：未知主题 '{}'。", "Error: Unknown topic '{}'.");
+    UNKNOWN_CATEGORY = ("错误：未知分类 '{}'。", "Error: Unknown category '{}'.");
+    UNKNOWN_TARGET = ("错误：未知的分类或主题 '{}'。", "Error: Unknown category or topic '{}'.");
+    AVAILABLE_TOPICS = ("可用主题：", "Available topics:");
+    AVAILABLE_CATEGORIES = ("可用分类：", "Available categories:");
+    ERR_DEPENDENCY_UNAVAILABLE = ("'{}' 已跳过，依赖不可用：{}", "'{}' skipped, dependency unavailable: {}");
+    ERR_SAMPLE_FAILED = ("示例 '{}' 运行失败", "sample '{}' failed");
+    ERR_TIMED_OUT = ("'{}' 运行 {} 秒后超时（可用 --timeout 修改时限）", "'{}' timed out after {}s (use --timeout to change the limit)");
+    ERR_CANCELLED = ("'{}' 已取消（Ctrl-C）", "'{}' was cancelled (Ctrl-C)");
+    WARN_UNREADABLE_PROGRESS = ("⚠ 警告：忽略无法读取的进度文件：{}", "⚠ Warning: ignoring unreadable progress file: {}");
+    WARN_PROGRESS_NOT_SAVED = ("⚠ 警告：进度未保存：{}", "⚠ Warning: progress not saved: {}");
+
+    // Running a topic
+    SKIPPING_UNAVAILABLE = ("⏭ 跳过 '{}'：所需的服务不可用。", "⏭ Skipping '{}': required services are unavailable.");
+    ENDPOINT_FROM = ("（地址来自 {}，参见 `hello doctor`）", "(endpoint from {}, see `hello doctor`)");
+    HINT_FORCE = ("使用 --force 仍然启动示例。", "Run with --force to start the sample anyway.");
+    RUNNING_SAMPLE = ("▶ 运行示例：", "▶ Running sample:");
+    SERVER_NOTE = ("⚠ 注意：主题 '{}' 是长期运行的服务，按 Ctrl-C 停止。", "⚠ Note: Topic '{}' is a long-running server; press Ctrl-C to stop it.");
+    HINT_SERVE_IN_BACKGROUND = ("也可以运行 `hello serve {} {}` 在后台启动。", "Run `hello serve {} {}` to start it in the background instead.");
+    SECTION_NOT_FOUND = ("⚠ 找不到章节 '{}'（{}）。", "⚠ Section '{}' not found in {}.");
+    AVAILABLE_SECTIONS = ("可用章节：", "Available sections:");
+    IMAGE_PLACEHOLDER = ("[图片: {}]", "[image: {}]");
+
+    // Listings
+    APP_TITLE = ("Hello Rust — 交互式 Rust 学习 CLI", "Hello Rust — Interactive Learning CLI");
+    CATEGORIES = ("分类：", "Categories:");
+    TOPIC_COUNT = ("{} 个主题", "{} topics");
+    HINT_CATEGORY_HELP = ("运行 `hello <分类> --help` 查看分类中的主题。", "Run `hello <category> --help` to list topics in a category.");
+    HINT_RUN_TOPIC = ("运行 `hello <分类> <主题>` 运行指定示例。", "Run `hello <category> <topic>` to run a specific sample.");
+    NO_TOPICS_IN_CATEGORY = ("分类 '{}' 中没有主题。", "No topics found in category '{}'.");
+    CATEGORY_TOPICS = ("{} 主题 — 运行：hello {} <主题>", "{} topics — Run: hello {} <topic>");
+    HEADER_TOPIC = ("主题", "TOPIC");
+    HEADER_DESCRIPTION = ("说明", "DESCRIPTION");
+    HEADER_DEPENDENCIES = ("依赖", "DEPENDENCIES");
+    NO_DEPENDENCIES = ("无", "none");
+    CATALOG_UP_TO_DATE = ("✔ 主题目录已是最新。", "✔ Topic catalog is up to date.");
+    UPDATED_FILE = ("✎ 已更新 {}", "✎ Updated {}");
+    BOOK_AGREES = ("✔ 书籍与主题注册表一致。", "✔ The book agrees with the topic registry.");
+    BOOK_DISAGREES = ("✘ 书籍与主题注册表不一致：", "✘ The book disagrees with the topic registry:");
+    HINT_SUMMARY_WRITE = ("运行 `hello list --summary write` 重新生成目录。", "Run `hello list --summary write` to regenerate the catalog.");
+
+    // Progress
+    PROGRESS_TITLE = ("学习进度（{}）", "Learning progress ({})");
+    PROGRESS_TOTAL = ("合计", "total");
+    RECENTLY_RUN = ("最近运行：", "Recently run:");
+    STATUS_COMPLETED = ("✔ 已完成", "✔ completed");
+    STATUS_UNFINISHED = ("✘ 未完成", "✘ unfinished");
+    STATUS_DOCS_READ = ("，已读教程", ", docs read");
+    STATUS_EXERCISE_PASSED = ("，练习已通过", ", exercise passed");
+    QUIZ_PROGRESS = ("测验：答对 {}/{} 题（已答 {} 题）。运行 `hello quiz` 练习。", "Quiz: {}/{} questions answered correctly ({} answered). Run `hello quiz` to practice.");
+    HINT_NEXT = ("运行 `hello next` 继续下一个未完成的主题。", "Run `hello next` to continue with the next unfinished topic.");
+    PROGRESS_RESET = ("进度已清除。", "Progress reset.");
+    ALL_COMPLETED = ("🎉 所有主题都已完成！运行 `hello progress` 查看汇总。", "🎉 All topics completed! Run `hello progress` for a summary.");
+    NEXT_TOPIC = ("下一个主题：{} — {}", "Next topic: {} — {}");
+    BUILDS_ON = ("前置主题：{}", "Builds on: {}");
+    RUN_COMMAND = ("运行：hello {} {}", "Run: hello {} {}");
+
+    // Exercises
+    NO_EXERCISE = ("主题 '{}' 还没有练习。", "Topic '{}' has no exercise yet.");
+    HINT_LIST_EXERCISES = ("运行 `hello exercise` 查看有练习的主题。", "Run `hello exercise` to list topics with exercises.");
+    EXERCISE_CREATED = ("已创建练习项目 {}", "Created exercise project {}");
+    EXERCISE_EDIT_HINT = ("编辑 src/lib.rs，替换所有 TODO，然后运行：", "Edit src/lib.rs and replace every TODO, then run:");
+    EXERCISE_CHECKING = ("▶ 正在检查 {}（{}）", "▶ Checking {} ({})");
+    EXERCISE_PASSED = ("✔ 全部检查通过！", "✔ All checks passed!");
+    EXERCISE_BUILD_FAILED = ("✘ 编译失败：", "✘ Build failed:");
+    EXERCISE_TESTS_FAILED = ("✘ 隐藏测试未通过：", "✘ Hidden tests failed:");
+    EXERCISE_WATCHING = ("正在监视 {} 的改动（Ctrl-C 停止）...", "Watching {} for changes (Ctrl-C to stop) ...");
+    EXERCISES_HEADER = ("练习 — 运行：hello exercise <主题>", "Exercises — Run: hello exercise <topic>");
+    EXERCISE_PROJECTS_DIR = ("练习项目创建在 {} 下", "Projects are created under {}");
+
+    // Quiz
+    NO_QUIZ_QUESTIONS = ("'{}' 还没有测验题。", "No quiz questions for '{}' yet.");
+    QUIZ_ALL = ("全部", "all");
+    QUESTION_HEADER = ("━━━ 第 {}/{} 题 · {} {}", "━━━ Question {}/{} · {} {}");
+    ANSWER_CORRECT = ("✔ 回答正确！", "✔ Correct!");
+    ANSWER_KEEP_PRACTICING = ("✘ 继续加油。", "✘ Keep practicing.");
+    ANSWER_INCORRECT = ("✘ 回答错误。答案：{}", "✘ Incorrect. Answer: {}");
+    QUIZ_BANK_DISAGREES = ("注意：题库答案是 \"{}\"，但示例输出与之不符。", "Note: the quiz bank says \"{}\", but the sample output disagrees.");
+    QUIZ_SCORE = ("得分：{}/{}（{}%）", "Score: {}/{} ({}%)");
+    CHECKING_ANSWER = ("（正在运行 `hello {} {}` 核对答案 ...）", "(running `hello {} {}` to check the answer ...)");
+    PROMPT_CHOICE = ("你的答案 [A-D，q 退出]：", "Your answer [A-D, q to quit]: ");
+    PROMPT_CHOICE_RETRY = ("请输入 A 到 D 之间的字母。", "Please enter a letter from A to D.");
+    PROMPT_TRUE_FALSE = ("你的答案 [y/n，q 退出]：", "Your answer [y/n, q to quit]: ");
+    PROMPT_YES_NO_RETRY = ("请回答 y 或 n。", "Please answer y or n.");
+    PROMPT_REVEAL = ("想好答案后按回车查看（q 退出）：", "Think of your answer, then press Enter to reveal it (q to quit): ");
+    PROMPT_SELF_GRADE = ("你答对了吗？[y/n]：", "Did you get it right? [y/n]: ");
+    ANSWER_REVEALED = ("**答案**: {}", "**Answer**: {}");
+
+    // Services
+    CHECKING_SERVICES = ("正在检查外部服务（每项超时 {}）", "Checking external services (timeout {} each)");
+    HEADER_SERVICE = ("服务", "SERVICE");
+    HEADER_ENDPOINT = ("地址", "ENDPOINT");
+    HEADER_TOPICS = ("主题数", "TOPICS");
+    HEADER_DETAIL = ("详情", "DETAIL");
+    ALL_SERVICES_AVAILABLE = ("所有服务均可用。", "All services are available.");
+    UNAVAILABLE_SKIPPED = ("需要不可用服务的主题会被跳过，除非使用 --force 运行。", "Topics needing an unavailable service are skipped unless run with --force.");
+    HINT_ENDPOINT_VARS = ("设置以下地址变量（或写入 .env）指向你的服务：", "Set the endpoint variables (or put them in .env) to point at your services:");
+    SERVER_ALREADY_RUNNING = ("服务 '{}' 已在运行（pid {}）。", "Server '{}' is already running (pid {}).");
+    SERVER_PID = ("pid：{}", "pid: {}");
+    SERVER_LOG = ("日志：{}", "log: {}");
+    SERVER_STARTING = ("正在启动 {} ...", "Starting {} ...");
+    SERVER_READY = ("✔ {} 已就绪，监听 127.0.0.1:{}", "✔ {} ready on 127.0.0.1:{}");
+    SERVER_STARTED = ("✔ {} 已启动", "✔ {} started");
+    HINT_STOP = ("运行 `hello stop {}` 关闭它。", "Run `hello stop {}` to shut it down.");
+    NO_SERVERS_HINT = ("没有正在运行的服务。使用 `hello serve <分类> <主题>` 启动一个。", "No servers running. Start one with `hello serve <category> <topic>`.");
+    NO_SERVERS = ("没有正在运行的服务。", "No servers running.");
+    HEADER_PID = ("进程号", "PID");
+    HEADER_PORT = ("端口", "PORT");
+    HEADER_STARTED = ("启动时间", "STARTED");
+    HEADER_LOG = ("日志", "LOG");
+    SERVER_EXITED = ("✘ {}（pid {}）已退出；参见 {}", "✘ {} (pid {}) has exited; see {}");
+    NO_SERVER_MATCHES = ("错误：没有匹配 '{}' 的运行中服务。", "Error: No running server matches '{}'.");
+    SERVER_STOPPED = ("✔ 已停止 {}（pid {}）", "✔ Stopped {} (pid {})");
+    SERVER_KILLED = ("✔ 已强制结束 {}（pid {}，等待 {} 后）", "✔ Killed {} (pid {}) after {}");
+
+    // Verification and batch runs
+    VERIFYING = ("正在校验 {} 个主题（快照目录 {}）", "Verifying {} topics against {}");
+    TIMED_OUT_AFTER = ("运行 {} 后超时", "timed out after {}");
+    SNAPSHOT_UPDATED = ("快照已更新", "snapshot updated");
+    SNAPSHOT_MISSING = ("缺少快照 {}", "no snapshot at {}");
+    SNAPSHOT_MISMATCH = ("输出与快照不同", "output differs from snapshot");
+    VERIFY_SUMMARY = ("汇总：{} 通过，{} 已更新，{} 失败，{} 缺失", "Summary: {} passed, {} blessed, {} failed, {} missing");
+    HINT_BLESS = ("运行 `hello verify --bless` 接受当前输出。", "Run `hello verify --bless` to accept the current output.");
+    SKIP_SERVER = ("长期运行的服务（请用 `hello serve`）", "long-running server (use `hello serve`)");
+    SKIP_DEPENDENCY_UNAVAILABLE = ("依赖不可用：{}", "dependency unavailable: {}");
+    RUNNING_TOPICS = ("正在运行 {} 个主题（每个超时 {} 秒）", "Running {} topics (timeout {}s each)");
+    RUN_ALL_SUMMARY = ("汇总：{} 通过，{} 失败，{} 超时，{} 跳过", "Summary: {} passed, {} failed, {} timed out, {} skipped");
+    REPORTS_WRITTEN = ("报告：{}、{}", "Reports: {}, {}");
+
+    // Search
+    SEARCH_RESULTS = ("\"{}\" 的搜索结果", "Search results for \"{}\"");
+    SEARCH_TOPICS = ("主题：", "Topics:");
+    SEARCH_TUTORIALS = ("教程：", "Tutorials:");
+    SEARCH_MORE = ("... 还有 {} 条（使用 --limit 显示更多）", "... {} more (use --limit to show more)");
+    SEARCH_NO_RESULTS = ("没有结果。运行 `hello list` 浏览所有分类。", "No results. Run `hello list` to browse all categories.");
+
+    // Sample sources
+    NO_SAMPLE_SOURCE = ("主题 '{}' 没有记录示例源码。", "Topic '{}' has no recorded sample source.");
+    SOURCE_NOT_FOUND = ("找不到函数 {}（{}）。", "Function {} not found in {}.");
+    HINT_EXPORT_SOURCE = ("导出为可修改的独立项目：hello source {} {} --export <目录>", "Export it as a standalone project to modify: hello source {} {} --export <dir>");
+    SOURCE_EXPORTED = ("✔ 已导出到 {}，运行：", "✔ Exported to {}; run it with:");
+    SOURCE_DEPENDENCIES = ("依赖：{}", "Dependencies: {}");
+    SOURCE_LOCAL_DEPENDENCIES = ("⚠ 未加入本仓库内的依赖：{}", "⚠ Left out dependencies on crates of this repository: {}");
+    SOURCE_UNRESOLVED = ("⚠ 未能复制：{}（需要自行替换）", "⚠ Not copied: {} (replace them yourself)");
+    SOURCE_NEEDS_ARGUMENTS = ("⚠ {} 需要参数，请在 src/main.rs 中补全调用", "⚠ {} takes arguments; complete the call in src/main.rs");
+
+    // Example projects
+    PROJECTS_HEADER = ("示例项目 — 运行：hello projects <项目> -- <参数>", "Example projects — Run: hello projects <project> -- <args>");
+    PROJECT_TUTORIAL = ("教程：{}", "Tutorial: {}");
+    PROJECT_TUTORIAL_ONLY = ("（仅教程）", " (tutorial only)");
+    PROJECTS_DIR = ("项目目录：{}", "Projects directory: {}");
+    UNKNOWN_PROJECT = ("错误：未知项目 '{}'。", "Error: Unknown project '{}'.");
+    HINT_LIST_PROJECTS = ("运行 `hello projects --list` 查看全部项目。", "Run `hello projects --list` to list all projects.");
+    PROJECT_NO_CODE = ("项目 '{}' 没有可运行的代码，请阅读教程 {}", "Project '{}' has no runnable code; read the tutorial {}");
+    PROJECT_FAILED = ("✘ {} 失败（{}）", "✘ {} failed ({})");
+
+    // Documentation server
+    DOCS_SERVING = ("📖 文档服务已启动：http://{}（按 Ctrl-C 停止）", "📖 Serving the docs on http://{} (press Ctrl-C to stop)");
+    DOCS_STOPPED = ("文档服务已停止。", "Docs server stopped.");
+    DOCS_BIND_FAILED = ("错误：无法监听端口 {}：{}", "Error: Cannot listen on port {}: {}");
+    DOCS_RUN_SAMPLE = ("▶ 运行示例（{}）", "▶ Run this sample ({})");
+    DOCS_SEARCH = ("搜索…", "Search…");
+    DOCS_NOT_FOUND = ("找不到 {}", "Not found: {}");
+    DOCS_FORBIDDEN = ("拒绝来自 {} 的请求：只有本机文档页面可以运行示例", "Refused request from {}: only the local docs pages can run samples");
+    DOCS_SERVER_TOPIC = ("{}/{} 是服务类主题，请用 `hello serve {} {}` 启动", "{}/{} is a server topic; start it with `hello serve {} {}`");
+
+    // Completions and man pages
+    MAN_TOPICS = ("主题", "TOPICS");
+    MAN_DEPENDENCIES = ("依赖：{}", "Dependencies: {}");
+    MAN_WRITTEN = ("✎ 已写入 {}", "✎ Wrote {}");
+
+    // Subcommand help
+    ABOUT_APP = ("交互式 Rust 学习 CLI（类似 Rustlings）", "Interactive Rustlings-like CLI for learning Rust");
+    ABOUT_LIST = ("列出所有分类中的主题", "List all available topics across all categories");
+    ABOUT_SEARCH = ("搜索主题名称、说明和教程（例如 \"借用\"、\"Arc\"）", "Search topic names, descriptions and tutorials (e.g. \"借用\", \"Arc\")");
+    ABOUT_PROGRESS = ("显示各分类已运行主题的完成情况", "Show per-category completion of the topics you have run");
+    ABOUT_VERIFY = ("对照黄金输出快照检查函数类主题", "Check Function topics against their golden-output snapshots");
+    ABOUT_RUN_ALL = ("隔离运行所有函数类主题，并写出 JUnit XML 和 JSON 报告", "Run every Function topic in isolation and write JUnit XML and JSON reports");
+    ABOUT_NEXT = ("按学习顺序显示下一个未完成的主题", "Show the next unfinished topic in learning order");
+    ABOUT_EXERCISE = ("完成主题练习：生成 cargo 项目、编译并运行隐藏测试", "Solve a topic's exercise: scaffold a cargo project, build it and run hidden tests");
+    ABOUT_QUIZ = ("回答教程中的测验题（例如 `hello quiz basic`、`hello quiz closure`）", "Answer quiz questions from the tutorials (e.g. `hello quiz basic`, `hello quiz closure`)");
+    ABOUT_DOCTOR = ("检查外部服务（Consul、MySQL、MQTT 等）是否可达", "Check which external services (Consul, MySQL, MQTT, ...) are reachable");
+    ABOUT_SERVE = ("在后台启动服务类主题（例如 `hello serve advance tokio-server`）", "Start a server topic in the background (e.g. `hello serve advance tokio-server`)");
+    ABOUT_PS = ("列出在后台运行的服务类主题", "List server topics running in the background");
+    ABOUT_STOP = ("停止后台运行的服务类主题", "Stop a background server topic");
+    ABOUT_SOURCE = ("输出主题运行的函数，或把它导出为独立的 cargo 项目", "Print the function a topic runs, or export it as a standalone cargo project");
+    ABOUT_DOCS = ("在浏览器中阅读教程（`hello docs serve`）", "Browse the tutorials in a web browser (`hello docs serve`)");
+    ABOUT_PROJECTS = ("编译并运行 examples/ 下的示例项目（例如 `hello projects todo -- add \"x\"`）", "Build and run the example projects under examples/ (e.g. `hello projects todo -- add \"x\"`)");
+    ABOUT_COMPLETIONS = ("输出 shell 补全脚本，可补全分类和主题名称", "Print a shell completion script that completes categories and topic names");
+    ABOUT_MAN = ("输出列出全部主题的 roff man 手册页，或把所有手册页写入目录", "Print the roff man page listing every topic, or write all pages to a directory");
+    HEADING_TOPIC_OPTIONS = ("主题选项", "Topic options");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translations_keep_placeholders() {
+        for (name, text) in ALL {
+            if text.en.is_empty() {
+                continue;
+            }
+            assert_eq!(
+                text.zh.matches("{}").count(),
+                text.en.matches("{}").count(),
+                "{} has a different number of placeholders in zh and en",
+                name
+            );
+        }
+    }
+}
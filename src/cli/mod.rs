@@ -1,118 +1,455 @@
 //! CLI module for interactive hello-rust subcommands
 //!
-//! Provides clap-based subcommand structure for running individual samples
+//! Provides clap-based subcommand structure for running individual samples.
+//! Fixed commands are declared in [`Commands`]; one subcommand per registered
+//! [`registry::Category`] is added at runtime by [`with_categories`].
 
-use clap::Subcommand;
+use std::path::PathBuf;
 
+use clap::builder::PossibleValuesParser;
+use clap::parser::ValueSource;
+use clap::{Arg, ArgMatches, Args, Command, Subcommand};
+
+use crate::cli::i18n::t;
+
+pub mod catalog;
+pub mod checkout;
+pub mod completions;
 pub mod dispatcher;
 pub mod docs_embed;
+pub mod docs_server;
+pub mod doctor;
+pub mod error;
+pub mod exercise;
+pub mod i18n;
+pub mod manpage;
+pub mod messages;
+pub mod projects;
+pub mod quiz;
 pub mod registry;
+pub mod render;
+pub mod run_all;
+pub mod runtime;
+pub mod search;
+pub mod serve;
+pub mod source;
+pub mod state;
+pub mod verify;
 
 /// Top-level CLI commands
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// List all available topics across all categories
-    List,
+    List {
+        /// Output format; json, markdown and csv export the full topic catalog
+        #[arg(long, value_enum, default_value_t = catalog::Format::Table)]
+        format: catalog::Format,
 
-    /// Rust fundamentals (ownership, generics, threads, ...)
-    Basic {
-        /// Specific topic to run (omit to list all)
-        topic: Option<String>,
+        /// Regenerate (write) or validate (check) the book's topic catalog and SUMMARY.md block
+        #[arg(long, value_enum, conflicts_with = "format")]
+        summary: Option<catalog::SummaryMode>,
 
-        /// Skip tutorial documentation and run sample directly
-        #[arg(long)]
-        no_docs: bool,
+        /// Directory of the book sources, used by --summary
+        #[arg(long, default_value = catalog::DEFAULT_DOCS_DIR)]
+        docs_dir: PathBuf,
+    },
 
-        /// Skip external dependency checks
+    /// Search topic names, descriptions and tutorials (e.g. "借用", "Arc")
+    Search {
+        /// Words to search for; every word must match
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Maximum number of tutorial sections to show
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+
+    /// Show per-category completion of the topics you have run
+    Progress {
+        /// Clear all recorded progress
         #[arg(long)]
-        force: bool,
+        reset: bool,
     },
 
-    /// Advanced patterns (async, databases, web, ...)
-    Advance {
-        /// Specific topic to run (omit to list all)
-        topic: Option<String>,
+    /// Check Function topics against their golden-output snapshots
+    Verify {
+        /// Only verify topics of this category
+        category: Option<String>,
 
-        /// Skip tutorial documentation and run sample directly
+        /// Write the current output as the new snapshots
         #[arg(long)]
-        no_docs: bool,
+        bless: bool,
 
-        /// Skip external dependency checks
+        /// Directory holding the snapshot files
+        /// (default: `tests/snapshots` in the current directory or next to the executable)
         #[arg(long)]
-        force: bool,
+        snapshot_dir: Option<PathBuf>,
+
+        /// Seconds a topic may run before it is killed
+        #[arg(long, default_value_t = 60)]
+        timeout: u64,
     },
 
-    /// Production-grade samples (gRPC, DI, templates, ...)
-    Awesome {
-        /// Specific topic to run (omit to list all)
-        topic: Option<String>,
+    /// Run every Function topic in isolation and write JUnit XML and JSON reports
+    RunAll {
+        /// Only run topics of this category
+        category: Option<String>,
+
+        /// Seconds a topic may run before it is killed
+        #[arg(long, default_value_t = 60)]
+        timeout: u64,
 
-        /// Skip tutorial documentation and run sample directly
+        /// Directory the junit.xml and report.json files are written to
+        #[arg(long, default_value = run_all::DEFAULT_REPORT_DIR)]
+        report_dir: PathBuf,
+    },
+
+    /// Show the next unfinished topic in learning order
+    Next {
+        /// Run the topic right away
         #[arg(long)]
-        no_docs: bool,
+        run: bool,
 
-        /// Skip external dependency checks
+        /// Skip tutorial documentation when running
         #[arg(long)]
-        force: bool,
+        no_docs: bool,
     },
 
-    /// Algorithm implementations (linked-list, pi calculation, ...)
-    Algo {
-        /// Specific topic to run (omit to list all)
+    /// Solve a topic's exercise: scaffold a cargo project, build it and run hidden tests
+    Exercise {
+        /// Topic name or "category/name" (omit to list exercises)
         topic: Option<String>,
 
-        /// Skip tutorial documentation and run sample directly
-        #[arg(long)]
-        no_docs: bool,
+        /// Re-check whenever a file of the project changes
+        #[arg(long, requires = "topic")]
+        watch: bool,
+
+        /// Overwrite src/lib.rs with the starter code
+        #[arg(long, requires = "topic")]
+        reset: bool,
+
+        /// Directory holding the exercise projects
+        #[arg(long, default_value = exercise::DEFAULT_EXERCISE_DIR)]
+        dir: PathBuf,
+    },
+
+    /// Answer quiz questions from the tutorials (e.g. `hello quiz basic`, `hello quiz closure`)
+    Quiz {
+        /// Category, topic name or "category/name" to quiz on (all if omitted)
+        target: Option<String>,
+
+        /// Maximum number of questions to ask
+        #[arg(long, default_value_t = 10)]
+        count: usize,
+    },
+
+    /// Check which external services (Consul, MySQL, MQTT, ...) are reachable
+    Doctor,
+
+    /// Start a server topic in the background (e.g. `hello serve advance tokio-server`)
+    Serve {
+        /// Category of the server topic
+        category: String,
+
+        /// Name of the server topic
+        topic: String,
+
+        /// Port to listen on, for topics with a --port option (default: the topic's own)
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+        port: Option<u16>,
+
+        /// Seconds to wait for the server port to accept connections
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+    },
+
+    /// List server topics running in the background
+    Ps,
+
+    /// Stop a background server topic
+    Stop {
+        /// Server to stop, as "name" or "category/name"
+        #[arg(required_unless_present = "all")]
+        topic: Option<String>,
+
+        /// Stop every running server
+        #[arg(long, conflicts_with = "topic")]
+        all: bool,
+
+        /// Seconds to wait for a graceful shutdown before killing
+        #[arg(long, default_value_t = 5)]
+        timeout: u64,
+    },
+
+    /// Print the function a topic runs, or export it as a standalone cargo project
+    Source {
+        /// Category of the topic
+        category: String,
+
+        /// Name of the topic
+        topic: String,
+
+        /// Write the sample as a cargo project to DIR/<category>-<topic>
+        #[arg(long, value_name = "DIR")]
+        export: Option<PathBuf>,
+    },
+
+    /// Build and run the example projects under examples/ (e.g. `hello projects todo -- add "x"`)
+    Projects {
+        #[command(subcommand)]
+        action: Option<ProjectAction>,
 
-        /// Skip external dependency checks
+        /// Project to build and run (omit to list projects)
+        #[arg(conflicts_with = "list")]
+        project: Option<String>,
+
+        /// Arguments passed to the project, after `--`
+        #[arg(last = true)]
+        args: Vec<String>,
+
+        /// List each project with its tutorial
         #[arg(long)]
-        force: bool,
+        list: bool,
+
+        /// Directory holding the example projects
+        /// (default: `examples/` in the current directory or next to the executable)
+        #[arg(long, global = true)]
+        dir: Option<PathBuf>,
     },
+
+    /// Browse the tutorials in a web browser
+    Docs {
+        #[command(subcommand)]
+        action: DocsAction,
+    },
+
+    /// Print a shell completion script that completes categories and topic names
+    Completions {
+        /// Shell to generate the script for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
+    /// Print the roff man page listing every topic, or write all pages to a directory
+    Man {
+        /// Write hello.1 and one page per subcommand to this directory
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<PathBuf>,
+    },
+}
+
+/// Subcommands of `hello docs`
+#[derive(Subcommand, Debug)]
+pub enum DocsAction {
+    /// Serve the book as HTML with search and runnable samples on http://127.0.0.1:PORT
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = docs_server::DEFAULT_PORT)]
+        port: u16,
+    },
+}
+
+/// Subcommands of `hello projects`
+#[derive(Subcommand, Debug)]
+pub enum ProjectAction {
+    /// Run the test suite of a project (e.g. `hello projects test todo`)
+    Test {
+        /// Project to test
+        project: String,
+
+        /// Arguments passed to the test harness, after `--`
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+}
+
+/// Arguments shared by every category subcommand (`hello <category> ...`)
+#[derive(Args, Debug, Clone, Default)]
+pub struct CategoryArgs {
+    /// Specific topic to run (omit to list all)
+    pub topic: Option<String>,
+
+    /// Skip tutorial documentation and run sample directly
+    #[arg(long)]
+    pub no_docs: bool,
+
+    /// Run even if required external services are unavailable
+    #[arg(long)]
+    pub force: bool,
+
+    /// Only show the tutorial section under this heading
+    #[arg(long, value_name = "HEADING")]
+    pub section: Option<String>,
+
+    /// Print the tutorial directly instead of through a pager
+    #[arg(long)]
+    pub no_pager: bool,
+
+    /// Cancel an async sample after SECS seconds (0 = no limit; default: the topic's own)
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+
+    /// Topic options given on the command line, as `(name, value)` pairs
+    ///
+    /// Declared per topic in the registry, so they are added to the category
+    /// command by [`with_categories`] and read back by [`topic_arg_values`].
+    #[arg(skip)]
+    pub topic_args: Vec<(String, String)>,
 }
 
-impl Commands {
-    /// Get the category name as a string
-    pub fn category_name(&self) -> Option<&'static str> {
-        match self {
-            Commands::List => None,
-            Commands::Basic { .. } => Some("basic"),
-            Commands::Advance { .. } => Some("advance"),
-            Commands::Awesome { .. } => Some("awesome"),
-            Commands::Algo { .. } => Some("algo"),
+/// Add one subcommand per registered category to `command`
+///
+/// Categories are taken from the inventory registry in `order`, so a new
+/// category only needs an `inventory::submit!` of a [`registry::Category`].
+pub fn with_categories(command: Command) -> Command {
+    registry::get_all_categories()
+        .into_iter()
+        .fold(command, |command, category| {
+            let subcommand = CategoryArgs::augment_args(Command::new(category.name))
+                .about(category.localized_description());
+            command.subcommand(with_topic_args(subcommand, category.name))
+        })
+}
+
+/// Add the options declared by the topics of `category` to its subcommand
+///
+/// Topics sharing an option name share the flag (and must declare the same
+/// kind); its help lists each topic with its default.
+fn with_topic_args(command: Command, category: &str) -> Command {
+    let mut options: Vec<(&registry::TopicArg, Vec<String>)> = Vec::new();
+    for topic in registry::get_topics_by_category(category) {
+        for arg in topic.args {
+            let usage = format!("{}: {}", topic.name, arg.default);
+            match options.iter_mut().find(|(known, _)| known.name == arg.name) {
+                Some((_, usages)) => usages.push(usage),
+                None => options.push((arg, vec![usage])),
+            }
         }
     }
 
-    /// Get the topic if specified
-    pub fn topic(&self) -> Option<&String> {
-        match self {
-            Commands::List => None,
-            Commands::Basic { topic, .. } => topic.as_ref(),
-            Commands::Advance { topic, .. } => topic.as_ref(),
-            Commands::Awesome { topic, .. } => topic.as_ref(),
-            Commands::Algo { topic, .. } => topic.as_ref(),
+    options.into_iter().fold(command, |command, (arg, usages)| {
+        let mut option = Arg::new(arg.name)
+            .long(arg.name)
+            .help(format!("{} [default {}]", arg.help, usages.join(", ")))
+            .help_heading(t!(HEADING_TOPIC_OPTIONS));
+        option = match arg.kind {
+            registry::ArgKind::Choice(choices) => {
+                option.value_parser(PossibleValuesParser::new(choices.iter().copied()))
+            }
+            kind => option.value_name(kind.value_name()),
+        };
+        command.arg(option)
+    })
+}
+
+/// Replace the help texts of `command` and its fixed subcommands by those of
+/// the output language
+///
+/// The doc comments of [`Commands`] stay the English source of the help; the
+/// catalog holds both languages.
+pub fn localize(command: Command) -> Command {
+    let abouts = [
+        ("list", messages::ABOUT_LIST),
+        ("search", messages::ABOUT_SEARCH),
+        ("progress", messages::ABOUT_PROGRESS),
+        ("verify", messages::ABOUT_VERIFY),
+        ("run-all", messages::ABOUT_RUN_ALL),
+        ("next", messages::ABOUT_NEXT),
+        ("exercise", messages::ABOUT_EXERCISE),
+        ("quiz", messages::ABOUT_QUIZ),
+        ("doctor", messages::ABOUT_DOCTOR),
+        ("serve", messages::ABOUT_SERVE),
+        ("ps", messages::ABOUT_PS),
+        ("stop", messages::ABOUT_STOP),
+        ("source", messages::ABOUT_SOURCE),
+        ("projects", messages::ABOUT_PROJECTS),
+        ("docs", messages::ABOUT_DOCS),
+        ("completions", messages::ABOUT_COMPLETIONS),
+        ("man", messages::ABOUT_MAN),
+    ];
+    abouts
+        .into_iter()
+        .fold(command.about(t!(ABOUT_APP)), |command, (name, about)| {
+            command.mut_subcommand(name, |sub| sub.about(about.get()))
+        })
+}
+
+/// Read the topic options given on the command line of a category subcommand
+pub fn topic_arg_values(category: &str, matches: &ArgMatches) -> Vec<(String, String)> {
+    let mut values = Vec::new();
+    for topic in registry::get_topics_by_category(category) {
+        for arg in topic.args {
+            if values.iter().any(|(name, _)| name == arg.name)
+                || matches.value_source(arg.name) != Some(ValueSource::CommandLine)
+            {
+                continue;
+            }
+            if let Some(value) = matches.get_one::<String>(arg.name) {
+                values.push((arg.name.to_string(), value.clone()));
+            }
         }
     }
+    values
+}
 
-    /// Check if no_docs flag is set
-    pub fn no_docs(&self) -> bool {
-        match self {
-            Commands::List => false,
-            Commands::Basic { no_docs, .. } => *no_docs,
-            Commands::Advance { no_docs, .. } => *no_docs,
-            Commands::Awesome { no_docs, .. } => *no_docs,
-            Commands::Algo { no_docs, .. } => *no_docs,
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::FromArgMatches;
+
+    #[test]
+    fn test_topic_args_become_category_options() {
+        let command = with_categories(Command::new("hello"));
+        command.clone().debug_assert();
+
+        let matches = command
+            .try_get_matches_from(["hello", "algo", "calc-pi", "--steps", "1e3"])
+            .unwrap();
+        let (_, sub_matches) = matches.subcommand().unwrap();
+        assert_eq!(
+            topic_arg_values("algo", sub_matches),
+            vec![("steps".to_string(), "1e3".to_string())]
+        );
     }
 
-    /// Check if force flag is set
-    pub fn force(&self) -> bool {
-        match self {
-            Commands::List => false,
-            Commands::Basic { force, .. } => *force,
-            Commands::Advance { force, .. } => *force,
-            Commands::Awesome { force, .. } => *force,
-            Commands::Algo { force, .. } => *force,
-        }
+    #[test]
+    fn test_projects_forward_args_and_test_subcommand() {
+        let command = Commands::augment_subcommands(Command::new("hello"));
+        let parse = |args: &[&str]| {
+            let matches = command.clone().try_get_matches_from(args).unwrap();
+            Commands::from_arg_matches(&matches).unwrap()
+        };
+
+        let Commands::Projects { project, args, .. } =
+            parse(&["hello", "projects", "todo", "--", "add", "x"])
+        else {
+            panic!("expected projects");
+        };
+        assert_eq!(project.as_deref(), Some("todo"));
+        assert_eq!(args, ["add", "x"]);
+
+        let Commands::Projects {
+            action: Some(ProjectAction::Test { project, .. }),
+            dir,
+            ..
+        } = parse(&["hello", "projects", "--dir", "/tmp", "test", "todo"])
+        else {
+            panic!("expected projects test");
+        };
+        assert_eq!(project, "todo");
+        assert_eq!(dir, Some(PathBuf::from("/tmp")));
+    }
+
+    #[test]
+    fn test_localize_replaces_abouts() {
+        let command = localize(Commands::augment_subcommands(Command::new("hello")));
+        command.clone().debug_assert();
+
+        let list = command.find_subcommand("list").unwrap();
+        assert_eq!(
+            list.get_about().map(ToString::to_string).as_deref(),
+            Some(messages::ABOUT_LIST.get())
+        );
     }
 }
@@ -0,0 +1,181 @@
+//! Example projects outside the workspace: `hello projects`
+//!
+//! The projects described in `docs/src/projects` are listed in [`PROJECTS`].
+//! Those with code live under `examples/` as standalone cargo projects that
+//! the workspace excludes; they are built with `cargo --offline` into their
+//! own `target` directory, so they never share (or invalidate) the build of
+//! `hello` itself.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+
+use anyhow::{bail, Context, Result};
+
+use crate::cli::checkout;
+use crate::cli::i18n::Text;
+
+/// Name of the directory holding the example projects
+pub const PROJECTS_DIR: &str = "examples";
+
+/// A project of the "项目实战" chapter
+pub struct Project {
+    /// Name used on the command line (e.g. "todo")
+    pub name: &'static str,
+    pub description: Text,
+    /// Directory of the cargo project inside the projects directory,
+    /// `None` for projects that only have a tutorial
+    pub dir: Option<&'static str>,
+    /// Tutorial path relative to the book sources
+    pub tutorial: &'static str,
+}
+
+impl Project {
+    /// Directory of the cargo project inside `root`, if the project has code
+    pub fn project_dir(&self, root: &Path) -> Option<PathBuf> {
+        self.dir.map(|dir| root.join(dir))
+    }
+}
+
+/// Find the example projects when `--dir` is not given
+///
+/// Looks for `examples/` in the current directory, then next to the `hello`
+/// executable and in its parent directories (a checkout's `target/debug/hello`
+/// finds the checkout's `examples/`). A directory only counts if it holds one
+/// of the projects. Falls back to `examples/` in the current directory.
+pub fn default_dir() -> PathBuf {
+    checkout::find(PROJECTS_DIR, has_projects).unwrap_or_else(|| PathBuf::from(PROJECTS_DIR))
+}
+
+/// Check whether `dir` holds at least one of the projects with code
+fn has_projects(dir: &Path) -> bool {
+    PROJECTS
+        .iter()
+        .filter_map(|project| project.project_dir(dir))
+        .any(|project_dir| project_dir.join("Cargo.toml").is_file())
+}
+
+/// What to do with a project
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// `cargo run`
+    Run,
+    /// `cargo test`
+    Test,
+}
+
+/// Every project, in the order of the book
+pub const PROJECTS: &[Project] = &[
+    Project {
+        name: "todo",
+        description: Text::new(
+            "命令行待办事项：clap、serde 持久化与 CLI 集成测试",
+            "Command-line todo list: clap, serde persistence and CLI integration tests",
+        ),
+        dir: Some("todo"),
+        tutorial: "projects/todo-cli/README.md",
+    },
+    Project {
+        name: "http-server",
+        description: Text::new("简易 HTTP 服务器", "Simple HTTP server"),
+        dir: None,
+        tutorial: "projects/http-server/README.md",
+    },
+    Project {
+        name: "web-scraper",
+        description: Text::new("多线程爬虫", "Multi-threaded web scraper"),
+        dir: None,
+        tutorial: "projects/web-scraper/README.md",
+    },
+    Project {
+        name: "binaries",
+        description: Text::new(
+            "IPC 与分布式示例（src/bin，用 cargo run --bin 运行）",
+            "IPC and distributed samples (src/bin, run with cargo run --bin)",
+        ),
+        dir: None,
+        tutorial: "projects/binaries/README.md",
+    },
+];
+
+/// Find a project by name
+pub fn find(name: &str) -> Option<&'static Project> {
+    PROJECTS.iter().find(|project| project.name == name)
+}
+
+/// Build the project in `dir` and run it (or its tests), forwarding `args`
+///
+/// Output goes straight to the terminal.
+///
+/// # Arguments
+/// * `args` - Arguments passed to the program (or to the test harness)
+pub fn run(dir: &Path, action: Action, args: &[String]) -> Result<ExitStatus> {
+    if !dir.join("Cargo.toml").is_file() {
+        bail!("{} is not a cargo project", dir.display());
+    }
+    let dir =
+        std::path::absolute(dir).with_context(|| format!("Failed to resolve {}", dir.display()))?;
+    cargo(&dir, action, args)
+        .status()
+        .context("Failed to run cargo; is it installed and on PATH?")
+}
+
+/// Prepare `cargo run` or `cargo test` in `dir`, with its own target directory
+///
+/// `dir` must be absolute: cargo resolves a relative `CARGO_TARGET_DIR`
+/// against the project it runs in, not against our working directory.
+fn cargo(dir: &Path, action: Action, args: &[String]) -> Command {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let subcommand = match action {
+        Action::Run => "run",
+        Action::Test => "test",
+    };
+    let mut command = Command::new(cargo);
+    command
+        .args([subcommand, "--offline"])
+        .arg("--")
+        .args(args)
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", dir.join("target"));
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tutorials_are_embedded() {
+        for project in PROJECTS {
+            assert!(
+                crate::cli::docs_embed::get_tutorial_content(project.tutorial).is_some(),
+                "missing tutorial {}",
+                project.tutorial
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_dir_finds_examples() {
+        // cargo runs the tests from the checkout, which holds examples/todo
+        assert!(has_projects(&default_dir()));
+        assert!(!has_projects(Path::new("src")));
+    }
+
+    #[test]
+    fn test_cargo_forwards_args_and_uses_own_target_dir() {
+        let dir = default_dir().join("todo");
+        assert!(dir.is_absolute());
+        let command = cargo(&dir, Action::Run, &["add".to_string(), "x".to_string()]);
+        let args: Vec<_> = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy())
+            .collect();
+        assert_eq!(args, ["run", "--offline", "--", "add", "x"]);
+        let target_dir = command
+            .get_envs()
+            .find(|(name, _)| *name == "CARGO_TARGET_DIR")
+            .and_then(|(_, value)| value);
+        assert_eq!(target_dir, Some(dir.join("target").as_os_str()));
+        assert_eq!(command.get_current_dir(), Some(dir.as_path()));
+    }
+}
@@ -0,0 +1,381 @@
+//! Quiz questions parsed from the embedded docs
+//!
+//! Questions come from `quiz/index.md` and from the "知识检查" sections at
+//! the end of the tutorials. A question starts with a `**问题 N**` line,
+//! may list `A)`..`D)` options, and keeps its answer and explanation in a
+//! `<details>` block:
+//!
+//! ```text
+//! **问题 1** 🟢 (基础)
+//!
+//! <!-- sample: basic/closure -->
+//! Prompt text and code ...
+//!
+//! A) first option
+//! B) second option
+//!
+//! <details>
+//! <summary>答案与解析</summary>
+//!
+//! **答案**: B) second option
+//!
+//! **解析**: explanation ...
+//! </details>
+//! ```
+//!
+//! The optional `sample` marker links a "predict the output" question to a
+//! topic, whose real output then decides which option is correct.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::cli::docs_embed;
+use crate::cli::registry::{self, Topic};
+
+/// Path of the quiz bank inside docs/src/
+pub const QUIZ_BANK: &str = "quiz/index.md";
+
+/// Heading text marking a quiz section inside a tutorial
+const QUIZ_HEADING: &str = "知识检查";
+
+static OPTION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-D])[).．、]\s*(.+?)\s*$").unwrap());
+static ANSWER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\*\*答案\*\*\s*[:：]?\s*(.*)$").unwrap());
+static SAMPLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^<!--\s*sample:\s*(\S+)\s*-->$").unwrap());
+
+/// How a question is answered and graded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Kind {
+    /// Pick one of the options; holds the letter of the documented answer
+    MultipleChoice(char),
+    /// Answer yes or no
+    TrueFalse(bool),
+    /// Free-form answer, graded by the learner after revealing it
+    Open,
+}
+
+/// A single quiz question
+#[derive(Debug, Clone)]
+pub struct Question {
+    /// Stable id: "<doc path>#<position in the file>"
+    pub id: String,
+    /// Document the question was parsed from, relative to docs/src/
+    pub doc_path: &'static str,
+    /// Text after `**问题 N**`, e.g. "🟢 (基础)"
+    pub label: String,
+    /// Prompt markdown, including code blocks
+    pub prompt: String,
+    /// Options as (letter, text)
+    pub options: Vec<(char, String)>,
+    /// Grading mode
+    pub kind: Kind,
+    /// Documented answer text
+    pub answer: String,
+    /// Explanation markdown shown after answering
+    pub explanation: String,
+    /// Linked sample ("category/name") whose output decides the answer
+    pub sample: Option<String>,
+}
+
+impl Question {
+    /// Topic whose sample output decides the answer, if any
+    pub fn sample_topic(&self) -> Option<&'static Topic> {
+        let (category, name) = self.sample.as_deref()?.split_once('/')?;
+        registry::get_topic(category, name)
+    }
+
+    /// Text of the option with `letter`
+    pub fn option(&self, letter: char) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(l, _)| *l == letter)
+            .map(|(_, text)| text.as_str())
+    }
+
+    /// Find the option that appears as a whole line of `stdout`
+    ///
+    /// Backticks and surrounding quotes are ignored. Returns `None` unless
+    /// exactly one option matches.
+    pub fn option_in_output(&self, stdout: &str) -> Option<char> {
+        let lines: Vec<&str> = stdout.lines().map(str::trim).collect();
+        let mut matching = self.options.iter().filter(|(_, text)| {
+            let text = strip_code(text);
+            !text.is_empty() && lines.contains(&text)
+        });
+        match (matching.next(), matching.next()) {
+            (Some((letter, _)), None) => Some(*letter),
+            _ => None,
+        }
+    }
+
+    /// Check whether this question belongs to `topic`
+    pub fn belongs_to(&self, topic: &Topic) -> bool {
+        topic.doc_path == Some(self.doc_path) || self.sample.as_deref() == Some(&topic.key())
+    }
+
+    /// Check whether this question belongs to `category`
+    pub fn in_category(&self, category: &str) -> bool {
+        self.doc_path
+            .strip_prefix(category)
+            .is_some_and(|rest| rest.starts_with('/'))
+            || registry::get_topics_by_category(category).any(|topic| self.belongs_to(topic))
+    }
+}
+
+/// Parse every question of the quiz bank and the tutorials
+pub fn all_questions() -> Vec<Question> {
+    let mut questions = Vec::new();
+    for file in docs_embed::all_markdown_files() {
+        if let (Some(path), Some(content)) = (file.path().to_str(), file.contents_utf8()) {
+            questions.extend(parse(path, content));
+        }
+    }
+    questions
+}
+
+/// Parse the questions of one markdown document
+///
+/// In the quiz bank every question counts; elsewhere only questions under
+/// a heading containing "知识检查" (up to the next heading of the same level).
+pub fn parse(doc_path: &'static str, content: &str) -> Vec<Question> {
+    let whole_file = doc_path == QUIZ_BANK;
+    let mut questions = Vec::new();
+    let mut quiz_level: Option<usize> = None;
+    let mut current: Option<Draft> = None;
+    let mut in_code = false;
+    let mut position = 0;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+        }
+
+        if !in_code && trimmed.starts_with('#') {
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            if quiz_level.is_some_and(|quiz| level <= quiz) {
+                quiz_level = None;
+            }
+            if trimmed.contains(QUIZ_HEADING) {
+                quiz_level = Some(level);
+            }
+            questions.extend(current.take().and_then(Draft::finish));
+            continue;
+        }
+        if !whole_file && quiz_level.is_none() {
+            continue;
+        }
+
+        if !in_code && let Some(rest) = trimmed.strip_prefix("**问题") {
+            questions.extend(current.take().and_then(Draft::finish));
+            position += 1;
+            let label = rest.split_once("**").map_or("", |(_, label)| label.trim());
+            current = Some(Draft::new(doc_path, position, label));
+            continue;
+        }
+        if let Some(draft) = current.as_mut()
+            && draft.push(line, in_code)
+        {
+            questions.extend(current.take().and_then(Draft::finish));
+        }
+    }
+    questions.extend(current.take().and_then(Draft::finish));
+    questions
+}
+
+/// A question while its lines are being collected
+struct Draft {
+    id: String,
+    doc_path: &'static str,
+    label: String,
+    prompt: Vec<String>,
+    options: Vec<(char, String)>,
+    sample: Option<String>,
+    details: Option<Vec<String>>,
+}
+
+impl Draft {
+    fn new(doc_path: &'static str, position: usize, label: &str) -> Self {
+        Self {
+            id: format!("{}#{}", doc_path, position),
+            doc_path,
+            label: label.to_string(),
+            prompt: Vec::new(),
+            options: Vec::new(),
+            sample: None,
+            details: None,
+        }
+    }
+
+    /// Add a line; returns `true` once the `<details>` block is closed
+    fn push(&mut self, line: &str, in_code: bool) -> bool {
+        let trimmed = line.trim();
+        if let Some(details) = self.details.as_mut() {
+            if !in_code && trimmed.starts_with("</details>") {
+                return true;
+            }
+            if !trimmed.starts_with("<summary>") {
+                details.push(line.to_string());
+            }
+        } else if !in_code && trimmed.starts_with("<details>") {
+            self.details = Some(Vec::new());
+        } else if let Some(sample) = SAMPLE.captures(trimmed) {
+            self.sample = Some(sample[1].to_string());
+        } else if let Some(option) = OPTION.captures(trimmed).filter(|_| !in_code) {
+            let letter = option[1].chars().next().unwrap_or('A');
+            self.options.push((letter, option[2].to_string()));
+        } else {
+            self.prompt.push(line.trim_end().to_string());
+        }
+        false
+    }
+
+    /// Build the question; drafts without an answer are dropped
+    fn finish(self) -> Option<Question> {
+        let details = self.details?;
+        let start = details.iter().position(|l| ANSWER.is_match(l.trim()))?;
+
+        // The answer follows `**答案**:` or, if that is empty, on the next lines
+        let inline = ANSWER.captures(details[start].trim())?[1]
+            .trim()
+            .to_string();
+        let mut end = start + 1;
+        let answer = if inline.is_empty() {
+            let first = details[end..].iter().position(|l| !l.trim().is_empty());
+            let first = end + first.unwrap_or(details.len() - end);
+            end = details[first..]
+                .iter()
+                .position(|l| l.trim().is_empty())
+                .map_or(details.len(), |p| first + p);
+            details[first..end].join("\n").trim().to_string()
+        } else {
+            inline
+        };
+        if answer.is_empty() {
+            return None;
+        }
+
+        let explanation = details[end..].join("\n").trim().to_string();
+        let prompt = self.prompt.join("\n").trim().to_string();
+        let kind = classify(&prompt, &self.options, &answer);
+
+        Some(Question {
+            id: self.id,
+            doc_path: self.doc_path,
+            label: self.label,
+            prompt,
+            options: self.options,
+            kind,
+            answer,
+            explanation,
+            sample: self.sample,
+        })
+    }
+}
+
+/// Decide how a question is graded from its options and documented answer
+fn classify(prompt: &str, options: &[(char, String)], answer: &str) -> Kind {
+    if !options.is_empty() {
+        let mut chars = answer.chars();
+        if let Some(letter) = chars.next().filter(|c| ('A'..='D').contains(c))
+            && chars.next().is_none_or(|c| ").．、 ".contains(c))
+            && options.iter().any(|(l, _)| *l == letter)
+        {
+            return Kind::MultipleChoice(letter);
+        }
+        if let Some((letter, _)) = options.iter().find(|(_, text)| text == answer) {
+            return Kind::MultipleChoice(*letter);
+        }
+        return Kind::Open;
+    }
+
+    if prompt.contains('吗') {
+        let answer = answer.trim_start_matches(['❌', '✅', ' ']);
+        if answer.starts_with(['不', '否', '错', '没']) {
+            return Kind::TrueFalse(false);
+        }
+        if ["会", "是", "对", "能", "可以", "正确"]
+            .iter()
+            .any(|yes| answer.starts_with(yes))
+        {
+            return Kind::TrueFalse(true);
+        }
+    }
+    Kind::Open
+}
+
+/// Remove inline-code backticks and surrounding quotes from an option
+fn strip_code(text: &str) -> &str {
+    text.trim().trim_matches('`').trim_matches('"').trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# Title\n\n**问题 1** 🟢 (不在题库中)\n\n## 知识检查\n\n\
+        **问题 1** 🟢 (基础)\n\n<!-- sample: basic/closure -->\n\n以下代码的输出是什么？\n\n\
+        ```rust\nA) not an option\n```\n\nA) `5`  \nB) `8`  \n\n<details>\n\
+        <summary>答案</summary>\n\n**答案**: B) `8`\n\n**解析**: 5 + 3\n</details>\n\n\
+        **问题 2** 🟡\n\n这段代码能编译通过吗？\n\n<details>\n\n**答案**：\n❌ 不能编译通过\n\n\
+        **修复**: 使用引用\n</details>\n\n## 下一节\n\n**问题 3**\n\n<details>\n**答案**: x\n</details>\n";
+
+    #[test]
+    fn test_parse_quiz_section_only() {
+        let questions = parse("basic/test.md", DOC);
+        assert_eq!(questions.len(), 2);
+
+        let first = &questions[0];
+        assert_eq!(first.id, "basic/test.md#1");
+        assert_eq!(first.label, "🟢 (基础)");
+        assert_eq!(first.sample.as_deref(), Some("basic/closure"));
+        assert_eq!(first.options.len(), 2);
+        assert!(first.prompt.contains("A) not an option"));
+        assert_eq!(first.kind, Kind::MultipleChoice('B'));
+        assert_eq!(first.explanation, "**解析**: 5 + 3");
+
+        let second = &questions[1];
+        assert_eq!(second.kind, Kind::TrueFalse(false));
+        assert_eq!(second.answer, "❌ 不能编译通过");
+        assert_eq!(second.explanation, "**修复**: 使用引用");
+    }
+
+    #[test]
+    fn test_option_in_output() {
+        let question = &parse("basic/test.md", DOC)[0];
+        assert_eq!(question.option_in_output("x\n8\n"), Some('B'));
+        assert_eq!(question.option_in_output("5\n8\n"), None);
+        assert_eq!(question.option_in_output("13\n"), None);
+    }
+
+    #[test]
+    fn test_quiz_bank_questions_are_gradable() {
+        let questions = parse(
+            QUIZ_BANK,
+            docs_embed::get_tutorial_content(QUIZ_BANK).unwrap(),
+        );
+        assert!(!questions.is_empty());
+        for question in &questions {
+            assert_ne!(question.kind, Kind::Open, "{} is not gradable", question.id);
+            if question.sample.is_some() {
+                assert!(
+                    question.sample_topic().is_some(),
+                    "{} links an unknown sample",
+                    question.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tutorial_questions_parsed() {
+        let questions = all_questions();
+        assert!(questions.iter().any(|q| q.doc_path == "basic/datatype.md"));
+        assert!(questions
+            .iter()
+            .any(|q| matches!(q.kind, Kind::MultipleChoice(_))));
+    }
+}
@@ -3,17 +3,130 @@
 //! New samples are registered by adding `inventory::submit!` declarations.
 //! No manual routing table updates required.
 
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe, Location};
+use std::pin::Pin;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
 use inventory::{collect, iter};
 
+use crate::cli::docs_embed;
+use crate::cli::i18n::{self, Lang};
+use crate::cli::runtime::{self, Completion};
+
 /// Type of sample (for execution handling)
 #[derive(Debug, Clone, Copy)]
 pub enum SampleType {
     /// Synchronous or async function that runs and returns
     Function,
-    /// Long-running server process (requires separate terminal)
+    /// Long-running server process (run in the background with `hello serve`)
     Server,
 }
 
+/// External service a sample needs at runtime, probed by `hello doctor`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dependency {
+    /// Consul agent (HTTP API)
+    Consul,
+    /// MySQL server
+    MySql,
+    /// SQLite (embedded, always available)
+    Sqlite,
+    /// MQTT broker
+    Mqtt,
+    /// Ollama LLM server (HTTP API)
+    Ollama,
+    /// gRPC server (e.g. `hello serve awesome grpc-greeter`)
+    Grpc,
+}
+
+impl Dependency {
+    /// Every dependency, in `hello doctor` order
+    pub const ALL: [Dependency; 6] = [
+        Dependency::Consul,
+        Dependency::MySql,
+        Dependency::Sqlite,
+        Dependency::Mqtt,
+        Dependency::Ollama,
+        Dependency::Grpc,
+    ];
+
+    /// Display name of the service
+    pub const fn name(self) -> &'static str {
+        match self {
+            Dependency::Consul => "Consul",
+            Dependency::MySql => "MySQL",
+            Dependency::Sqlite => "SQLite",
+            Dependency::Mqtt => "MQTT",
+            Dependency::Ollama => "Ollama",
+            Dependency::Grpc => "gRPC",
+        }
+    }
+}
+
+impl fmt::Display for Dependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Value type of a topic argument
+#[derive(Debug, Clone, Copy)]
+pub enum ArgKind {
+    /// Non-negative integer; accepts `10_000` and scientific notation such as `1e7`
+    Integer,
+    /// TCP port, 1 to 65535
+    Port,
+    /// Free-form text
+    Text,
+    /// One of a fixed set of values
+    Choice(&'static [&'static str]),
+}
+
+/// A typed command-line option a topic accepts, e.g. `--steps 1e7`
+#[derive(Debug, Clone, Copy)]
+pub struct TopicArg {
+    /// Long option name without the leading `--` (kebab-case)
+    pub name: &'static str,
+
+    /// One-line help shown by `hello <category> --help`
+    pub help: &'static str,
+
+    /// Value used when the option is not given
+    pub default: &'static str,
+
+    /// Accepted values
+    pub kind: ArgKind,
+}
+
+/// Parsed arguments passed to a topic's sample, with defaults filled in
+#[derive(Debug, Clone, Default)]
+pub struct ArgValues {
+    values: BTreeMap<&'static str, String>,
+}
+
+/// Result of a sample; an error is reported with its chain and exit code 4
+pub type TopicResult = anyhow::Result<()>;
+
+/// Future of an async sample, run on the shared runtime (see [`runtime`])
+pub type TopicFuture = Pin<Box<dyn Future<Output = TopicResult>>>;
+
+/// Entry point of a sample
+#[derive(Debug, Clone, Copy)]
+pub enum SampleFn {
+    /// Plain synchronous function
+    Sync(fn() -> TopicResult),
+    /// Synchronous function taking the topic's arguments
+    SyncWithArgs(fn(&ArgValues) -> TopicResult),
+    /// Async function, run on the shared runtime
+    Async(fn() -> TopicFuture),
+    /// Async function taking the topic's arguments, run on the shared runtime
+    AsyncWithArgs(fn(ArgValues) -> TopicFuture),
+}
+
 /// A Topic represents a single learnable unit with its sample function
 #[derive(Debug, Clone)]
 pub struct Topic {
@@ -23,48 +136,511 @@ pub struct Topic {
     /// One-line Chinese description with English technical terms
     pub description: &'static str,
 
-    /// Parent category: "basic", "advance", "awesome", "algo"
+    /// English description shown with `--lang en` (`None` = use `description`)
+    pub description_en: Option<&'static str>,
+
+    /// Parent category name, must match a registered [`Category`]
     pub category: &'static str,
 
-    /// Zero-argument function pointer to execute the sample
-    pub func: fn(),
+    /// Entry point of the sample
+    pub func: SampleFn,
+
+    /// Options the sample accepts (empty slice if none)
+    pub args: &'static [TopicArg],
+
+    /// Time an async sample may run before it is cancelled (`None` = no limit)
+    pub timeout: Option<Duration>,
 
     /// External services required (empty slice if none)
-    /// Valid values: "Consul", "MySQL", "SQLite", "MQTT", "Ollama", "gRPC"
-    pub dependencies: &'static [&'static str],
+    pub dependencies: &'static [Dependency],
 
     /// Path to embedded markdown tutorial (e.g., "basic/ownership.md")
     pub doc_path: Option<&'static str>,
 
     /// Whether this is a function or server sample
     pub sample_type: SampleType,
+
+    /// Topics to finish first, as "name" (same category) or "category/name"
+    pub prerequisites: &'static [&'static str],
+
+    /// Output depends on the machine or randomness, so `hello verify` skips it
+    pub volatile_output: bool,
+
+    /// Local TCP port a server sample listens on, probed for readiness
+    pub port: Option<u16>,
+
+    /// Where the topic is registered (captured by [`Topic::new`])
+    pub source: &'static Location<'static>,
+
+    /// Function the sample runs, shown by `hello source` (`None` = not recorded)
+    pub sample_source: Option<SampleSource>,
+}
+
+/// The function a topic runs, located in the embedded sources (see [`source`])
+///
+/// [`source`]: crate::cli::source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleSource {
+    /// File relative to the repository root (e.g. "src/basic/ownership_sample.rs")
+    pub file: &'static str,
+    /// Name of the function; its span is found in the file when needed
+    pub function: &'static str,
+}
+
+/// A Category groups topics under one `hello <category>` subcommand
+#[derive(Debug, Clone)]
+pub struct Category {
+    /// CLI subcommand identifier (e.g. "basic", "advance")
+    pub name: &'static str,
+
+    /// Human-readable title shown in topic listings
+    pub display_name: &'static str,
+
+    /// Sort key for listings and `--help` (lower comes first)
+    pub order: u32,
+
+    /// One-line summary used as the subcommand's help text
+    pub description: &'static str,
+
+    /// English title and summary shown with `--lang en` (`None` = untranslated)
+    pub english: Option<(&'static str, &'static str)>,
+}
+
+/// A hands-on exercise for a topic: a library stub plus hidden tests
+///
+/// `hello exercise <topic>` writes `starter` as `src/lib.rs` of a fresh cargo
+/// project; `tests` is only added while checking, as `tests/check.rs`.
+#[derive(Debug, Clone)]
+pub struct Exercise {
+    /// Key of the topic this exercise belongs to ("category/name")
+    pub topic: &'static str,
+
+    /// Starter `src/lib.rs` with `TODO`-marked stubs
+    pub starter: &'static str,
+
+    /// Hidden integration tests run against the learner's solution
+    pub tests: &'static str,
 }
 
 // Register the inventory collection for all topics
 collect!(Topic);
 
+// Register the inventory collection for all exercises
+collect!(Exercise);
+
+// Register the inventory collection for all categories
+collect!(Category);
+
+impl Category {
+    /// Create a new Category instance
+    pub const fn new(
+        name: &'static str,
+        display_name: &'static str,
+        order: u32,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            display_name,
+            order,
+            description,
+            english: None,
+        }
+    }
+
+    /// Add the English title and summary
+    pub const fn with_english(
+        mut self,
+        display_name: &'static str,
+        description: &'static str,
+    ) -> Self {
+        self.english = Some((display_name, description));
+        self
+    }
+
+    /// Get the title in the output language
+    pub fn localized_display_name(&self) -> &'static str {
+        match (i18n::lang(), self.english) {
+            (Lang::En, Some((display_name, _))) => display_name,
+            _ => self.display_name,
+        }
+    }
+
+    /// Get the summary in the output language
+    pub fn localized_description(&self) -> &'static str {
+        match (i18n::lang(), self.english) {
+            (Lang::En, Some((_, description))) => description,
+            _ => self.description,
+        }
+    }
+}
+
+impl Exercise {
+    /// Create a new Exercise instance
+    pub const fn new(topic: &'static str, starter: &'static str, tests: &'static str) -> Self {
+        Self {
+            topic,
+            starter,
+            tests,
+        }
+    }
+
+    /// Get the topic this exercise belongs to
+    pub fn topic(&self) -> Option<&'static Topic> {
+        find_topic(self.topic)
+    }
+}
+
+impl ArgKind {
+    /// Placeholder shown in `--help`; choices list their possible values instead
+    pub fn value_name(&self) -> Option<&'static str> {
+        match self {
+            ArgKind::Integer => Some("N"),
+            ArgKind::Port => Some("PORT"),
+            ArgKind::Text => Some("TEXT"),
+            ArgKind::Choice(_) => None,
+        }
+    }
+}
+
+impl TopicArg {
+    /// Declare an integer option
+    pub const fn integer(name: &'static str, default: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            default,
+            kind: ArgKind::Integer,
+        }
+    }
+
+    /// Declare a port option
+    pub const fn port(name: &'static str, default: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            default,
+            kind: ArgKind::Port,
+        }
+    }
+
+    /// Declare a text option
+    pub const fn text(name: &'static str, default: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            default,
+            kind: ArgKind::Text,
+        }
+    }
+
+    /// Declare an option taking one of `choices`
+    pub const fn choice(
+        name: &'static str,
+        choices: &'static [&'static str],
+        default: &'static str,
+        help: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            help,
+            default,
+            kind: ArgKind::Choice(choices),
+        }
+    }
+
+    /// Check that `value` is valid for this option
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match self.kind {
+            ArgKind::Integer => parse_integer(value).map(|_| ()).ok_or_else(|| {
+                format!(
+                    "invalid value '{}' for --{}: expected an integer such as 1000 or 1e7",
+                    value, self.name
+                )
+            }),
+            ArgKind::Port => parse_port(value).map(|_| ()).ok_or_else(|| {
+                format!(
+                    "invalid value '{}' for --{}: expected a port between 1 and 65535",
+                    value, self.name
+                )
+            }),
+            ArgKind::Text => Ok(()),
+            ArgKind::Choice(choices) if choices.contains(&value) => Ok(()),
+            ArgKind::Choice(choices) => Err(format!(
+                "invalid value '{}' for --{}: expected one of {}",
+                value,
+                self.name,
+                choices.join(", ")
+            )),
+        }
+    }
+}
+
+impl ArgValues {
+    /// Get an integer option
+    ///
+    /// # Panics
+    /// If the topic does not declare `name` as an [`ArgKind::Integer`] option
+    pub fn integer(&self, name: &str) -> u64 {
+        self.values
+            .get(name)
+            .and_then(|value| parse_integer(value))
+            .unwrap_or_else(|| panic!("topic option --{} is not a declared integer", name))
+    }
+
+    /// Get a port option
+    ///
+    /// # Panics
+    /// If the topic does not declare `name` as an [`ArgKind::Port`] option
+    pub fn port(&self, name: &str) -> u16 {
+        self.values
+            .get(name)
+            .and_then(|value| parse_port(value))
+            .unwrap_or_else(|| panic!("topic option --{} is not a declared port", name))
+    }
+
+    /// Get a text or choice option
+    ///
+    /// # Panics
+    /// If the topic does not declare `name`
+    pub fn text(&self, name: &str) -> &str {
+        self.values
+            .get(name)
+            .unwrap_or_else(|| panic!("topic option --{} is not declared", name))
+    }
+}
+
+/// Parse a non-negative integer written as `10000`, `10_000` or `1e7`
+pub fn parse_integer(value: &str) -> Option<u64> {
+    let value = value.trim().replace('_', "");
+    if let Ok(integer) = value.parse::<u64>() {
+        return Some(integer);
+    }
+    let (mantissa, exponent) = value.split_once(['e', 'E'])?;
+    let mantissa: f64 = mantissa.parse().ok()?;
+    let exponent: i32 = exponent.parse().ok()?;
+    let number = mantissa * 10f64.powi(exponent);
+    (number >= 0.0 && number.fract() == 0.0 && number <= u64::MAX as f64).then_some(number as u64)
+}
+
+/// Parse a port number, rejecting 0 and values above 65535
+fn parse_port(value: &str) -> Option<u16> {
+    parse_integer(value)
+        .and_then(|port| u16::try_from(port).ok())
+        .filter(|&port| port != 0)
+}
+
 impl Topic {
-    /// Create a new Topic instance
+    /// Create a new Topic instance, recording the caller as its source location
+    #[track_caller]
     pub const fn new(
         name: &'static str,
         description: &'static str,
         category: &'static str,
-        func: fn(),
-        dependencies: &'static [&'static str],
+        func: fn() -> TopicResult,
+        dependencies: &'static [Dependency],
         doc_path: Option<&'static str>,
         sample_type: SampleType,
     ) -> Self {
         Self {
             name,
             description,
+            description_en: None,
             category,
-            func,
+            func: SampleFn::Sync(func),
+            args: &[],
+            timeout: None,
             dependencies,
             doc_path,
             sample_type,
+            prerequisites: &[],
+            volatile_output: false,
+            port: None,
+            source: Location::caller(),
+            sample_source: None,
         }
     }
 
+    /// Create a new Topic whose sample is an async function
+    ///
+    /// The future is run on the shared runtime; the sample must not build its own.
+    #[track_caller]
+    pub const fn new_async(
+        name: &'static str,
+        description: &'static str,
+        category: &'static str,
+        func: fn() -> TopicFuture,
+        dependencies: &'static [Dependency],
+        doc_path: Option<&'static str>,
+        sample_type: SampleType,
+    ) -> Self {
+        let mut topic = Self::new(
+            name,
+            description,
+            category,
+            || Ok(()),
+            dependencies,
+            doc_path,
+            sample_type,
+        );
+        topic.func = SampleFn::Async(func);
+        topic
+    }
+
+    /// Add the English description shown with `--lang en`
+    pub const fn with_english(mut self, description: &'static str) -> Self {
+        self.description_en = Some(description);
+        self
+    }
+
+    /// Record the file and function the sample runs, for `hello source`
+    pub const fn with_sample_source(mut self, file: &'static str, function: &'static str) -> Self {
+        self.sample_source = Some(SampleSource { file, function });
+        self
+    }
+
+    /// Declare topics that should be finished before this one
+    pub const fn with_prerequisites(mut self, prerequisites: &'static [&'static str]) -> Self {
+        self.prerequisites = prerequisites;
+        self
+    }
+
+    /// Mark the sample output as machine- or run-dependent
+    pub const fn with_volatile_output(mut self) -> Self {
+        self.volatile_output = true;
+        self
+    }
+
+    /// Declare the options the sample accepts and its entry point taking them
+    pub const fn with_args(
+        mut self,
+        args: &'static [TopicArg],
+        func: fn(&ArgValues) -> TopicResult,
+    ) -> Self {
+        self.args = args;
+        self.func = SampleFn::SyncWithArgs(func);
+        self
+    }
+
+    /// Declare the options an async sample accepts and its entry point taking them
+    pub const fn with_async_args(
+        mut self,
+        args: &'static [TopicArg],
+        func: fn(ArgValues) -> TopicFuture,
+    ) -> Self {
+        self.args = args;
+        self.func = SampleFn::AsyncWithArgs(func);
+        self
+    }
+
+    /// Cancel an async sample after `seconds` (overridable with `--timeout`)
+    pub const fn with_timeout(mut self, seconds: u64) -> Self {
+        self.timeout = Some(Duration::from_secs(seconds));
+        self
+    }
+
+    /// Declare the local port a server sample listens on
+    pub const fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Port the server listens on when run with `values`
+    ///
+    /// Its `--port` option if it declares one, otherwise the [`Topic::with_port`] default
+    pub fn listen_port(&self, values: &ArgValues) -> Option<u16> {
+        self.args
+            .iter()
+            .find(|arg| arg.name == "port" && matches!(arg.kind, ArgKind::Port))
+            .map(|arg| values.port(arg.name))
+            .or(self.port)
+    }
+
+    /// Get the description in the output language
+    pub fn localized_description(&self) -> &'static str {
+        match (i18n::lang(), self.description_en) {
+            (Lang::En, Some(description)) => description,
+            _ => self.description,
+        }
+    }
+
+    /// Unique "category/name" key of this topic
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.category, self.name)
+    }
+
+    /// Resolve `prerequisites` to registered topics, skipping unknown names
+    pub fn prerequisite_topics(&self) -> Vec<&'static Topic> {
+        self.prerequisites
+            .iter()
+            .filter_map(|prerequisite| match prerequisite.split_once('/') {
+                Some((category, name)) => get_topic(category, name),
+                None => get_topic(self.category, prerequisite),
+            })
+            .collect()
+    }
+
+    /// Get the names of the external dependencies, joined by ", "
+    pub fn dependency_names(&self) -> String {
+        let names: Vec<_> = self.dependencies.iter().map(|d| d.name()).collect();
+        names.join(", ")
+    }
+
+    /// Validate the given `(name, value)` options and fill in the defaults
+    ///
+    /// # Errors
+    /// An unknown option name or a value that does not match its [`ArgKind`]
+    pub fn resolve_args(&self, given: &[(String, String)]) -> Result<ArgValues, String> {
+        let mut values = BTreeMap::new();
+        for arg in self.args {
+            values.insert(arg.name, arg.default.to_string());
+        }
+        for (name, value) in given {
+            let Some(arg) = self.args.iter().find(|arg| arg.name == name) else {
+                return Err(format!("topic '{}' does not accept --{}", self.key(), name));
+            };
+            arg.check(value)?;
+            values.insert(arg.name, value.clone());
+        }
+        Ok(ArgValues { values })
+    }
+
+    /// Run the sample with `values` (see [`Topic::resolve_args`])
+    ///
+    /// Async samples run on the shared runtime and are cancelled once
+    /// `timeout` elapses or Ctrl-C is pressed; synchronous samples always
+    /// run to completion.
+    ///
+    /// # Errors
+    /// The error returned by the sample, or a panic turned into an error
+    /// (the panic message itself is still printed by the panic hook)
+    pub fn run(&self, values: ArgValues, timeout: Option<Duration>) -> Result<Completion> {
+        let run = || match self.func {
+            SampleFn::Sync(func) => func().map(|()| Completion::Finished),
+            SampleFn::SyncWithArgs(func) => func(&values).map(|()| Completion::Finished),
+            SampleFn::Async(func) => runtime::run(func(), timeout),
+            SampleFn::AsyncWithArgs(func) => runtime::run(func(values), timeout),
+        };
+        panic::catch_unwind(AssertUnwindSafe(run)).unwrap_or_else(|payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic payload");
+            Err(anyhow!("sample panicked: {}", message))
+        })
+    }
+
+    /// Check whether the sample is an async function
+    pub fn is_async(&self) -> bool {
+        matches!(self.func, SampleFn::Async(_) | SampleFn::AsyncWithArgs(_))
+    }
+
+    /// Source location as "file:line"
+    pub fn source_location(&self) -> String {
+        format!("{}:{}", self.source.file(), self.source.line())
+    }
+
     /// Check if this topic has external dependencies
     pub fn has_dependencies(&self) -> bool {
         !self.dependencies.is_empty()
@@ -86,13 +662,228 @@ pub fn get_topic(category: &str, name: &str) -> Option<&'static Topic> {
     iter::<Topic>().find(|topic| topic.category == category && topic.name == name)
 }
 
-/// Get all unique categories
-pub fn get_all_categories() -> impl Iterator<Item = &'static str> {
-    let mut categories = Vec::new();
-    for topic in iter::<Topic>() {
-        if !categories.contains(&topic.category) {
-            categories.push(topic.category);
+/// Get all registered exercises, sorted by topic key
+pub fn get_all_exercises() -> Vec<&'static Exercise> {
+    let mut exercises: Vec<_> = iter::<Exercise>().collect();
+    exercises.sort_by_key(|exercise| exercise.topic);
+    exercises
+}
+
+/// Get the exercise of `topic`, if it ships one
+pub fn get_exercise(topic: &Topic) -> Option<&'static Exercise> {
+    let key = topic.key();
+    iter::<Exercise>().find(|exercise| exercise.topic == key)
+}
+
+/// Find a topic by "category/name" or by a bare name (first match wins)
+pub fn find_topic(spec: &str) -> Option<&'static Topic> {
+    match spec.split_once('/') {
+        Some((category, name)) => get_topic(category, name),
+        None => iter::<Topic>().find(|topic| topic.name == spec),
+    }
+}
+
+/// Get all registered categories, sorted by their `order`
+pub fn get_all_categories() -> Vec<&'static Category> {
+    let mut categories: Vec<_> = iter::<Category>().collect();
+    categories.sort_by_key(|category| (category.order, category.name));
+    categories
+}
+
+/// Get a specific category by name
+pub fn get_category(name: &str) -> Option<&'static Category> {
+    iter::<Category>().find(|category| category.name == name)
+}
+
+/// Get all topics in registry order
+///
+/// Categories come by `order`, then topics by where their tutorial appears
+/// in the book's `SUMMARY.md`, then by name.
+pub fn get_sorted_topics() -> Vec<&'static Topic> {
+    get_all_categories()
+        .into_iter()
+        .flat_map(|category| {
+            let mut topics: Vec<_> = get_topics_by_category(category.name).collect();
+            topics.sort_by_key(|topic| {
+                let position = topic.doc_path.and_then(docs_embed::summary_position);
+                (position.unwrap_or(usize::MAX), topic.name)
+            });
+            topics
+        })
+        .collect()
+}
+
+/// Get all topics in recommended learning order
+///
+/// The base order is the registry order of [`get_sorted_topics`]. A topic is
+/// moved after its prerequisites; prerequisite cycles fall back to the base
+/// order.
+pub fn learning_path() -> Vec<&'static Topic> {
+    let mut remaining = get_sorted_topics();
+
+    let mut path: Vec<&'static Topic> = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        let ready = remaining
+            .iter()
+            .position(|topic| {
+                topic
+                    .prerequisite_topics()
+                    .iter()
+                    .all(|prerequisite| path.iter().any(|done| std::ptr::eq(*done, *prerequisite)))
+            })
+            .unwrap_or(0);
+        path.push(remaining.remove(ready));
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_topic_has_registered_category() {
+        for topic in get_all_topics() {
+            assert!(
+                get_category(topic.category).is_some(),
+                "topic '{}' uses unregistered category '{}'",
+                topic.name,
+                topic.category
+            );
+        }
+    }
+
+    #[test]
+    fn test_learning_path_respects_prerequisites() {
+        let path = learning_path();
+        assert_eq!(path.len(), get_all_topics().count());
+
+        let position = |topic: &Topic| path.iter().position(|t| std::ptr::eq(*t, topic));
+        for topic in &path {
+            for prerequisite in topic.prerequisite_topics() {
+                assert!(
+                    position(prerequisite) < position(topic),
+                    "'{}' must come after its prerequisite '{}'",
+                    topic.key(),
+                    prerequisite.key()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_prerequisites_are_registered() {
+        for topic in get_all_topics() {
+            assert_eq!(
+                topic.prerequisite_topics().len(),
+                topic.prerequisites.len(),
+                "topic '{}' has an unknown prerequisite in {:?}",
+                topic.key(),
+                topic.prerequisites
+            );
+        }
+    }
+
+    #[test]
+    fn test_exercises_belong_to_topics() {
+        for exercise in get_all_exercises() {
+            assert!(
+                exercise.topic().is_some(),
+                "exercise for unknown topic '{}'",
+                exercise.topic
+            );
+            assert!(exercise.starter.contains("TODO"));
+        }
+    }
+
+    #[test]
+    fn test_parse_integer() {
+        assert_eq!(parse_integer("10000"), Some(10_000));
+        assert_eq!(parse_integer("10_000"), Some(10_000));
+        assert_eq!(parse_integer("1e7"), Some(10_000_000));
+        assert_eq!(parse_integer("2.5E3"), Some(2_500));
+        assert_eq!(parse_integer("1.5"), None);
+        assert_eq!(parse_integer("-1"), None);
+        assert_eq!(parse_integer("many"), None);
+    }
+
+    #[test]
+    fn test_resolve_args_fills_defaults() {
+        let topic = get_topic("algo", "calc-pi").unwrap();
+        let values = topic.resolve_args(&[]).unwrap();
+        for arg in topic.args {
+            assert_eq!(values.text(arg.name), arg.default);
+        }
+
+        let given = [("steps".to_string(), "1e3".to_string())];
+        assert_eq!(topic.resolve_args(&given).unwrap().integer("steps"), 1_000);
+
+        let bad = [("method".to_string(), "guess".to_string())];
+        assert!(topic.resolve_args(&bad).is_err());
+        let unknown = [("port".to_string(), "1".to_string())];
+        assert!(topic.resolve_args(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_port_args_are_checked_and_probed() {
+        let topic = get_topic("awesome", "grpc-greeter").unwrap();
+        let defaults = topic.resolve_args(&[]).unwrap();
+        assert_eq!(topic.listen_port(&defaults), topic.port);
+
+        let given = [("port".to_string(), "6000".to_string())];
+        let values = topic.resolve_args(&given).unwrap();
+        assert_eq!(values.port("port"), 6000);
+        assert_eq!(topic.listen_port(&values), Some(6000));
+
+        for bad in ["0", "65536", "1e10", "http"] {
+            let given = [("port".to_string(), bad.to_string())];
+            assert!(topic.resolve_args(&given).is_err(), "--port {}", bad);
+        }
+    }
+
+    #[test]
+    fn test_shared_topic_args_agree() {
+        for category in get_all_categories() {
+            let args: Vec<_> = get_topics_by_category(category.name)
+                .flat_map(|topic| topic.args)
+                .collect();
+            for (i, arg) in args.iter().enumerate() {
+                for other in args[i + 1..].iter().filter(|a| a.name == arg.name) {
+                    assert_eq!(
+                        format!("{:?}", arg.kind),
+                        format!("{:?}", other.kind),
+                        "--{} has different kinds in category '{}'",
+                        arg.name,
+                        category.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_topic_arg_defaults_are_valid() {
+        for topic in get_all_topics() {
+            for arg in topic.args {
+                assert!(
+                    arg.check(arg.default).is_ok(),
+                    "'{}' has an invalid default for --{}",
+                    topic.key(),
+                    arg.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_category_names_are_unique() {
+        let categories = get_all_categories();
+        for (i, category) in categories.iter().enumerate() {
+            assert!(
+                categories[i + 1..].iter().all(|c| c.name != category.name),
+                "category '{}' registered twice",
+                category.name
+            );
         }
     }
-    categories.into_iter()
 }
@@ -0,0 +1,654 @@
+//! Terminal rendering of embedded markdown tutorials
+//!
+//! A small line-based renderer for the markdown used in `docs/src`:
+//! headings, Rust code blocks with syntax highlighting, mdbook-admonish
+//! fences, blockquotes, tables, lists and inline links. Links to other
+//! chapters are resolved to the `hello` command (or doc path) that shows them.
+
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::cli::i18n::t;
+use crate::cli::registry;
+
+/// Width used for rules, boxes and wrapping when `COLUMNS` is not set
+const DEFAULT_WIDTH: usize = 80;
+
+/// Fallback screen height when `LINES` is not set
+const DEFAULT_HEIGHT: usize = 24;
+
+/// Rust keywords highlighted in code blocks
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+/// ANSI styles used by the renderer
+mod ansi {
+    pub const RESET: &str = "\x1b[0m";
+    pub const BOLD: &str = "\x1b[1m";
+    pub const DIM: &str = "\x1b[2m";
+    pub const ITALIC: &str = "\x1b[3m";
+    pub const UNDERLINE: &str = "\x1b[4m";
+    pub const RED: &str = "\x1b[31m";
+    pub const GREEN: &str = "\x1b[32m";
+    pub const YELLOW: &str = "\x1b[33m";
+    pub const BLUE: &str = "\x1b[34m";
+    pub const MAGENTA: &str = "\x1b[35m";
+    pub const CYAN: &str = "\x1b[36m";
+}
+
+/// Rendering options
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer<'a> {
+    /// Whether to emit ANSI escape codes
+    pub color: bool,
+    /// Width of rules and boxes
+    pub width: usize,
+    /// Path of the rendered document relative to docs/src/, for resolving links
+    pub doc_path: &'a str,
+}
+
+impl<'a> Renderer<'a> {
+    /// Create a renderer for stdout: colors only on a terminal and without `NO_COLOR`
+    pub fn for_stdout(doc_path: &'a str) -> Self {
+        Self {
+            color: std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+            width: env_usize("COLUMNS").unwrap_or(DEFAULT_WIDTH).min(120),
+            doc_path,
+        }
+    }
+
+    /// Render a whole markdown document
+    pub fn render(&self, markdown: &str) -> String {
+        let mut out = Vec::new();
+        let lines: Vec<&str> = markdown.lines().collect();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i];
+            let trimmed = line.trim_start();
+
+            if let Some(info) = trimmed.strip_prefix("```") {
+                let end = (i + 1..lines.len())
+                    .find(|&j| lines[j].trim_start().starts_with("```"))
+                    .unwrap_or(lines.len());
+                let body = &lines[i + 1..end];
+                match info.trim().strip_prefix("admonish") {
+                    Some(kind) => self.admonition(kind.trim(), body, &mut out),
+                    None => self.code_block(info.trim(), body, &mut out),
+                }
+                i = end + 1;
+                continue;
+            }
+
+            if trimmed.starts_with('|') {
+                let end = (i..lines.len())
+                    .find(|&j| !lines[j].trim_start().starts_with('|'))
+                    .unwrap_or(lines.len());
+                self.table(&lines[i..end], &mut out);
+                i = end;
+                continue;
+            }
+
+            out.push(self.block_line(line));
+            i += 1;
+        }
+
+        let mut text = out.join("\n");
+        text.push('\n');
+        text
+    }
+
+    fn block_line(&self, line: &str) -> String {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+
+        if trimmed.starts_with('#') {
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            let text = self.inline(trimmed[level..].trim());
+            return match level {
+                1 => format!(
+                    "{}\n{}",
+                    self.paint(&[ansi::BOLD, ansi::UNDERLINE, ansi::MAGENTA], &text),
+                    self.paint(&[ansi::MAGENTA], &"═".repeat(self.width))
+                ),
+                2 => format!(
+                    "\n{}",
+                    self.paint(&[ansi::BOLD, ansi::CYAN], &format!("▌ {}", text))
+                ),
+                _ => self.paint(
+                    &[ansi::BOLD],
+                    &format!("{} {}", "▸".repeat(level - 2), text),
+                ),
+            };
+        }
+        if matches!(trimmed, "---" | "***" | "___") {
+            return self.paint(&[ansi::DIM], &"─".repeat(self.width));
+        }
+        if let Some(quote) = trimmed.strip_prefix('>') {
+            return format!(
+                "{}{} {}",
+                indent,
+                self.paint(&[ansi::BLUE], "┃"),
+                self.paint(&[ansi::ITALIC], &self.inline(quote.trim_start()))
+            );
+        }
+        for bullet in ["- ", "* ", "+ "] {
+            if let Some(item) = trimmed.strip_prefix(bullet) {
+                let item = match item.strip_prefix("[ ] ") {
+                    Some(todo) => format!("☐ {}", todo),
+                    None => match item.strip_prefix("[x] ") {
+                        Some(done) => format!("☑ {}", done),
+                        None => item.to_string(),
+                    },
+                };
+                return format!("{}• {}", indent, self.inline(&item));
+            }
+        }
+        format!("{}{}", indent, self.inline(trimmed))
+    }
+
+    /// Render a fenced code block inside a left gutter
+    fn code_block(&self, info: &str, body: &[&str], out: &mut Vec<String>) {
+        let language = info.split([',', ' ']).next().unwrap_or("");
+        let title = if language.is_empty() {
+            "code"
+        } else {
+            language
+        };
+        out.push(self.paint(&[ansi::DIM], &format!("┌─ {}", title)));
+        for line in body {
+            let code = if language == "rust" {
+                self.highlight_rust(line)
+            } else {
+                line.to_string()
+            };
+            out.push(format!("{} {}", self.paint(&[ansi::DIM], "│"), code));
+        }
+        out.push(self.paint(&[ansi::DIM], "└─"));
+    }
+
+    /// Render an mdbook-admonish fence (```admonish warning "Title") as a box
+    fn admonition(&self, kind: &str, body: &[&str], out: &mut Vec<String>) {
+        let (kind, title) = kind.split_once(' ').unwrap_or((kind, ""));
+        let kind = if kind.is_empty() { "note" } else { kind };
+        let (icon, color) = match kind {
+            "warning" | "caution" | "attention" => ("⚠", ansi::YELLOW),
+            "danger" | "error" | "failure" | "bug" => ("✘", ansi::RED),
+            "tip" | "hint" | "success" | "check" | "done" => ("✔", ansi::GREEN),
+            "important" => ("★", ansi::MAGENTA),
+            _ => ("ℹ", ansi::BLUE),
+        };
+        let title = title.trim().trim_matches('"');
+        let header = if title.is_empty() {
+            format!("{} {}", icon, kind.to_uppercase())
+        } else {
+            format!("{} {}", icon, title)
+        };
+        let rule = "─".repeat(self.width.saturating_sub(header.width() + 4));
+
+        out.push(self.paint(&[color], &format!("┌─ {} {}", header, rule)));
+        for line in body {
+            out.push(format!(
+                "{} {}",
+                self.paint(&[color], "│"),
+                self.inline(line.trim())
+            ));
+        }
+        out.push(self.paint(
+            &[color],
+            &format!("└{}", "─".repeat(self.width.saturating_sub(1))),
+        ));
+    }
+
+    /// Render a pipe table with aligned columns
+    fn table(&self, rows: &[&str], out: &mut Vec<String>) {
+        let is_separator = |cells: &[String]| {
+            cells
+                .iter()
+                .all(|c| !c.is_empty() && c.chars().all(|ch| matches!(ch, '-' | ':' | ' ')))
+        };
+        let parsed: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| {
+                let row = row.trim().trim_start_matches('|').trim_end_matches('|');
+                row.split('|').map(|cell| cell.trim().to_string()).collect()
+            })
+            .collect();
+
+        let rendered: Vec<(bool, Vec<String>)> = parsed
+            .iter()
+            .map(|cells| {
+                let separator = is_separator(cells);
+                let cells = cells.iter().map(|cell| self.inline(cell)).collect();
+                (separator, cells)
+            })
+            .collect();
+        let columns = rendered.iter().map(|(_, c)| c.len()).max().unwrap_or(0);
+        let mut widths = vec![0; columns];
+        for (separator, cells) in &rendered {
+            if !separator {
+                for (width, cell) in widths.iter_mut().zip(cells) {
+                    *width = (*width).max(visible_width(cell));
+                }
+            }
+        }
+
+        let border = |left: &str, middle: &str, right: &str| {
+            let segments: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+            self.paint(
+                &[ansi::DIM],
+                &format!("{}{}{}", left, segments.join(middle), right),
+            )
+        };
+        out.push(border("┌", "┬", "┐"));
+        for (index, (separator, cells)) in rendered.iter().enumerate() {
+            if *separator {
+                out.push(border("├", "┼", "┤"));
+                continue;
+            }
+            let bar = self.paint(&[ansi::DIM], "│");
+            let mut line = bar.clone();
+            for (column, width) in widths.iter().enumerate() {
+                let cell = cells.get(column).map(String::as_str).unwrap_or("");
+                let padding = " ".repeat(width - visible_width(cell));
+                let cell = if index == 0 {
+                    self.paint(&[ansi::BOLD], cell)
+                } else {
+                    cell.to_string()
+                };
+                line.push_str(&format!(" {}{} {}", cell, padding, bar));
+            }
+            out.push(line);
+        }
+        out.push(border("└", "┴", "┘"));
+    }
+
+    /// Render inline markup: code spans, bold, links, images and HTML tags
+    pub fn inline(&self, text: &str) -> String {
+        let mut out = String::new();
+        let mut rest = text;
+        let mut bold = false;
+
+        while let Some(c) = rest.chars().next() {
+            if c == '`' {
+                if let Some(end) = rest[1..].find('`') {
+                    out.push_str(&self.paint(&[ansi::YELLOW], &rest[1..1 + end]));
+                    rest = &rest[end + 2..];
+                    continue;
+                }
+            } else if rest.starts_with("**") {
+                bold = !bold;
+                out.push_str(if !self.color {
+                    ""
+                } else if bold {
+                    ansi::BOLD
+                } else {
+                    ansi::RESET
+                });
+                rest = &rest[2..];
+                continue;
+            } else if let Some((label, target, len)) = parse_link(rest) {
+                let is_image = rest.starts_with('!');
+                out.push_str(&self.link(label, target, is_image));
+                rest = &rest[len..];
+                continue;
+            } else if c == '<'
+                && let Some(end) = html_tag_len(rest)
+            {
+                if rest[..end].starts_with("<br") {
+                    out.push(' ');
+                }
+                rest = &rest[end..];
+                continue;
+            }
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        if bold && self.color {
+            out.push_str(ansi::RESET);
+        }
+        out
+    }
+
+    /// Render a link, resolving chapter links to the command that shows them
+    fn link(&self, label: &str, target: &str, is_image: bool) -> String {
+        if is_image {
+            return self.paint(&[ansi::DIM], &t!(IMAGE_PLACEHOLDER, label));
+        }
+        let label = self.paint(&[ansi::UNDERLINE, ansi::BLUE], label);
+        if target.starts_with('#') {
+            return label;
+        }
+        let resolved = match resolve_doc_link(self.doc_path, target) {
+            Some(doc) => match registry::get_all_topics().find(|t| t.doc_path == Some(&doc)) {
+                Some(topic) => format!("hello {} {}", topic.category, topic.name),
+                None => doc,
+            },
+            None => target.to_string(),
+        };
+        format!(
+            "{} {}",
+            label,
+            self.paint(&[ansi::DIM], &format!("⟨{}⟩", resolved))
+        )
+    }
+
+    /// Highlight one line of Rust code
+    fn highlight_rust(&self, line: &str) -> String {
+        if !self.color {
+            return line.to_string();
+        }
+        let chars: Vec<char> = line.chars().collect();
+        let mut out = String::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let start = i;
+            if c == '/' && chars.get(i + 1) == Some(&'/') {
+                let comment: String = chars[i..].iter().collect();
+                out.push_str(&self.paint(&[ansi::DIM, ansi::ITALIC], &comment));
+                break;
+            } else if c == '"' {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(chars.len());
+                let literal: String = chars[start..i].iter().collect();
+                out.push_str(&self.paint(&[ansi::GREEN], &literal));
+            } else if c.is_ascii_digit() {
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+                {
+                    i += 1;
+                }
+                let number: String = chars[start..i].iter().collect();
+                out.push_str(&self.paint(&[ansi::MAGENTA], &number));
+            } else if c.is_alphabetic() || c == '_' {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                let styled = if RUST_KEYWORDS.contains(&word.as_str()) {
+                    self.paint(&[ansi::BOLD, ansi::MAGENTA], &word)
+                } else if chars.get(i) == Some(&'!') {
+                    i += 1;
+                    self.paint(&[ansi::CYAN], &format!("{}!", word))
+                } else if word.starts_with(char::is_uppercase) {
+                    self.paint(&[ansi::YELLOW], &word)
+                } else if chars.get(i) == Some(&'(') {
+                    self.paint(&[ansi::BLUE], &word)
+                } else {
+                    word
+                };
+                out.push_str(&styled);
+            } else {
+                out.push(c);
+                i += 1;
+            }
+        }
+        out
+    }
+
+    /// Wrap `text` in the given ANSI styles (no-op without color)
+    fn paint(&self, styles: &[&str], text: &str) -> String {
+        if !self.color || text.is_empty() {
+            return text.to_string();
+        }
+        format!("{}{}{}", styles.concat(), text, ansi::RESET)
+    }
+}
+
+/// Extract one section of a markdown document
+///
+/// The section starts at the first heading containing `heading`
+/// (case-insensitive) and ends before the next heading of the same or a
+/// higher level. Headings inside code fences are ignored.
+pub fn extract_section(markdown: &str, heading: &str) -> Option<String> {
+    let needle = heading.to_lowercase();
+    let mut in_code = false;
+    let mut section: Option<(usize, Vec<&str>)> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+        }
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        let is_heading = !in_code && level > 0;
+
+        match &mut section {
+            Some((start_level, lines)) => {
+                if is_heading && level <= *start_level {
+                    break;
+                }
+                lines.push(line);
+            }
+            None if is_heading && trimmed[level..].to_lowercase().contains(&needle) => {
+                section = Some((level, vec![line]));
+            }
+            None => {}
+        }
+    }
+    section.map(|(_, lines)| lines.join("\n"))
+}
+
+/// List the headings of a markdown document, skipping code fences
+pub fn headings(markdown: &str) -> Vec<String> {
+    let mut in_code = false;
+    markdown
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") {
+                in_code = !in_code;
+            }
+            (!in_code && trimmed.starts_with('#'))
+                .then(|| trimmed.trim_start_matches('#').trim().to_string())
+        })
+        .collect()
+}
+
+/// Print `text`, piping it through a pager when it does not fit on screen
+///
+/// Uses `$PAGER` (default `less -R`) only when stdout is a terminal; falls
+/// back to printing directly if the pager cannot be started.
+pub fn page(text: &str) {
+    let height = env_usize("LINES").unwrap_or(DEFAULT_HEIGHT);
+    if !std::io::stdout().is_terminal() || text.lines().count() < height {
+        print!("{}", text);
+        return;
+    }
+
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        print!("{}", text);
+        return;
+    };
+
+    match Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+    {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // The pager closing early (user pressed `q`) is not an error
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            let _ = child.wait();
+        }
+        Err(_) => print!("{}", text),
+    }
+}
+
+/// Resolve a relative `.md` link against the directory of `doc_path`
+///
+/// # Returns
+/// The linked path relative to docs/src/, or `None` for external links
+fn resolve_doc_link(doc_path: &str, target: &str) -> Option<String> {
+    if target.contains("://") || target.starts_with("mailto:") {
+        return None;
+    }
+    let target = target.split('#').next()?;
+    if !target.ends_with(".md") {
+        return None;
+    }
+
+    let mut parts: Vec<&str> = doc_path.split('/').collect();
+    parts.pop();
+    for segment in target.split('/') {
+        match segment {
+            "." | "" => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(segment),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// Parse `[label](target)` or `![alt](src)` at the start of `text`
+///
+/// # Returns
+/// The label, the target and the number of bytes consumed
+fn parse_link(text: &str) -> Option<(&str, &str, usize)> {
+    let offset = usize::from(text.starts_with('!'));
+    let rest = text[offset..].strip_prefix('[')?;
+    let label_end = rest.find("](")?;
+    let target_start = label_end + 2;
+    let target_len = rest[target_start..].find(')')?;
+    let label = &rest[..label_end];
+    if label.contains('[') {
+        return None;
+    }
+    let target = &rest[target_start..target_start + target_len];
+    Some((label, target, offset + 1 + target_start + target_len + 1))
+}
+
+/// Length of an HTML tag like `<br>`, `</div>` or `<img src="...">` at the start of `text`
+fn html_tag_len(text: &str) -> Option<usize> {
+    let body = text.strip_prefix('<')?;
+    let body = body.strip_prefix('/').unwrap_or(body);
+    if !body.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    text.find('>').map(|end| end + 1)
+}
+
+/// Display width of `text`, ignoring ANSI escape sequences
+pub fn visible_width(text: &str) -> usize {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+    plain.width()
+}
+
+fn env_usize(name: &str) -> Option<usize> {
+    std::env::var(name).ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(doc_path: &str) -> Renderer<'_> {
+        Renderer {
+            color: false,
+            width: 40,
+            doc_path,
+        }
+    }
+
+    #[test]
+    fn test_inline_links_and_html() {
+        let renderer = plain("basic/ownership.md");
+        assert_eq!(
+            renderer.inline("see [变量](expression.md)<br>and `code`"),
+            "see 变量 ⟨hello basic expression⟩ and code"
+        );
+        assert_eq!(
+            renderer.inline("[Book](https://doc.rust-lang.org/book/)"),
+            "Book ⟨https://doc.rust-lang.org/book/⟩"
+        );
+        assert_eq!(
+            renderer.inline("![logo](logo.png)"),
+            t!(IMAGE_PLACEHOLDER, "logo")
+        );
+    }
+
+    #[test]
+    fn test_resolve_doc_link() {
+        assert_eq!(
+            resolve_doc_link("advance/data/json.md", "../async/tokio.md#intro"),
+            Some("advance/async/tokio.md".to_string())
+        );
+        assert_eq!(resolve_doc_link("basic/a.md", "https://x.io/a.md"), None);
+    }
+
+    #[test]
+    fn test_table_alignment_with_cjk() {
+        let text = plain("").render("| 概念 | Rust |\n| --- | --- |\n| 所有权 | move |\n");
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], "│ 概念   │ Rust │");
+        assert_eq!(lines[3], "│ 所有权 │ move │");
+        assert!(lines.iter().all(|l| l.width() == lines[0].width()));
+    }
+
+    #[test]
+    fn test_admonition_box() {
+        let text = plain("").render("```admonish important\n**重复学习。**\n```\n");
+        assert!(text.starts_with("┌─ ★ IMPORTANT"));
+        assert!(text.contains("│ 重复学习。"));
+    }
+
+    #[test]
+    fn test_extract_section() {
+        let markdown = "# T\n## A\na\n### A.1\nsub\n```\n# not heading\n```\n## B\nb";
+        assert_eq!(
+            extract_section(markdown, "a").unwrap(),
+            "## A\na\n### A.1\nsub\n```\n# not heading\n```"
+        );
+        assert!(extract_section(markdown, "missing").is_none());
+        assert_eq!(headings(markdown), vec!["T", "A", "A.1", "B"]);
+    }
+
+    #[test]
+    fn test_highlight_rust() {
+        let renderer = Renderer {
+            color: true,
+            ..plain("")
+        };
+        let line = renderer.highlight_rust("let s = String::from(\"hi\"); // move");
+        assert!(line.contains(&format!(
+            "{}{}let{}",
+            ansi::BOLD,
+            ansi::MAGENTA,
+            ansi::RESET
+        )));
+        assert!(line.contains(&format!("{}\"hi\"{}", ansi::GREEN, ansi::RESET)));
+        assert_eq!(
+            visible_width(&line),
+            "let s = String::from(\"hi\"); // move".len()
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! Batch execution of every runnable topic
+//!
+//! `hello run-all` runs each Function topic in its own child `hello` process
+//! (see [`verify::run_topic`]), so a panicking or hanging sample only fails
+//! its own entry. Server topics and topics whose services are unavailable are
+//! reported as skipped. Results are written as JUnit XML and JSON reports.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::cli::doctor::{self, Status};
+use crate::cli::error;
+use crate::cli::i18n::t;
+use crate::cli::registry::{self, Dependency, SampleType, Topic};
+use crate::cli::verify;
+
+/// Default directory the reports are written to
+pub const DEFAULT_REPORT_DIR: &str = "target/run-all";
+
+/// File name of the JUnit XML report inside the report directory
+pub const JUNIT_FILE: &str = "junit.xml";
+
+/// File name of the JSON report inside the report directory
+pub const JSON_FILE: &str = "report.json";
+
+/// Outcome of running one topic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum Outcome {
+    /// The sample exited successfully
+    Passed,
+    /// The sample panicked or exited with an error; carries the last error line
+    Failed(String),
+    /// The sample was killed after exceeding the timeout
+    TimedOut,
+    /// The sample was not run; carries the reason
+    Skipped(String),
+}
+
+/// Result of one topic, as written to the reports
+#[derive(Debug, Clone, Serialize)]
+pub struct TopicReport {
+    pub category: &'static str,
+    pub name: &'static str,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    /// Wall-clock seconds the child process ran (0 when skipped)
+    pub seconds: f64,
+    /// Masked stdout of the child
+    pub stdout: String,
+    /// Masked stderr of the child
+    pub stderr: String,
+}
+
+/// Counts of each outcome over a run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Totals {
+    pub passed: usize,
+    pub failed: usize,
+    pub timed_out: usize,
+    pub skipped: usize,
+}
+
+impl Totals {
+    /// Count the outcomes of `reports`
+    pub fn of<'a>(reports: impl IntoIterator<Item = &'a TopicReport>) -> Self {
+        reports
+            .into_iter()
+            .fold(Totals::default(), |mut totals, report| {
+                match report.outcome {
+                    Outcome::Passed => totals.passed += 1,
+                    Outcome::Failed(_) => totals.failed += 1,
+                    Outcome::TimedOut => totals.timed_out += 1,
+                    Outcome::Skipped(_) => totals.skipped += 1,
+                }
+                totals
+            })
+    }
+
+    /// Number of topics that did not pass or get skipped
+    pub fn failures(&self) -> usize {
+        self.failed + self.timed_out
+    }
+}
+
+/// Topics of `category` (or of all categories) in learning order
+pub fn topics(category: Option<&str>) -> Vec<&'static Topic> {
+    registry::learning_path()
+        .into_iter()
+        .filter(|topic| category.is_none_or(|c| topic.category == c))
+        .collect()
+}
+
+/// Probe every dependency needed by `topics` once, in `hello doctor` order
+pub fn probe_dependencies(topics: &[&Topic]) -> Vec<(Dependency, Status)> {
+    let needed: Vec<_> = Dependency::ALL
+        .into_iter()
+        .filter(|dependency| topics.iter().any(|t| t.dependencies.contains(dependency)))
+        .collect();
+    doctor::probe_all(&needed)
+}
+
+/// Reason `topic` cannot be run in a batch, if any
+///
+/// # Arguments
+/// * `topic` - The topic to check
+/// * `probes` - Probe results covering the topic's dependencies
+pub fn skip_reason(topic: &Topic, probes: &[(Dependency, Status)]) -> Option<String> {
+    if let SampleType::Server = topic.sample_type {
+        return Some(t!(SKIP_SERVER).to_string());
+    }
+    let unavailable: Vec<_> = topic
+        .dependencies
+        .iter()
+        .filter(|dependency| {
+            !probes
+                .iter()
+                .any(|(probed, status)| probed == *dependency && status.is_available())
+        })
+        .map(|dependency| dependency.name())
+        .collect();
+    if unavailable.is_empty() {
+        None
+    } else {
+        Some(t!(SKIP_DEPENDENCY_UNAVAILABLE, unavailable.join(", ")))
+    }
+}
+
+/// Run `topic` in a child process and classify the result
+pub fn run_topic(topic: &'static Topic, timeout: Duration) -> TopicReport {
+    let report = |outcome, seconds, stdout, stderr| TopicReport {
+        category: topic.category,
+        name: topic.name,
+        outcome,
+        seconds,
+        stdout,
+        stderr,
+    };
+    match verify::run_topic(topic, timeout) {
+        Ok(captured) => {
+            // The child's own exit code tells its timeout apart from a failure
+            let outcome = match captured.code {
+                _ if captured.timed_out => Outcome::TimedOut,
+                Some(0) => Outcome::Passed,
+                Some(error::EXIT_TIMED_OUT) => Outcome::TimedOut,
+                Some(error::EXIT_DEPENDENCY_UNAVAILABLE) => {
+                    Outcome::Skipped(failure_message(&captured.stderr))
+                }
+                _ => Outcome::Failed(failure_message(&captured.stderr)),
+            };
+            report(
+                outcome,
+                captured.elapsed.as_secs_f64(),
+                captured.stdout,
+                captured.stderr,
+            )
+        }
+        Err(e) => report(
+            Outcome::Failed(format!("{:#}", e)),
+            0.0,
+            String::new(),
+            String::new(),
+        ),
+    }
+}
+
+/// A skipped entry for `topic`
+pub fn skipped(topic: &'static Topic, reason: String) -> TopicReport {
+    TopicReport {
+        category: topic.category,
+        name: topic.name,
+        outcome: Outcome::Skipped(reason),
+        seconds: 0.0,
+        stdout: String::new(),
+        stderr: String::new(),
+    }
+}
+
+/// Panic or error message of a failed child, taken from its stderr
+///
+/// The line after `panicked at` when the sample panicked, else the first
+/// cause of a rendered error chain (see [`TopicError::render`]), else the
+/// last line that is not a note.
+///
+/// [`TopicError::render`]: crate::cli::error::TopicError::render
+pub fn failure_message(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let after = |marker: &str| {
+        lines
+            .iter()
+            .position(|line| line.contains(marker))
+            .and_then(|i| lines.get(i + 1))
+            .copied()
+    };
+    after("panicked at")
+        .or_else(|| after("Caused by:").map(|cause| cause.strip_prefix("0: ").unwrap_or(cause)))
+        .or_else(|| {
+            lines
+                .iter()
+                .rfind(|line| !line.starts_with("note:"))
+                .copied()
+        })
+        .unwrap_or("exited with a non-zero status")
+        .to_string()
+}
+
+/// Render `reports` as a JUnit XML document, one `<testsuite>` per category
+pub fn to_junit(reports: &[TopicReport]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let totals = Totals::of(reports);
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"hello run-all\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+        reports.len(),
+        totals.failures(),
+        totals.skipped,
+        reports.iter().map(|r| r.seconds).sum::<f64>()
+    );
+
+    let mut categories: Vec<&str> = Vec::new();
+    for report in reports {
+        if !categories.contains(&report.category) {
+            categories.push(report.category);
+        }
+    }
+    for category in categories {
+        let suite: Vec<_> = reports.iter().filter(|r| r.category == category).collect();
+        let totals = Totals::of(suite.iter().copied());
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+            escape_xml(category),
+            suite.len(),
+            totals.failures(),
+            totals.skipped,
+            suite.iter().map(|r| r.seconds).sum::<f64>()
+        );
+        for report in suite {
+            let _ = writeln!(
+                xml,
+                "    <testcase classname=\"hello.{}\" name=\"{}\" time=\"{:.3}\">",
+                escape_xml(report.category),
+                escape_xml(report.name),
+                report.seconds
+            );
+            match &report.outcome {
+                Outcome::Passed => {}
+                Outcome::Failed(message) => {
+                    let _ = writeln!(
+                        xml,
+                        "      <failure message=\"{}\" type=\"failed\"/>",
+                        escape_xml(message)
+                    );
+                }
+                Outcome::TimedOut => {
+                    let _ = writeln!(
+                        xml,
+                        "      <failure message=\"timed out\" type=\"timeout\"/>"
+                    );
+                }
+                Outcome::Skipped(reason) => {
+                    let _ = writeln!(xml, "      <skipped message=\"{}\"/>", escape_xml(reason));
+                }
+            }
+            for (tag, text) in [
+                ("system-out", &report.stdout),
+                ("system-err", &report.stderr),
+            ] {
+                if !text.is_empty() {
+                    let _ = writeln!(xml, "      <{tag}>{}</{tag}>", escape_xml(text));
+                }
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Render `reports` as a JSON document with totals
+pub fn to_json(reports: &[TopicReport]) -> String {
+    let document = serde_json::json!({
+        "totals": Totals::of(reports),
+        "topics": reports,
+    });
+    serde_json::to_string_pretty(&document).expect("run-all reports serialize")
+}
+
+/// Write the JUnit and JSON reports into `dir`, returning their paths
+pub fn write_reports(dir: &Path, reports: &[TopicReport]) -> Result<(PathBuf, PathBuf)> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let junit = dir.join(JUNIT_FILE);
+    let json = dir.join(JSON_FILE);
+    fs::write(&junit, to_junit(reports))
+        .with_context(|| format!("Failed to write {}", junit.display()))?;
+    fs::write(&json, to_json(reports))
+        .with_context(|| format!("Failed to write {}", json.display()))?;
+    Ok((junit, json))
+}
+
+/// Escape text for XML attributes and element content
+///
+/// Control characters other than tab and newline are not allowed in XML 1.0
+/// and are dropped.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(category: &'static str, name: &'static str, outcome: Outcome) -> TopicReport {
+        TopicReport {
+            category,
+            name,
+            outcome,
+            seconds: 0.5,
+            stdout: "a < b\n".to_string(),
+            stderr: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_failure_message_takes_panic_text() {
+        let stderr = "\nthread 'main' panicked at src/leetcode/two_sum.rs:12:9:\n\
+                      index out of bounds: the len is 1 but the index is 1\n\
+                      stack backtrace:\n   0: __rustc::rust_begin_unwind\n\
+                      note: Some details are omitted\n";
+        assert_eq!(
+            failure_message(stderr),
+            "index out of bounds: the len is 1 but the index is 1"
+        );
+        assert_eq!(
+            failure_message("Error: 'advance/ollama' timed out\n"),
+            "Error: 'advance/ollama' timed out"
+        );
+        assert_eq!(
+            failure_message(
+                "Error: sample 'advance/dotenv' failed\n\nCaused by:\n    \
+                 0: CARGO_MANIFEST_DIR is only set when run through cargo\n    \
+                 1: environment variable not found\n"
+            ),
+            "CARGO_MANIFEST_DIR is only set when run through cargo"
+        );
+        assert_eq!(failure_message(""), "exited with a non-zero status");
+    }
+
+    #[test]
+    fn test_skip_reason() {
+        let sqlx = registry::get_topic("advance", "sqlx").unwrap();
+        let down = vec![(Dependency::Sqlite, Status::Unavailable("gone".into()))];
+        let up = vec![(Dependency::Sqlite, Status::Available("ok".into()))];
+        assert_eq!(
+            skip_reason(sqlx, &down).as_deref(),
+            Some(t!(SKIP_DEPENDENCY_UNAVAILABLE, "SQLite").as_str())
+        );
+        assert_eq!(skip_reason(sqlx, &up), None);
+
+        let server = registry::get_topic("advance", "tokio-server").unwrap();
+        assert_eq!(skip_reason(server, &up).as_deref(), Some(t!(SKIP_SERVER)));
+    }
+
+    #[test]
+    fn test_junit_report() {
+        let reports = vec![
+            report("basic", "closure", Outcome::Passed),
+            report("basic", "panic", Outcome::Failed("boom & \"bang\"".into())),
+            report(
+                "advance",
+                "sqlx",
+                Outcome::Skipped("dependency unavailable".into()),
+            ),
+        ];
+        let xml = to_junit(&reports);
+        assert!(xml.contains(
+            "<testsuites name=\"hello run-all\" tests=\"3\" failures=\"1\" skipped=\"1\""
+        ));
+        assert!(xml.contains("<testsuite name=\"basic\" tests=\"2\" failures=\"1\" skipped=\"0\""));
+        assert!(xml.contains("<failure message=\"boom &amp; &quot;bang&quot;\" type=\"failed\"/>"));
+        assert!(xml.contains("<skipped message=\"dependency unavailable\"/>"));
+        assert!(xml.contains("<system-out>a &lt; b\n</system-out>"));
+    }
+
+    #[test]
+    fn test_json_report() {
+        let reports = vec![
+            report("basic", "closure", Outcome::Passed),
+            report("basic", "spin", Outcome::TimedOut),
+        ];
+        let json: serde_json::Value = serde_json::from_str(&to_json(&reports)).unwrap();
+        assert_eq!(json["totals"]["passed"], 1);
+        assert_eq!(json["totals"]["timed_out"], 1);
+        assert_eq!(json["topics"][0]["status"], "passed");
+        assert_eq!(json["topics"][1]["status"], "timed_out");
+        assert_eq!(json["topics"][0]["category"], "basic");
+    }
+}
@@ -0,0 +1,99 @@
+//! Shared tokio runtime for async topics
+//!
+//! Async topics (registered with [`Topic::new_async`](crate::cli::registry::Topic::new_async))
+//! never build a runtime of their own: the dispatcher runs their future on one
+//! multi-thread runtime, created on first use, racing it against the topic's
+//! timeout and Ctrl-C. Dropping the future on either cancels the sample.
+
+use std::future::{self, Future};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use tokio::runtime::{Builder, Runtime};
+
+/// Environment variable overriding the number of worker threads
+pub const WORKER_THREADS_ENV: &str = "HELLO_WORKER_THREADS";
+
+/// How an async topic ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Completion {
+    /// The sample's future finished
+    Finished,
+    /// The timeout elapsed first; carries the timeout
+    TimedOut(Duration),
+    /// Ctrl-C was pressed first
+    Cancelled,
+}
+
+/// Get the shared runtime, building it on first use
+///
+/// # Panics
+/// If the runtime cannot be built (e.g. the OS refuses to spawn threads)
+pub fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        let mut builder = Builder::new_multi_thread();
+        builder.enable_all().thread_name("hello-sample");
+        if let Some(threads) = std::env::var(WORKER_THREADS_ENV)
+            .ok()
+            .and_then(|value| value.parse::<usize>().ok())
+            .filter(|&threads| threads > 0)
+        {
+            builder.worker_threads(threads);
+        }
+        builder.build().expect("Failed to build the tokio runtime")
+    })
+}
+
+/// Run an async topic until it finishes, `timeout` elapses or Ctrl-C is pressed
+///
+/// # Errors
+/// The error the sample's future finished with
+pub fn run<F>(future: F, timeout: Option<Duration>) -> anyhow::Result<Completion>
+where
+    F: Future<Output = anyhow::Result<()>>,
+{
+    runtime().block_on(async move {
+        let deadline = async {
+            match timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => future::pending().await,
+            }
+        };
+        tokio::select! {
+            result = future => result.map(|()| Completion::Finished),
+            () = deadline => Ok(Completion::TimedOut(timeout.unwrap_or_default())),
+            _ = tokio::signal::ctrl_c() => Ok(Completion::Cancelled),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_finishes() {
+        let completion = run(
+            async {
+                tokio::task::yield_now().await;
+                Ok(())
+            },
+            None,
+        );
+        assert_eq!(completion.unwrap(), Completion::Finished);
+    }
+
+    #[test]
+    fn test_run_times_out() {
+        let timeout = Duration::from_millis(50);
+        let completion = run(future::pending(), Some(timeout));
+        assert_eq!(completion.unwrap(), Completion::TimedOut(timeout));
+    }
+
+    #[test]
+    fn test_run_returns_sample_error() {
+        let completion = run(async { Err(anyhow::anyhow!("connection refused")) }, None);
+        assert_eq!(completion.unwrap_err().to_string(), "connection refused");
+    }
+}
@@ -0,0 +1,286 @@
+//! Full-text search across topics and embedded tutorials
+//!
+//! Ranks matches from `Topic.name`, `Topic.description` and the markdown
+//! sections in `docs_embed::DOCS_DIR`, and suggests close topic names on typos.
+
+use crate::cli::docs_embed;
+use crate::cli::registry::{self, Topic};
+
+/// Score for a topic whose name equals a query term
+const SCORE_NAME_EXACT: u32 = 100;
+/// Score for a topic whose name contains a query term
+const SCORE_NAME_PARTIAL: u32 = 50;
+/// Score for a topic whose description contains a query term
+const SCORE_DESCRIPTION: u32 = 30;
+/// Score for a tutorial section whose heading contains a query term
+const SCORE_HEADING: u32 = 20;
+/// Score per occurrence of a query term in a tutorial section body
+const SCORE_BODY: u32 = 5;
+/// Maximum number of body occurrences counted per term
+const MAX_BODY_HITS: u32 = 4;
+/// Number of characters kept on each side of a match in a snippet
+const SNIPPET_RADIUS: usize = 30;
+
+/// Where a search hit was found
+#[derive(Debug, Clone)]
+pub enum HitSource {
+    /// A registered topic matched by name or description
+    Topic(&'static Topic),
+    /// A section of an embedded tutorial
+    Doc {
+        /// Path relative to docs/src/ (e.g. "basic/threads.md")
+        path: &'static str,
+        /// Heading the matching text falls under
+        heading: String,
+        /// Matching line, shortened around the first match
+        snippet: String,
+    },
+}
+
+/// A single ranked search result
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    /// Relevance score (higher is better)
+    pub score: u32,
+    /// Matched topic or tutorial section
+    pub source: HitSource,
+}
+
+/// A markdown section: a heading and the lines up to the next heading
+struct Section {
+    heading: String,
+    lines: Vec<&'static str>,
+}
+
+/// Search topics and tutorials for every whitespace-separated term in `query`
+///
+/// Matching is case-insensitive substring matching, so both "arc" and
+/// CJK terms like "借用" work. A result must match all terms.
+///
+/// # Returns
+/// Hits sorted by descending score, topics before tutorials on ties
+pub fn search(query: &str) -> Vec<SearchHit> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<SearchHit> = registry::get_all_topics()
+        .filter_map(|topic| {
+            score_topic(topic, &terms).map(|score| SearchHit {
+                score,
+                source: HitSource::Topic(topic),
+            })
+        })
+        .collect();
+
+    for file in docs_embed::all_markdown_files() {
+        let (Some(path), Some(content)) = (file.path().to_str(), file.contents_utf8()) else {
+            continue;
+        };
+        for section in split_sections(content) {
+            if let Some(score) = score_section(&section, &terms) {
+                hits.push(SearchHit {
+                    score,
+                    source: HitSource::Doc {
+                        path,
+                        snippet: snippet(&section, &terms[0]),
+                        heading: section.heading,
+                    },
+                });
+            }
+        }
+    }
+
+    hits.sort_by(|a, b| {
+        b.score.cmp(&a.score).then_with(|| {
+            let rank = |hit: &SearchHit| matches!(hit.source, HitSource::Doc { .. });
+            rank(a).cmp(&rank(b))
+        })
+    });
+    hits
+}
+
+/// Suggest candidates within a small edit distance of `word`
+///
+/// The allowed distance grows with the word length (one edit per three
+/// characters, at least one), so short names need a near-exact match.
+///
+/// # Returns
+/// Candidates sorted by distance, closest first
+pub fn suggest<'a>(word: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let word = word.to_lowercase();
+    let max_distance = (word.chars().count() / 3).max(1);
+
+    let mut close: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|candidate| (levenshtein(&word, &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    close.sort();
+    close.dedup();
+    close.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+/// Edit distance between two strings, counted in characters
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+fn score_topic(topic: &Topic, terms: &[String]) -> Option<u32> {
+    let name = topic.name.to_lowercase();
+    let description = format!(
+        "{} {}",
+        topic.description,
+        topic.description_en.unwrap_or_default()
+    )
+    .to_lowercase();
+
+    terms.iter().try_fold(0, |total, term| {
+        let mut score = 0;
+        if name == *term {
+            score += SCORE_NAME_EXACT;
+        } else if name.contains(term.as_str()) {
+            score += SCORE_NAME_PARTIAL;
+        }
+        if description.contains(term.as_str()) {
+            score += SCORE_DESCRIPTION;
+        }
+        (score > 0).then_some(total + score)
+    })
+}
+
+fn score_section(section: &Section, terms: &[String]) -> Option<u32> {
+    let heading = section.heading.to_lowercase();
+    let body = section.lines.join("\n").to_lowercase();
+
+    terms.iter().try_fold(0, |total, term| {
+        let mut score = 0;
+        if heading.contains(term.as_str()) {
+            score += SCORE_HEADING;
+        }
+        let occurrences = body.matches(term.as_str()).count() as u32;
+        score += occurrences.min(MAX_BODY_HITS) * SCORE_BODY;
+        (score > 0).then_some(total + score)
+    })
+}
+
+/// Split markdown into sections at headings, ignoring `#` inside code fences
+fn split_sections(content: &'static str) -> Vec<Section> {
+    let mut sections = vec![Section {
+        heading: String::new(),
+        lines: Vec::new(),
+    }];
+    let mut in_code = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+        }
+        if !in_code && trimmed.starts_with('#') {
+            sections.push(Section {
+                heading: trimmed.trim_start_matches('#').trim().to_string(),
+                lines: Vec::new(),
+            });
+        } else if let Some(section) = sections.last_mut() {
+            section.lines.push(line);
+        }
+    }
+
+    sections.retain(|section| !section.heading.is_empty() || !section.lines.is_empty());
+    sections
+}
+
+/// Shorten the first line containing `term` to a window around the match
+fn snippet(section: &Section, term: &str) -> String {
+    let Some(line) = section
+        .lines
+        .iter()
+        .map(|line| line.trim())
+        .find(|line| line.to_lowercase().contains(term))
+    else {
+        return section.heading.clone();
+    };
+
+    let chars: Vec<char> = line.chars().collect();
+    let lower: Vec<char> = line.to_lowercase().chars().collect();
+    let term: Vec<char> = term.chars().collect();
+    let position = lower
+        .windows(term.len())
+        .position(|window| window == term.as_slice())
+        .unwrap_or(0)
+        .min(chars.len());
+
+    let start = position.saturating_sub(SNIPPET_RADIUS);
+    let end = (position + term.len() + SNIPPET_RADIUS).min(chars.len());
+    let mut text: String = chars[start..end].iter().collect();
+    if start > 0 {
+        text.insert(0, '…');
+    }
+    if end < chars.len() {
+        text.push('…');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("ownership", "ownership"), 0);
+        assert_eq!(levenshtein("ownrship", "ownership"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("借用", "借"), 1);
+        assert_eq!(levenshtein("", "arc"), 3);
+    }
+
+    #[test]
+    fn test_suggest_typo() {
+        let names = ["ownership", "threads", "traits", "tracing"];
+        assert_eq!(suggest("ownrship", names), vec!["ownership"]);
+        assert_eq!(suggest("trait", names), vec!["traits"]);
+        assert!(suggest("tokio", names).is_empty());
+    }
+
+    #[test]
+    fn test_split_sections_ignores_code_fences() {
+        let sections =
+            split_sections("# Title\nintro\n```rust\n# not a heading\n```\n## Next\nbody");
+        let headings: Vec<_> = sections.iter().map(|s| s.heading.as_str()).collect();
+        assert_eq!(headings, vec!["Title", "Next"]);
+        assert_eq!(sections[0].lines.len(), 4);
+    }
+
+    #[test]
+    fn test_search_topic_description() {
+        let hits = search("Arc");
+        assert!(hits.iter().any(|hit| matches!(
+            hit.source,
+            HitSource::Topic(topic) if topic.name == "threads"
+        )));
+    }
+
+    #[test]
+    fn test_search_tutorial_cjk() {
+        let hits = search("借用");
+        assert!(hits.iter().any(|hit| matches!(
+            &hit.source,
+            HitSource::Doc { snippet, .. } if snippet.contains("借用")
+        )));
+    }
+}
@@ -0,0 +1,302 @@
+//! Background lifecycle of Server topics
+//!
+//! `hello serve` starts a server topic in a detached child `hello` process,
+//! with stdout/stderr redirected to a log file. A JSON record with the PID,
+//! port and log file is kept under `<state dir>/servers/` so that `hello ps`
+//! and `hello stop` can find the process again from another terminal.
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::net::{SocketAddr, TcpStream};
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+use nix::sys::signal::{self, Signal};
+use nix::unistd::Pid;
+use serde::{Deserialize, Serialize};
+
+use crate::cli::registry::{ArgValues, SampleType, Topic};
+use crate::cli::state;
+
+/// Directory name of the server records inside the state directory
+const SERVERS_DIR: &str = "servers";
+
+/// Poll interval while waiting for a port or a process
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Timeout of a single readiness connection attempt
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Number of bytes shown from the end of the log when a server fails
+const LOG_TAIL_BYTES: u64 = 2048;
+
+/// A server topic running in the background
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerRecord {
+    /// Topic key ("category/name")
+    pub topic: String,
+
+    /// PID of the child `hello` process
+    pub pid: u32,
+
+    /// Local port the server listens on
+    pub port: Option<u16>,
+
+    /// File receiving the server's stdout and stderr
+    pub log_file: PathBuf,
+
+    /// When the server was started
+    pub started: DateTime<Local>,
+}
+
+impl ServerRecord {
+    /// Check whether the recorded process is still alive
+    pub fn is_alive(&self) -> bool {
+        is_alive(self.pid)
+    }
+
+    /// Check whether this record belongs to `topic` ("name" or "category/name")
+    pub fn matches(&self, topic: &str) -> bool {
+        self.topic == topic
+            || self
+                .topic
+                .split_once('/')
+                .is_some_and(|(_, name)| name == topic)
+    }
+
+    /// Remove the record file (the log file is kept)
+    pub fn remove(&self) -> Result<()> {
+        let path = record_path(&self.topic);
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = record_path(&self.topic);
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Directory holding the server records and logs
+pub fn servers_dir() -> PathBuf {
+    state::state_dir().join(SERVERS_DIR)
+}
+
+/// Get all server records, including those whose process has exited
+///
+/// Unreadable record files are skipped.
+pub fn records() -> Vec<ServerRecord> {
+    let Ok(entries) = fs::read_dir(servers_dir()) else {
+        return Vec::new();
+    };
+    let mut records: Vec<ServerRecord> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+    records.sort_by(|a, b| a.topic.cmp(&b.topic));
+    records
+}
+
+/// Get the live record of `topic`, if its server is running
+pub fn running(topic: &Topic) -> Option<ServerRecord> {
+    let key = topic.key();
+    records()
+        .into_iter()
+        .find(|record| record.topic == key && record.is_alive())
+}
+
+/// Start `topic` in a background `hello` process and wait until it is ready
+///
+/// The process gets the options in `values`. The server counts as ready once
+/// the port it listens on with them accepts connections (or right away if the
+/// topic declares no port). If the process exits or `timeout` elapses first,
+/// it is killed and the end of its log is included in the error.
+pub fn start(topic: &Topic, values: &ArgValues, timeout: Duration) -> Result<ServerRecord> {
+    if !matches!(topic.sample_type, SampleType::Server) {
+        bail!(
+            "'{}' is not a server topic; run it with `hello {} {}`",
+            topic.key(),
+            topic.category,
+            topic.name
+        );
+    }
+    let port = topic.listen_port(values);
+    if let Some(port) = port
+        && port_open(port)
+    {
+        bail!("port {} is already in use by another process", port);
+    }
+
+    let dir = servers_dir();
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let log_file = dir.join(format!("{}-{}.log", topic.category, topic.name));
+    let log = File::create(&log_file)
+        .with_context(|| format!("Failed to create {}", log_file.display()))?;
+
+    let exe = std::env::current_exe().context("Failed to locate the hello executable")?;
+    let options = topic
+        .args
+        .iter()
+        .flat_map(|arg| [format!("--{}", arg.name), values.text(arg.name).to_string()]);
+    let mut child = Command::new(exe)
+        .args([topic.category, topic.name, "--no-docs", "--force"])
+        .args(options)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        // Own process group, so Ctrl-C in this terminal does not reach it
+        .process_group(0)
+        .spawn()
+        .with_context(|| format!("Failed to start server '{}'", topic.key()))?;
+
+    let record = ServerRecord {
+        topic: topic.key(),
+        pid: child.id(),
+        port,
+        log_file,
+        started: Local::now(),
+    };
+    record.save()?;
+
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            let _ = record.remove();
+            bail!(
+                "server exited during startup ({})\n{}",
+                status,
+                log_tail(&record.log_file)
+            );
+        }
+        if record.port.is_none_or(port_open) {
+            return Ok(record);
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            let _ = record.remove();
+            bail!(
+                "port {} not ready after {:?}\n{}",
+                record.port.unwrap_or_default(),
+                timeout,
+                log_tail(&record.log_file)
+            );
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Stop a background server: SIGTERM first, SIGKILL once `grace` elapses
+///
+/// # Returns
+/// `true` if the process had to be killed
+pub fn stop(record: &ServerRecord, grace: Duration) -> Result<bool> {
+    let mut killed = false;
+    if record.is_alive() {
+        let pid = Pid::from_raw(record.pid as i32);
+        signal::kill(pid, Signal::SIGTERM)
+            .with_context(|| format!("Failed to signal process {}", record.pid))?;
+
+        let started = Instant::now();
+        while record.is_alive() && started.elapsed() < grace {
+            thread::sleep(POLL_INTERVAL);
+        }
+        if record.is_alive() {
+            signal::kill(pid, Signal::SIGKILL)
+                .with_context(|| format!("Failed to kill process {}", record.pid))?;
+            killed = true;
+        }
+    }
+    record.remove()?;
+    Ok(killed)
+}
+
+/// Check whether a process with `pid` exists
+fn is_alive(pid: u32) -> bool {
+    signal::kill(Pid::from_raw(pid as i32), None).is_ok()
+}
+
+/// Check whether something accepts connections on local `port`
+fn port_open(port: u16) -> bool {
+    let address = SocketAddr::from(([127, 0, 0, 1], port));
+    TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).is_ok()
+}
+
+/// Path of the record file of the topic with key `key`
+fn record_path(key: &str) -> PathBuf {
+    servers_dir().join(format!("{}.json", key.replace('/', "-")))
+}
+
+/// Read the last few lines of a log file for error messages
+fn log_tail(path: &Path) -> String {
+    let mut content = String::new();
+    if let Ok(mut file) = File::open(path) {
+        let length = file.metadata().map_or(0, |m| m.len());
+        let _ = file.seek(SeekFrom::Start(length.saturating_sub(LOG_TAIL_BYTES)));
+        let mut buffer = Vec::new();
+        let _ = file.read_to_end(&mut buffer);
+        content = String::from_utf8_lossy(&buffer).into_owned();
+    }
+    if content.trim().is_empty() {
+        format!("(no output in {})", path.display())
+    } else {
+        format!("--- {} ---\n{}", path.display(), content.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::registry;
+
+    fn record(topic: &str) -> ServerRecord {
+        ServerRecord {
+            topic: topic.to_string(),
+            pid: std::process::id(),
+            port: Some(8080),
+            log_file: PathBuf::from("server.log"),
+            started: Local::now(),
+        }
+    }
+
+    #[test]
+    fn test_record_matches_name_or_key() {
+        let record = record("advance/tokio-server");
+        assert!(record.matches("tokio-server"));
+        assert!(record.matches("advance/tokio-server"));
+        assert!(!record.matches("tokio"));
+        assert!(!record.matches("awesome/tokio-server"));
+    }
+
+    #[test]
+    fn test_current_process_is_alive() {
+        assert!(record("advance/tokio-server").is_alive());
+    }
+
+    #[test]
+    fn test_start_rejects_function_topic() {
+        let topic = registry::get_topic("basic", "ownership").unwrap();
+        let values = topic.resolve_args(&[]).unwrap();
+        let error = start(topic, &values, Duration::from_secs(1)).unwrap_err();
+        assert!(error.to_string().contains("not a server topic"));
+    }
+
+    #[test]
+    fn test_server_topics_declare_ports() {
+        let servers: Vec<_> = registry::get_all_topics()
+            .filter(|t| matches!(t.sample_type, SampleType::Server))
+            .collect();
+        assert!(!servers.is_empty());
+        for topic in servers {
+            assert!(topic.port.is_some(), "'{}' has no port", topic.key());
+        }
+    }
+}
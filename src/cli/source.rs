@@ -0,0 +1,676 @@
+//! Embedded sample sources for `hello source`
+//!
+//! The sources of the samples (this crate's `src/` and the `awesome` and
+//! `leetcode` crates) are compiled into the binary like the tutorials. A
+//! topic records the file and function it runs ([`SampleSource`]); the span
+//! of the function is found by scanning the file, so it follows edits.
+//!
+//! Exporting writes `<dir>/<category>-<name>/` as a standalone cargo project:
+//! the sample's module and the modules it reaches through `crate::` paths,
+//! keeping their module paths, a `main` calling the sample, and the
+//! dependencies the copied code uses, taken from its crate's manifest.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use include_dir::{include_dir, Dir};
+use regex::Regex;
+
+use crate::cli::registry::{SampleSource, Topic};
+
+/// A crate whose sources are embedded
+struct SourceCrate {
+    /// Directory of the sources relative to the repository root
+    root: &'static str,
+    files: Dir<'static>,
+    /// The crate's Cargo.toml, for the dependencies of exported projects
+    manifest: &'static str,
+}
+
+/// Every crate holding samples
+const CRATES: &[SourceCrate] = &[
+    SourceCrate {
+        root: "src",
+        files: include_dir!("src"),
+        manifest: include_str!("../../Cargo.toml"),
+    },
+    SourceCrate {
+        root: "crates/awesome/src",
+        files: include_dir!("crates/awesome/src"),
+        manifest: include_str!("../../crates/awesome/Cargo.toml"),
+    },
+    SourceCrate {
+        root: "crates/leetcode/src",
+        files: include_dir!("crates/leetcode/src"),
+        manifest: include_str!("../../crates/leetcode/Cargo.toml"),
+    },
+];
+
+/// Module of the CLI itself, which exported projects cannot take along
+const CLI_MODULE: &str = "cli";
+
+/// Lines of a function in its file, 1-based and inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A sample function found in the embedded sources
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub span: Span,
+    /// Code of the function, with its doc comments and attributes
+    pub code: String,
+}
+
+/// Result of exporting a topic's sample
+#[derive(Debug, Clone)]
+pub struct Export {
+    /// Directory of the project written
+    pub dir: PathBuf,
+    /// Names of the dependencies added to Cargo.toml
+    pub dependencies: Vec<String>,
+    /// Dependencies on crates of this repository, left out of Cargo.toml
+    pub local_dependencies: Vec<String>,
+    /// `crate::` paths that could not be copied (e.g. into the CLI)
+    pub unresolved: Vec<String>,
+    /// The sample takes arguments or is a method, so `main` must be completed
+    pub needs_arguments: bool,
+}
+
+/// Get an embedded source file
+///
+/// # Arguments
+/// * `path` - Path relative to the repository root (e.g. "src/basic/ownership_sample.rs")
+pub fn get_file(path: &str) -> Option<&'static str> {
+    let (source_crate, relative) = find_crate(path)?;
+    source_crate.files.get_file(relative)?.contents_utf8()
+}
+
+/// Find the function a topic runs in the embedded sources
+pub fn find_function(sample: &SampleSource) -> Option<Function> {
+    let file = get_file(sample.file)?;
+    let span = function_span(file, sample.function)?;
+    let code = file
+        .lines()
+        .skip(span.start - 1)
+        .take(span.end - span.start + 1)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(Function { span, code })
+}
+
+/// Find the lines of the function `name` in `code`
+///
+/// A free function is preferred over a method or a function of a nested
+/// module with the same name. The span includes the doc comments and
+/// attributes above the function.
+pub fn function_span(code: &str, name: &str) -> Option<Span> {
+    let pattern = Regex::new(&format!(
+        r"^\s*(pub(\([^)]*\))?\s+)?(const\s+)?(async\s+)?(unsafe\s+)?fn\s+{}\b",
+        regex::escape(name)
+    ))
+    .ok()?;
+    let lines: Vec<&str> = code.lines().collect();
+    let found: Vec<usize> = (0..lines.len())
+        .filter(|&i| pattern.is_match(lines[i]))
+        .collect();
+    let start = found
+        .iter()
+        .copied()
+        .find(|&i| !lines[i].starts_with(char::is_whitespace))
+        .or(found.first().copied())?;
+    let end = start + body_end(&lines[start..].join("\n"))?;
+
+    let mut first = start;
+    while first > 0 {
+        let above = lines[first - 1].trim_start();
+        if !above.starts_with("///") && !above.starts_with("#[") {
+            break;
+        }
+        first -= 1;
+    }
+    Some(Span {
+        start: first + 1,
+        end: end + 1,
+    })
+}
+
+/// Line (counted from 0) closing the first brace block of `code`
+///
+/// Braces in comments, string and char literals are skipped.
+fn body_end(code: &str) -> Option<usize> {
+    let chars: Vec<char> = code.chars().collect();
+    let mut depth = 0usize;
+    let mut line = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\n' => line += 1,
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i + 1 < chars.len() && chars[i + 1] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                    line += usize::from(chars[i] == '\n');
+                    i += 1;
+                }
+                i += 1;
+            }
+            'r' if i == 0 || !(chars[i - 1].is_alphanumeric() || chars[i - 1] == '_') => {
+                // Raw string: r"..." or r#"..."#
+                let hashes = chars[i + 1..].iter().take_while(|&&c| c == '#').count();
+                if chars.get(i + 1 + hashes) == Some(&'"') {
+                    let close: Vec<char> = std::iter::once('"')
+                        .chain(std::iter::repeat_n('#', hashes))
+                        .collect();
+                    i += 2 + hashes;
+                    while i < chars.len() && !chars[i..].starts_with(&close) {
+                        line += usize::from(chars[i] == '\n');
+                        i += 1;
+                    }
+                    i += close.len() - 1;
+                }
+            }
+            '"' => {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    line += usize::from(chars.get(i) == Some(&'\n'));
+                    i += 1;
+                }
+            }
+            // Char literal ('{', '\''); a lifetime has no closing quote
+            '\'' if chars.get(i + 1) == Some(&'\\') => {
+                i += 2;
+                while i < chars.len() && chars[i] != '\'' {
+                    i += 1;
+                }
+            }
+            '\'' if chars.get(i + 2) == Some(&'\'') => i += 2,
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(line);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Write the sample of `topic` as a standalone cargo project under `root`
+///
+/// # Returns
+/// What was written, and what the learner has to complete by hand
+pub fn export(topic: &Topic, root: &Path) -> Result<Export> {
+    let Some(sample) = topic.sample_source else {
+        bail!("topic {} has no recorded sample source", topic.key());
+    };
+    let (source_crate, relative) = find_crate(sample.file)
+        .with_context(|| format!("{} is not an embedded source", sample.file))?;
+    let function = find_function(&sample)
+        .with_context(|| format!("function {} not found in {}", sample.function, sample.file))?;
+    let dir = root.join(format!("{}-{}", topic.category, topic.name));
+    if dir.exists() {
+        bail!(
+            "{} already exists; remove it or export elsewhere",
+            dir.display()
+        );
+    }
+
+    let mut files = BTreeSet::new();
+    let mut unresolved = BTreeSet::new();
+    collect_modules(
+        source_crate,
+        relative,
+        relative,
+        &mut files,
+        &mut unresolved,
+    );
+    let code: Vec<&str> = files
+        .iter()
+        .filter_map(|path| source_crate.files.get_file(path)?.contents_utf8())
+        .collect();
+
+    // Dependencies: every crate of the manifest the copied code names
+    let path_pattern =
+        Regex::new(r"\buse\s+([a-z_][a-z0-9_]*)\s*;|\b([a-z_][a-z0-9_]*)::").expect("valid regex");
+    let macro_pattern = Regex::new(r"(?:^|[^:\w])([a-z_][a-z0-9_]*)!").expect("valid regex");
+    let named: BTreeSet<&str> = code
+        .iter()
+        .flat_map(|code| path_pattern.captures_iter(code))
+        .filter_map(|captures| captures.get(1).or(captures.get(2)))
+        .map(|name| name.as_str())
+        .collect();
+    let macros: BTreeSet<&str> = code
+        .iter()
+        .flat_map(|code| macro_pattern.captures_iter(code))
+        .filter_map(|captures| captures.get(1))
+        .map(|name| name.as_str())
+        .collect();
+
+    let signature = signature(&function.code, sample.function);
+    let is_async = signature.contains("async ");
+    let mut dependencies = BTreeMap::new();
+    let mut local_dependencies = Vec::new();
+    let mut macro_crates = Vec::new();
+    for (name, entry) in manifest_dependencies(source_crate.manifest) {
+        let ident = name.replace('-', "_");
+        if !named.contains(ident.as_str()) && !macros.contains(ident.as_str()) {
+            continue;
+        }
+        if entry.contains("path =") {
+            local_dependencies.push(name);
+            continue;
+        }
+        if macros.contains(ident.as_str()) {
+            macro_crates.push(ident);
+        }
+        dependencies.insert(name, entry);
+    }
+    if is_async {
+        // `#[tokio::main]` needs the runtime and macros features
+        dependencies.insert(
+            "tokio".to_string(),
+            r#"tokio = { version = "1", features = ["full"] }"#.to_string(),
+        );
+    }
+
+    // `main` calls the sample through its module path
+    let module = relative
+        .trim_end_matches(".rs")
+        .trim_end_matches("/mod")
+        .replace('/', "::");
+    let top_level = function
+        .code
+        .lines()
+        .any(|line| line.starts_with(|c: char| !c.is_whitespace()) && line.contains("fn "));
+    let needs_arguments = !top_level || takes_arguments(&signature, sample.function);
+    let call = format!("{}::{}()", module, sample.function);
+    let main = main_rs(
+        topic,
+        sample.file,
+        &files,
+        &macro_crates,
+        MainCall {
+            call: (!needs_arguments).then_some(call.as_str()),
+            function: sample.function,
+            is_async,
+            returns_result: signature
+                .split_once("->")
+                .is_some_and(|(_, returns)| returns.contains("Result")),
+        },
+    );
+
+    fs::create_dir_all(dir.join("src"))?;
+    let mut manifest = format!(
+        "[package]\nname = \"{}-{}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\n",
+        topic.category, topic.name
+    );
+    for entry in dependencies.values() {
+        manifest.push_str(entry);
+        manifest.push('\n');
+    }
+    manifest.push_str("\n# Keep the project out of any enclosing workspace\n[workspace]\n");
+    fs::write(dir.join("Cargo.toml"), manifest)?;
+    fs::write(dir.join(".gitignore"), "/target\n")?;
+    fs::write(dir.join("src").join("main.rs"), main)?;
+    for (path, content) in module_files(&files) {
+        let target = dir.join("src").join(&path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = match content {
+            Some(content) => content,
+            None => source_crate
+                .files
+                .get_file(&path)
+                .and_then(|file| file.contents_utf8())
+                .unwrap_or_default()
+                .to_string(),
+        };
+        fs::write(target, content).with_context(|| format!("Failed to write {}", path))?;
+    }
+
+    Ok(Export {
+        dir,
+        dependencies: dependencies.into_keys().collect(),
+        local_dependencies,
+        unresolved: unresolved.into_iter().collect(),
+        needs_arguments,
+    })
+}
+
+/// Split a repository path into its embedded crate and the path inside it
+fn find_crate(path: &str) -> Option<(&'static SourceCrate, &str)> {
+    CRATES.iter().find_map(|source_crate| {
+        let relative = path.strip_prefix(source_crate.root)?.strip_prefix('/')?;
+        Some((source_crate, relative))
+    })
+}
+
+/// Add the module file `path`, and the modules it names with `crate::`, to `files`
+///
+/// A module with a `mod.rs` is copied with its whole directory, unless it
+/// holds the sample (its `mod.rs` is the category's registration module).
+fn collect_modules(
+    source_crate: &SourceCrate,
+    sample: &str,
+    path: &str,
+    files: &mut BTreeSet<String>,
+    unresolved: &mut BTreeSet<String>,
+) {
+    if !files.insert(path.to_string()) {
+        return;
+    }
+    let Some(code) = source_crate
+        .files
+        .get_file(path)
+        .and_then(|file| file.contents_utf8())
+    else {
+        return;
+    };
+
+    let pattern =
+        Regex::new(r"crate::((?:[a-z_][a-z0-9_]*::)*[a-z_][a-z0-9_]*)").expect("valid regex");
+    for captures in pattern.captures_iter(code) {
+        let reference = &captures[1];
+        let segments: Vec<&str> = reference.split("::").collect();
+        let module = (segments[0] != CLI_MODULE)
+            .then(|| resolve(source_crate, &segments))
+            .flatten();
+        match module {
+            Some(module) if module.ends_with("/mod.rs") => {
+                let dir = module.trim_end_matches("mod.rs");
+                if sample.starts_with(dir) {
+                    unresolved.insert(format!("crate::{}", reference));
+                    continue;
+                }
+                for file in all_files(source_crate, dir.trim_end_matches('/')) {
+                    collect_modules(source_crate, sample, &file, files, unresolved);
+                }
+            }
+            Some(module) => collect_modules(source_crate, sample, &module, files, unresolved),
+            None => {
+                unresolved.insert(format!("crate::{}", reference));
+            }
+        }
+    }
+}
+
+/// File of the module a `crate::` path names: its longest prefix that is a module
+fn resolve(source_crate: &SourceCrate, segments: &[&str]) -> Option<String> {
+    (1..=segments.len()).rev().find_map(|len| {
+        let module = segments[..len].join("/");
+        [format!("{}.rs", module), format!("{}/mod.rs", module)]
+            .into_iter()
+            .find(|path| source_crate.files.get_file(path).is_some())
+    })
+}
+
+/// Paths of the `.rs` files below `dir` of a crate
+fn all_files(source_crate: &SourceCrate, dir: &str) -> Vec<String> {
+    fn walk(dir: &Dir<'static>, out: &mut Vec<String>) {
+        for file in dir.files() {
+            if file
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "rs")
+            {
+                out.push(file.path().to_string_lossy().into_owned());
+            }
+        }
+        for sub in dir.dirs() {
+            walk(sub, out);
+        }
+    }
+    let mut out = Vec::new();
+    if let Some(dir) = source_crate.files.get_dir(dir) {
+        walk(dir, &mut out);
+    }
+    out
+}
+
+/// Files of the exported `src/` besides `main.rs`
+///
+/// Copied files keep their content (`None`); a directory without a copied
+/// `mod.rs` gets a generated one declaring its modules.
+fn module_files(files: &BTreeSet<String>) -> Vec<(String, Option<String>)> {
+    let mut out: Vec<_> = files.iter().map(|path| (path.clone(), None)).collect();
+    for (dir, children) in declarations(files) {
+        if dir.is_empty() {
+            continue;
+        }
+        let declarations: String = children
+            .iter()
+            .map(|child| format!("pub mod {};\n", child))
+            .collect();
+        out.push((format!("{}/mod.rs", dir), Some(declarations)));
+    }
+    out
+}
+
+/// Modules to declare in each directory lacking a copied `mod.rs` (`""` is `main.rs`)
+fn declarations(files: &BTreeSet<String>) -> BTreeMap<String, BTreeSet<String>> {
+    let copied_dir = |dir: &str| files.contains(&format!("{}/mod.rs", dir));
+    let mut declarations: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for path in files {
+        let module = path.trim_end_matches(".rs").trim_end_matches("/mod");
+        let mut parts: Vec<&str> = module.split('/').collect();
+        while let Some(child) = parts.pop() {
+            let parent = parts.join("/");
+            if !parent.is_empty() && copied_dir(&parent) {
+                break;
+            }
+            declarations
+                .entry(parent)
+                .or_default()
+                .insert(child.to_string());
+        }
+    }
+    declarations
+}
+
+/// How `main` runs the sample
+struct MainCall<'a> {
+    /// Call expression, `None` if the learner has to write it
+    call: Option<&'a str>,
+    function: &'a str,
+    is_async: bool,
+    returns_result: bool,
+}
+
+/// Generate `src/main.rs`: the module declarations and a `main` running the sample
+fn main_rs(
+    topic: &Topic,
+    file: &str,
+    files: &BTreeSet<String>,
+    macro_crates: &[String],
+    main: MainCall,
+) -> String {
+    let mut code = format!(
+        "//! hello {} {}: {}\n//!\n//! Exported from {} by `hello source --export`.\n\n",
+        topic.category,
+        topic.name,
+        topic.localized_description(),
+        file
+    );
+    code.push_str("#![allow(dead_code, unused_imports)]\n\n");
+    for name in macro_crates {
+        code.push_str(&format!("#[macro_use]\nextern crate {};\n", name));
+    }
+    if !macro_crates.is_empty() {
+        code.push('\n');
+    }
+    for module in declarations(files).remove("").unwrap_or_default() {
+        code.push_str(&format!("mod {};\n", module));
+    }
+    code.push('\n');
+
+    if main.is_async {
+        code.push_str("#[tokio::main]\nasync fn main() {\n");
+    } else {
+        code.push_str("fn main() {\n");
+    }
+    match main.call {
+        None => code.push_str(&format!(
+            "    // TODO: `{}` takes arguments (or is a method); call it here\n    todo!()\n",
+            main.function
+        )),
+        Some(call) => {
+            let call = if main.is_async {
+                format!("{}.await", call)
+            } else {
+                call.to_string()
+            };
+            if main.returns_result {
+                code.push_str(&format!(
+                    "    if let Err(e) = {} {{\n        eprintln!(\"Error: {{:?}}\", e);\n        std::process::exit(1);\n    }}\n",
+                    call
+                ));
+            } else {
+                code.push_str(&format!("    {};\n", call));
+            }
+        }
+    }
+    code.push_str("}\n");
+    code
+}
+
+/// Declaration of the function `name`, from `fn` up to its opening brace
+fn signature(code: &str, name: &str) -> String {
+    let declaration = format!("fn {}", name);
+    let start = code
+        .find(&declaration)
+        .map(|fn_start| code[..fn_start].rfind('\n').map_or(0, |line| line + 1))
+        .unwrap_or(0);
+    let rest = &code[start..];
+    rest[..rest.find('{').unwrap_or(rest.len())].to_string()
+}
+
+/// Check whether the function declared by `signature` takes parameters
+fn takes_arguments(signature: &str, name: &str) -> bool {
+    signature
+        .split_once(&format!("fn {}", name))
+        .and_then(|(_, rest)| rest.split_once('('))
+        .and_then(|(_, params)| params.split_once(')'))
+        .is_some_and(|(params, _)| !params.trim().is_empty())
+}
+
+/// Entries of the `[dependencies]` table of a manifest, as `(name, entry)`
+///
+/// An entry spans the lines up to the next key, so multi-line inline tables
+/// are kept whole.
+fn manifest_dependencies(manifest: &str) -> Vec<(String, String)> {
+    let mut entries: Vec<(String, String)> = Vec::new();
+    let mut in_dependencies = false;
+    for line in manifest.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[')
+            && !line.starts_with(char::is_whitespace)
+            && trimmed.ends_with(']')
+            && !trimmed.contains('=')
+        {
+            in_dependencies = trimmed == "[dependencies]";
+            continue;
+        }
+        if !in_dependencies || trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let continues = line.starts_with(char::is_whitespace) || line.starts_with([']', '}']);
+        match (continues, entries.last_mut()) {
+            (true, Some((_, entry))) => {
+                entry.push('\n');
+                entry.push_str(line);
+            }
+            _ => {
+                if let Some((name, _)) = line.split_once('=') {
+                    entries.push((name.trim().to_string(), line.to_string()));
+                }
+            }
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::registry;
+
+    #[test]
+    fn test_every_sample_function_is_found() {
+        for topic in registry::get_all_topics() {
+            let sample = topic
+                .sample_source
+                .unwrap_or_else(|| panic!("{} has no sample source", topic.key()));
+            let function = find_function(&sample)
+                .unwrap_or_else(|| panic!("{} not found in {}", sample.function, sample.file));
+            assert!(function.code.contains(&format!("fn {}", sample.function)));
+            let last = function.code.lines().last().unwrap_or("");
+            assert!(last.trim_start().starts_with('}'), "{}", topic.key());
+        }
+    }
+
+    #[test]
+    fn test_function_span_skips_braces_in_literals() {
+        let code = "use std::fmt;\n\n/// Doc\n#[inline]\npub fn braces() {\n    let s = \"}\";\n    let r = r#\"}\"#;\n    let c = '}';\n    // }\n    if true { println!(\"{}\", s); }\n}\n\nfn after() {}\n";
+        assert_eq!(
+            function_span(code, "braces"),
+            Some(Span { start: 3, end: 11 })
+        );
+        assert_eq!(
+            function_span(code, "after"),
+            Some(Span { start: 13, end: 13 })
+        );
+        assert_eq!(function_span(code, "missing"), None);
+    }
+
+    #[test]
+    fn test_manifest_dependencies_keep_multiline_entries() {
+        let manifest = "[package]\nname = \"x\"\n\n[dependencies]\nserde = \"1\"\nsqlx = { version = \"0.8\", features = [\n    \"sqlite\",\n] }\nlocal = { path = \"crates/local\" }\n\n[dev-dependencies]\ntempfile = \"3\"\n";
+        let entries = manifest_dependencies(manifest);
+        let names: Vec<_> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["serde", "sqlx", "local"]);
+        assert!(entries[1].1.ends_with("] }"));
+    }
+
+    #[test]
+    fn test_export_writes_standalone_project() {
+        let root = tempfile::tempdir().unwrap();
+        let topic = registry::get_topic("basic", "logger").unwrap();
+        let export = export(topic, root.path()).unwrap();
+
+        let src = export.dir.join("src");
+        let main = fs::read_to_string(src.join("main.rs")).unwrap();
+        assert!(main.contains("mod basic;"));
+        assert!(main.contains("basic::logger_sample::logger_print();"));
+        // `crate::basic::tracing_sample` is copied along
+        let declared = fs::read_to_string(src.join("basic").join("mod.rs")).unwrap();
+        assert_eq!(
+            declared,
+            "pub mod logger_sample;\npub mod tracing_sample;\n"
+        );
+        assert!(src.join("basic").join("tracing_sample.rs").is_file());
+
+        let manifest = fs::read_to_string(export.dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("[workspace]"));
+        assert!(export.dependencies.iter().any(|name| name == "log"));
+        assert!(!export.needs_arguments);
+        assert!(export.unresolved.is_empty());
+
+        assert!(super::export(topic, root.path()).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+//! Per-user learner state persisted between runs
+//!
+//! Progress is stored as JSON under the state directory, which defaults to
+//! `~/.hello-rust` and can be overridden with the `HELLO_STATE_DIR`
+//! environment variable.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::i18n::t;
+use crate::cli::registry::Topic;
+
+/// Environment variable overriding the state directory
+pub const STATE_DIR_ENV: &str = "HELLO_STATE_DIR";
+
+/// File name of the progress file inside the state directory
+const PROGRESS_FILE: &str = "progress.json";
+
+/// Get the directory holding all per-user CLI state
+pub fn state_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(STATE_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+    home::home_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(".hello-rust")
+}
+
+/// Progress of a single topic
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TopicProgress {
+    /// Number of times the sample was started
+    pub runs: u32,
+
+    /// When the sample was last started
+    pub last_run: Option<DateTime<Local>>,
+
+    /// Whether the sample ran to completion at least once
+    pub completed: bool,
+
+    /// Whether the tutorial was displayed at least once
+    pub docs_read: bool,
+
+    /// Whether the topic's exercise passed its hidden tests
+    #[serde(default)]
+    pub exercise_passed: bool,
+}
+
+/// Latest result of a quiz question
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizResult {
+    /// Whether the latest answer was correct
+    pub correct: bool,
+
+    /// Number of times the question was answered
+    pub attempts: u32,
+
+    /// When the question was last answered
+    pub answered: DateTime<Local>,
+}
+
+/// Learner progress across all topics, keyed by "category/name"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Progress {
+    pub topics: BTreeMap<String, TopicProgress>,
+
+    /// Quiz results keyed by question id ("<doc path>#<n>")
+    #[serde(default)]
+    pub quiz: BTreeMap<String, QuizResult>,
+}
+
+impl Progress {
+    /// Path of the progress file
+    pub fn path() -> PathBuf {
+        state_dir().join(PROGRESS_FILE)
+    }
+
+    /// Load progress from the default location
+    ///
+    /// A missing file yields empty progress; an unreadable one is reported
+    /// and treated as empty so the CLI keeps working.
+    pub fn load() -> Self {
+        let path = Self::path();
+        if !path.exists() {
+            return Self::default();
+        }
+        Self::load_from(&path).unwrap_or_else(|e| {
+            eprintln!("{}", t!(WARN_UNREADABLE_PROGRESS, format!("{:#}", e)));
+            Self::default()
+        })
+    }
+
+    /// Load progress from `path`
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Save progress to the default location, warning on failure
+    pub fn save(&self) {
+        if let Err(e) = self.save_to(&Self::path()) {
+            eprintln!("{}", t!(WARN_PROGRESS_NOT_SAVED, format!("{:#}", e)));
+        }
+    }
+
+    /// Save progress to `path`, creating parent directories
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Get the progress of `topic`, if it was ever started
+    pub fn get(&self, topic: &Topic) -> Option<&TopicProgress> {
+        self.topics.get(&topic.key())
+    }
+
+    /// Check whether `topic` ran to completion at least once
+    pub fn is_completed(&self, topic: &Topic) -> bool {
+        self.get(topic).is_some_and(|p| p.completed)
+    }
+
+    /// Record that the sample of `topic` was started now
+    pub fn record_run(&mut self, topic: &Topic) {
+        let entry = self.topics.entry(topic.key()).or_default();
+        entry.runs += 1;
+        entry.last_run = Some(Local::now());
+    }
+
+    /// Record that the sample of `topic` ran to completion
+    pub fn record_completed(&mut self, topic: &Topic) {
+        self.topics.entry(topic.key()).or_default().completed = true;
+    }
+
+    /// Record that the tutorial of `topic` was displayed
+    pub fn record_docs_read(&mut self, topic: &Topic) {
+        self.topics.entry(topic.key()).or_default().docs_read = true;
+    }
+
+    /// Record that the exercise of `topic` passed its hidden tests
+    pub fn record_exercise_passed(&mut self, topic: &Topic) {
+        self.topics.entry(topic.key()).or_default().exercise_passed = true;
+    }
+
+    /// Record an answer to the quiz question `id`
+    pub fn record_quiz_answer(&mut self, id: &str, correct: bool) {
+        let attempts = self.quiz.get(id).map_or(0, |r| r.attempts);
+        self.quiz.insert(
+            id.to_string(),
+            QuizResult {
+                correct,
+                attempts: attempts + 1,
+                answered: Local::now(),
+            },
+        );
+    }
+
+    /// Check whether the latest answer to quiz question `id` was correct
+    pub fn quiz_correct(&self, id: &str) -> bool {
+        self.quiz.get(id).is_some_and(|r| r.correct)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::registry;
+
+    #[test]
+    fn test_progress_roundtrip() {
+        let topic = registry::get_topic("basic", "ownership").unwrap();
+        let mut progress = Progress::default();
+        progress.record_run(topic);
+        progress.record_docs_read(topic);
+        progress.record_completed(topic);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(PROGRESS_FILE);
+        progress.save_to(&path).unwrap();
+
+        let loaded = Progress::load_from(&path).unwrap();
+        let entry = loaded.get(topic).unwrap();
+        assert_eq!(entry.runs, 1);
+        assert!(entry.completed && entry.docs_read);
+        assert!(entry.last_run.is_some());
+    }
+
+    #[test]
+    fn test_quiz_answers_keep_latest() {
+        let mut progress = Progress::default();
+        progress.record_quiz_answer("quiz/index.md#1", false);
+        progress.record_quiz_answer("quiz/index.md#1", true);
+
+        assert!(progress.quiz_correct("quiz/index.md#1"));
+        assert_eq!(progress.quiz["quiz/index.md#1"].attempts, 2);
+        assert!(!progress.quiz_correct("quiz/index.md#2"));
+
+        // Files written before quizzes existed still load
+        let old: Progress = serde_json::from_str(r#"{"topics":{}}"#).unwrap();
+        assert!(old.quiz.is_empty());
+    }
+
+    #[test]
+    fn test_run_without_completion() {
+        let topic = registry::get_topic("basic", "threads").unwrap();
+        let mut progress = Progress::default();
+        progress.record_run(topic);
+        progress.record_run(topic);
+
+        assert_eq!(progress.get(topic).unwrap().runs, 2);
+        assert!(!progress.is_completed(topic));
+    }
+}
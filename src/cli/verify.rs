@@ -0,0 +1,367 @@
+//! Golden-output verification of Function topics
+//!
+//! Each eligible topic is run in a child `hello` process, its stdout/stderr
+//! are captured and masked, and the result is compared with a checked-in
+//! snapshot under `tests/snapshots/<category>/<topic>.txt`.
+//!
+//! Children run in the project root (the checkout holding `data/`), since
+//! samples open relative paths: they read their input from `data/` and write
+//! files there too (`data/filtered_employees.csv`, `data/test.db`), which are
+//! ignored by git.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::LazyLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+use crate::cli::checkout;
+use crate::cli::registry::{self, SampleType, Topic};
+use crate::cli::state::STATE_DIR_ENV;
+
+/// Directory holding the snapshot files, relative to the project root
+pub const SNAPSHOT_DIR: &str = "tests/snapshots";
+
+/// Input file the samples read, marking the project root
+const SAMPLE_INPUT: &str = "data/employees.csv";
+
+/// Poll interval while waiting for a child process
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Non-deterministic tokens and their placeholders, applied in order
+static MASKS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    [
+        (
+            r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}",
+            "[UUID]",
+        ),
+        (
+            r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:?\d{2})?( UTC)?",
+            "[TIMESTAMP]",
+        ),
+        (r"\b\d{2}:\d{2}:\d{2}(\.\d+)?\b", "[TIME]"),
+        (r"(?i)(\bp?pid\b\s*[:=]?\s*)\d+", "${1}[PID]"),
+        (r"(进程\s*ID\s*[:：=]?\s*)\d+", "${1}[PID]"),
+        (r"/(?:[\w.-]+/)*(?:\.tmp[A-Za-z0-9]+|#\d+)", "[TMPFILE]"),
+        (r"\b0x[0-9a-fA-F]{6,}\b", "[ADDR]"),
+        (r"\b\d+(\.\d+)?(ns|µs|us|ms)\b", "[DURATION]"),
+        (r"\b\d+\.\d+s\b", "[DURATION]"),
+        (r"ThreadId\(\d+\)", "ThreadId([N])"),
+    ]
+    .into_iter()
+    .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+    .collect()
+});
+
+/// Captured result of running a topic in a child process
+#[derive(Debug, Clone)]
+pub struct Captured {
+    /// Whether the child was killed after exceeding the timeout
+    pub timed_out: bool,
+    /// Exit code of the child (`None` if it was killed by a signal)
+    pub code: Option<i32>,
+    /// Wall-clock time the child ran
+    pub elapsed: Duration,
+    /// Masked exit status, stdout and stderr in snapshot format
+    pub output: String,
+    /// Masked stdout alone
+    pub stdout: String,
+    /// Masked stderr alone
+    pub stderr: String,
+}
+
+/// Outcome of verifying one topic
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    /// Output matches the snapshot
+    Passed,
+    /// Snapshot was written with `--bless`
+    Blessed,
+    /// No snapshot exists yet
+    Missing(String),
+    /// Output differs from the snapshot
+    Mismatch(Vec<DiffLine>),
+}
+
+/// One line of a line-based diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Line present in both (kept for context)
+    Same(String),
+    /// Line only in the snapshot
+    Removed(String),
+    /// Line only in the actual output
+    Added(String),
+}
+
+/// Check whether a topic can be verified: a function without external
+/// services whose output is reproducible
+pub fn is_verifiable(topic: &Topic) -> bool {
+    matches!(topic.sample_type, SampleType::Function)
+        && !topic.has_dependencies()
+        && !topic.volatile_output
+}
+
+/// Get the verifiable topics of `category` (or of all categories) in registry order
+pub fn verifiable_topics(category: Option<&str>) -> Vec<&'static Topic> {
+    registry::learning_path()
+        .into_iter()
+        .filter(|topic| category.is_none_or(|c| topic.category == c))
+        .filter(|topic| is_verifiable(topic))
+        .collect()
+}
+
+/// Find the snapshot files when `--snapshot-dir` is not given
+///
+/// Looks for `tests/snapshots` the way [`project_root`] looks for `data/`.
+/// Falls back to `tests/snapshots` in the current directory.
+pub fn default_snapshot_dir() -> PathBuf {
+    checkout::find(SNAPSHOT_DIR, Path::is_dir).unwrap_or_else(|| PathBuf::from(SNAPSHOT_DIR))
+}
+
+/// Find the project root the samples run in
+///
+/// The root is the first directory holding the samples' input files: the
+/// current directory, or the directory of the `hello` executable or one of its
+/// parents. Falls back to the current directory.
+pub fn project_root() -> PathBuf {
+    checkout::find(SAMPLE_INPUT, Path::is_file)
+        .and_then(|input| input.ancestors().nth(2).map(Path::to_path_buf))
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default()
+}
+
+/// Path of the snapshot file of `topic` inside `snapshot_dir`
+pub fn snapshot_path(snapshot_dir: &Path, topic: &Topic) -> PathBuf {
+    snapshot_dir
+        .join(topic.category)
+        .join(format!("{}.txt", topic.name))
+}
+
+/// Replace non-deterministic tokens (UUIDs, timestamps, PIDs, ...) with placeholders
+///
+/// # Arguments
+/// * `text` - Raw output
+/// * `pid` - PID of the process that produced `text`, masked wherever it appears
+/// * `cwd` - Working directory of that process, masked as `[CWD]`
+pub fn mask(text: &str, pid: Option<u32>, cwd: Option<&Path>) -> String {
+    let mut masked = match pid {
+        Some(pid) => Regex::new(&format!(r"\b{}\b", pid))
+            .unwrap()
+            .replace_all(text, "[PID]")
+            .into_owned(),
+        None => text.to_string(),
+    };
+    if let Some(cwd) = cwd.and_then(Path::to_str) {
+        masked = masked.replace(cwd, "[CWD]");
+    }
+    for (regex, replacement) in MASKS.iter() {
+        masked = regex.replace_all(&masked, *replacement).into_owned();
+    }
+    masked
+}
+
+/// Run `topic` in a child `hello` process and capture its masked output
+///
+/// The child runs in the [`project_root`] whatever the caller's working
+/// directory, gets a throwaway state directory so verification does not
+/// touch learner progress, and is killed once `timeout` elapses. It prints
+/// in English, so snapshots and reports do not depend on the locale.
+pub fn run_topic(topic: &Topic, timeout: Duration) -> Result<Captured> {
+    let exe = std::env::current_exe().context("Failed to locate the hello executable")?;
+    let state_dir = tempfile::tempdir()?;
+    let root = project_root();
+
+    let mut child = Command::new(exe)
+        .args([topic.category, topic.name, "--no-docs", "--force"])
+        .args(["--lang", "en"])
+        .current_dir(&root)
+        .env(STATE_DIR_ENV, state_dir.path())
+        .env_remove("RUST_LOG")
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start topic '{}'", topic.key()))?;
+    let pid = child.id();
+
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let started = Instant::now();
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            timed_out = true;
+            child.kill()?;
+            break child.wait()?;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    let elapsed = started.elapsed();
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    let output = format!(
+        "--- status: {} ---\n--- stdout ---\n{}--- stderr ---\n{}",
+        status
+            .code()
+            .map_or_else(|| "killed".to_string(), |c| c.to_string()),
+        stdout,
+        stderr
+    );
+
+    Ok(Captured {
+        timed_out,
+        code: status.code(),
+        elapsed,
+        output: mask(&output, Some(pid), Some(&root)),
+        stdout: mask(&stdout, Some(pid), Some(&root)),
+        stderr: mask(&stderr, Some(pid), Some(&root)),
+    })
+}
+
+/// Compare captured output with the snapshot of `topic`, or write it when blessing
+pub fn check_snapshot(
+    snapshot_dir: &Path,
+    topic: &Topic,
+    captured: &Captured,
+    bless: bool,
+) -> Result<Outcome> {
+    let path = snapshot_path(snapshot_dir, topic);
+    let expected = std::fs::read_to_string(&path).ok();
+
+    if expected.as_deref() == Some(captured.output.as_str()) {
+        return Ok(Outcome::Passed);
+    }
+    if bless {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &captured.output)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        return Ok(Outcome::Blessed);
+    }
+    Ok(match expected {
+        Some(expected) => Outcome::Mismatch(line_diff(&expected, &captured.output)),
+        None => Outcome::Missing(path.display().to_string()),
+    })
+}
+
+/// Line-based diff of `expected` against `actual` (longest common subsequence)
+pub fn line_diff(expected: &str, actual: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] = length of the LCS of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            diff.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        } else {
+            diff.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        }
+    }
+    diff
+}
+
+/// Read a child pipe to a string on a background thread
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        String::from_utf8_lossy(&buffer).into_owned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_tokens() {
+        let text = "id=67e55044-10b1-426f-9247-bb680e5fe0c8 at 2026-10-18T09:30:00.123Z\n\
+                    pid: 4242, child 4243 at 0x7ffd5e8c1a2c took 1.25ms\n\
+                    父进程 ID: 31188, path: \"/tmp/.tmpRRiN3O\"";
+        assert_eq!(
+            mask(text, Some(4243), None),
+            "id=[UUID] at [TIMESTAMP]\npid: [PID], child [PID] at [ADDR] took [DURATION]\n\
+             父进程 ID: [PID], path: \"[TMPFILE]\""
+        );
+    }
+
+    #[test]
+    fn test_mask_keeps_plain_numbers() {
+        assert_eq!(mask("area=1500, 30x50", None, None), "area=1500, 30x50");
+    }
+
+    #[test]
+    fn test_mask_working_directory() {
+        assert_eq!(
+            mask(
+                "wrote /work/hello/data/out.csv",
+                None,
+                Some(Path::new("/work/hello"))
+            ),
+            "wrote [CWD]/data/out.csv"
+        );
+    }
+
+    #[test]
+    fn test_samples_run_in_project_root() {
+        let root = project_root();
+        assert!(root.is_absolute());
+        assert!(root.join(SAMPLE_INPUT).is_file());
+        assert!(default_snapshot_dir().is_absolute());
+    }
+
+    #[test]
+    fn test_line_diff() {
+        let diff = line_diff("a\nb\nc\n", "a\nx\nc\n");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a".into()),
+                DiffLine::Added("x".into()),
+                DiffLine::Removed("b".into()),
+                DiffLine::Same("c".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verifiable_topics_skip_dependencies() {
+        let topics = verifiable_topics(Some("advance"));
+        assert!(!topics.is_empty());
+        assert!(topics.iter().all(|t| t.dependencies.is_empty()));
+        assert!(!topics.iter().any(|t| t.name == "sqlx"));
+    }
+}
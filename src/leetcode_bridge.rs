@@ -0,0 +1,110 @@
+//! LeetCode crate problem registration
+//!
+//! This module registers the problems of the `leetcode` workspace crate as
+//! topics of the `leetcode` category, named by problem number
+//! (`hello leetcode 1`). Running a topic runs every solution variant against
+//! all test cases and prints the cross-checked report.
+
+use std::fmt::Debug;
+
+use anyhow::anyhow;
+use leetcode::problem::Problem;
+use leetcode::{solution_0001, solution_0002};
+
+use crate::cli::registry::{Category, SampleType, Topic, TopicResult};
+
+// Register the leetcode category with inventory
+inventory::submit! {
+    Category::new(
+        "leetcode",
+        "LeetCode 题解",
+        50,
+        "LeetCode 题解：多种解法对照运行、交叉校验与计时",
+    )
+    .with_english(
+        "LeetCode Solutions",
+        "LeetCode solutions: run every variant, cross-check and time them",
+    )
+}
+
+inventory::submit! {
+    Topic::new(
+        "1",
+        "两数之和 (Two Sum) · 简单",
+        "leetcode",
+        || run_problem(&solution_0001::PROBLEM),
+        &[],
+        Some("leetcode/0001-two-sum.md"),
+        SampleType::Function,
+    )
+    .with_english("Two Sum · Easy")
+    .with_sample_source("crates/leetcode/src/solution_0001.rs", "two_sum")
+    .with_volatile_output()
+}
+
+inventory::submit! {
+    Topic::new(
+        "2",
+        "两数相加 (Add Two Numbers) · 中等",
+        "leetcode",
+        || run_problem(&solution_0002::PROBLEM),
+        &[],
+        Some("leetcode/0002-add-two-numbers.md"),
+        SampleType::Function,
+    )
+    .with_english("Add Two Numbers · Medium")
+    .with_sample_source("crates/leetcode/src/solution_0002.rs", "add_two_numbers")
+    .with_prerequisites(&["1"])
+    .with_volatile_output()
+}
+
+/// Run all variants of `problem` and print the report
+///
+/// Fails if a variant misses a test case or the variants disagree.
+fn run_problem<I, O>(problem: &Problem<I, O>) -> TopicResult
+where
+    I: Clone + Debug + 'static,
+    O: PartialEq + Debug + 'static,
+{
+    let report = problem.run();
+    println!("{}", report);
+    if report.is_ok() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "problem {} ({}) has failing or disagreeing variants",
+            report.number,
+            report.title
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::registry;
+
+    /// Check the descriptions of topic `name` against the title and difficulty of `problem`
+    fn assert_describes<I, O>(name: &str, problem: &Problem<I, O>) {
+        let topic = registry::get_topic("leetcode", name).unwrap();
+        assert_eq!(
+            topic.description,
+            format!(
+                "{} ({}) · {}",
+                problem.title_zh,
+                problem.title,
+                problem.difficulty.name_zh()
+            )
+        );
+        assert_eq!(
+            topic.description_en.unwrap(),
+            format!("{} · {}", problem.title, problem.difficulty.name())
+        );
+    }
+
+    #[test]
+    fn test_descriptions_come_from_problems() {
+        assert_describes("1", &solution_0001::PROBLEM);
+        assert_describes("2", &solution_0002::PROBLEM);
+    }
+}
@@ -5,11 +5,39 @@
 //! Usage:
 //!   hello                    Show help and categories
 //!   hello list               List all topics
+//!   hello list --format json Export the topic catalog (json, markdown, csv)
+//!   hello search <query>     Search topics and tutorials
+//!   hello progress           Show learning progress
+//!   hello next               Show the next unfinished topic
+//!   hello verify [category]  Check sample output against snapshots
+//!   hello run-all [category] Run every topic in isolation, write JUnit/JSON reports
+//!   hello exercise <topic>   Solve a topic's exercise (--watch to re-check)
+//!   hello quiz [target]      Answer quiz questions from the tutorials
+//!   hello doctor             Check external services used by samples
+//!   hello serve <cat> <topic> Start a server topic in the background
+//!   hello ps                 List background servers
+//!   hello stop <topic>       Stop a background server
 //!   hello basic              List basic topics
 //!   hello basic <topic>      Run a basic topic sample
 //!   hello advance <topic>    Run an advance topic sample
 //!   hello awesome <topic>    Run an awesome topic sample
 //!   hello algo <topic>       Run an algo topic sample
+//!   hello leetcode 1         Run, cross-check and time every solution of problem 1
+//!   hello algo calc-pi --steps 1e7 --method bbp
+//!                            Run a topic with its options (see `hello algo --help`)
+//!   hello source <cat> <topic> Print the function a topic runs (--export DIR to copy it)
+//!   hello projects --list    List the example projects with their tutorials
+//!   hello projects todo -- add "x"
+//!                            Build examples/todo and run it with the arguments after `--`
+//!   hello projects test todo Run the test suite of an example project
+//!   hello docs serve [--port N] Browse the tutorials on http://127.0.0.1:3000 with runnable samples
+//!   hello completions <shell> Print a completion script (bash, zsh, fish, powershell)
+//!   hello man [--out-dir DIR] Print the man page, or write all pages to DIR
+//!   hello --lang en <...>    Print in English (default: from LANG, else Chinese)
+//!
+//! Exit codes of running a topic (see `cli::error`):
+//!   2 unknown topic or invalid options, 3 dependency unavailable,
+//!   4 sample failed (error or panic), 5 timed out, 130 cancelled with Ctrl-C
 
 #[macro_use]
 extern crate cfg_if;
@@ -17,14 +45,17 @@ extern crate log;
 extern crate getset;
 extern crate inventory;
 
-use clap::Parser;
-use crate::cli::{Commands, dispatcher};
+use std::time::Duration;
+
+use clap::{CommandFactory, FromArgMatches, Parser};
+use crate::cli::{CategoryArgs, Commands, DocsAction, ProjectAction, dispatcher};
 
 mod cli;
 mod advance;
 mod algo;
 mod basic;
 mod awesome_bridge;
+mod leetcode_bridge;
 
 /// Hello Rust CLI — Interactive Learning Tool
 #[derive(Parser, Debug)]
@@ -36,101 +67,212 @@ struct Cli {
     /// Subcommand to execute
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// Output language (default: from the LANG environment variable, else zh)
+    #[arg(long, global = true, value_enum)]
+    lang: Option<cli::i18n::Lang>,
+}
+
+/// Build the full `hello` command: fixed subcommands plus one per category
+fn command() -> clap::Command {
+    cli::localize(cli::with_categories(Cli::command()))
 }
 
 fn main() {
-    let cli = Cli::parse();
-    
+    // The language is chosen before parsing so that `--help` is localized too
+    cli::i18n::init(cli::i18n::lang_from_args(std::env::args_os()));
+    let matches = command().get_matches();
+
+    // Category subcommands are built from the registry, not from `Commands`
+    if let Some((name, sub_matches)) = matches.subcommand()
+        && let Some(category) = cli::registry::get_category(name)
+    {
+        let mut args = CategoryArgs::from_arg_matches(sub_matches).unwrap_or_else(|e| e.exit());
+        args.topic_args = cli::topic_arg_values(category.name, sub_matches);
+        dispatcher::run_category(category, &args);
+        return;
+    }
+
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
     match &cli.command {
         // No subcommand: show help
         None => {
             dispatcher::list_all();
         }
-        
+
         // List all topics
-        Some(Commands::List) => {
-            dispatcher::list_all();
+        Some(Commands::List {
+            summary: Some(mode),
+            docs_dir,
+            ..
+        }) => {
+            if !dispatcher::sync_summary(*mode, docs_dir) {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::List { format, .. }) => {
+            dispatcher::list(*format);
         }
-        
-        // Basic category
-        Some(Commands::Basic { topic, no_docs, force }) => {
-            match topic {
-                Some(topic_name) => {
-                    // Run specific topic
-                    if let Some(topic) = cli::registry::get_topic("basic", topic_name) {
-                        if let Err(e) = dispatcher::execute_topic(topic, *no_docs, *force) {
-                            eprintln!("Error: {}", e);
-                            std::process::exit(1);
-                        }
-                    } else {
-                        dispatcher::handle_unknown_topic("basic", topic_name);
-                        std::process::exit(1);
-                    }
-                }
-                None => {
-                    // List all basic topics
-                    dispatcher::list_category("basic");
-                }
+
+        // Search topics and tutorials
+        Some(Commands::Search { query, limit }) => {
+            dispatcher::search(&query.join(" "), *limit);
+        }
+
+        // Learner progress
+        Some(Commands::Progress { reset: true }) => {
+            dispatcher::reset_progress();
+        }
+        Some(Commands::Progress { reset: false }) => {
+            dispatcher::show_progress();
+        }
+
+        // Golden-output verification
+        Some(Commands::Verify {
+            category,
+            bless,
+            snapshot_dir,
+            timeout,
+        }) => {
+            let snapshot_dir = snapshot_dir
+                .clone()
+                .unwrap_or_else(cli::verify::default_snapshot_dir);
+            let passed = dispatcher::verify(
+                category.as_deref(),
+                *bless,
+                &snapshot_dir,
+                Duration::from_secs(*timeout),
+            );
+            if !passed {
+                std::process::exit(1);
+            }
+        }
+
+        // Batch run of every topic
+        Some(Commands::RunAll {
+            category,
+            timeout,
+            report_dir,
+        }) => {
+            let passed =
+                dispatcher::run_all(category.as_deref(), report_dir, Duration::from_secs(*timeout));
+            if !passed {
+                std::process::exit(1);
             }
         }
-        
-        // Advance category
-        Some(Commands::Advance { topic, no_docs, force }) => {
-            match topic {
-                Some(topic_name) => {
-                    if let Some(topic) = cli::registry::get_topic("advance", topic_name) {
-                        if let Err(e) = dispatcher::execute_topic(topic, *no_docs, *force) {
-                            eprintln!("Error: {}", e);
-                            std::process::exit(1);
-                        }
-                    } else {
-                        dispatcher::handle_unknown_topic("advance", topic_name);
-                        std::process::exit(1);
-                    }
-                }
-                None => {
-                    dispatcher::list_category("advance");
-                }
+
+        // Next unfinished topic
+        Some(Commands::Next { run, no_docs }) => {
+            dispatcher::show_next(*run, *no_docs);
+        }
+
+        // Hands-on exercises
+        Some(Commands::Exercise {
+            topic,
+            watch,
+            reset,
+            dir,
+        }) => {
+            if !dispatcher::exercise(topic.as_deref(), dir, *watch, *reset) {
+                std::process::exit(1);
             }
         }
-        
-        // Awesome category
-        Some(Commands::Awesome { topic, no_docs, force }) => {
-            match topic {
-                Some(topic_name) => {
-                    if let Some(topic) = cli::registry::get_topic("awesome", topic_name) {
-                        if let Err(e) = dispatcher::execute_topic(topic, *no_docs, *force) {
-                            eprintln!("Error: {}", e);
-                            std::process::exit(1);
-                        }
-                    } else {
-                        dispatcher::handle_unknown_topic("awesome", topic_name);
-                        std::process::exit(1);
-                    }
-                }
-                None => {
-                    dispatcher::list_category("awesome");
-                }
+
+        // Interactive quiz
+        Some(Commands::Quiz { target, count }) => {
+            if !dispatcher::quiz(target.as_deref(), *count) {
+                std::process::exit(1);
+            }
+        }
+
+        // External service probes
+        Some(Commands::Doctor) => {
+            if !dispatcher::doctor() {
+                std::process::exit(1);
+            }
+        }
+
+        // Background server topics
+        Some(Commands::Serve {
+            category,
+            topic,
+            port,
+            timeout,
+        }) => {
+            if !dispatcher::serve(category, topic, *port, Duration::from_secs(*timeout)) {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Ps) => {
+            dispatcher::ps();
+        }
+        Some(Commands::Stop {
+            topic,
+            all: _,
+            timeout,
+        }) => {
+            if !dispatcher::stop(topic.as_deref(), Duration::from_secs(*timeout)) {
+                std::process::exit(1);
+            }
+        }
+
+        // Sample source of a topic
+        Some(Commands::Source {
+            category,
+            topic,
+            export,
+        }) => {
+            if !dispatcher::source(category, topic, export.as_deref()) {
+                std::process::exit(1);
+            }
+        }
+
+        // Tutorials in the browser
+        Some(Commands::Docs {
+            action: DocsAction::Serve { port },
+        }) => {
+            if !dispatcher::docs_serve(*port) {
+                std::process::exit(1);
+            }
+        }
+
+        // Example projects under examples/
+        Some(Commands::Projects {
+            action: Some(ProjectAction::Test { project, args }),
+            dir,
+            ..
+        }) => {
+            let dir = dir.clone().unwrap_or_else(cli::projects::default_dir);
+            if !dispatcher::project(project, cli::projects::Action::Test, args, &dir) {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Projects {
+            project: Some(project),
+            args,
+            dir,
+            ..
+        }) => {
+            let dir = dir.clone().unwrap_or_else(cli::projects::default_dir);
+            if !dispatcher::project(project, cli::projects::Action::Run, args, &dir) {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Projects { dir, .. }) => {
+            let dir = dir.clone().unwrap_or_else(cli::projects::default_dir);
+            dispatcher::list_projects(&dir);
+        }
+
+        // Shell completions and man pages, generated from the full command
+        Some(Commands::Completions { shell }) => {
+            if !dispatcher::completions(*shell, command()) {
+                std::process::exit(1);
             }
         }
-        
-        // Algo category
-        Some(Commands::Algo { topic, no_docs, force }) => {
-            match topic {
-                Some(topic_name) => {
-                    if let Some(topic) = cli::registry::get_topic("algo", topic_name) {
-                        if let Err(e) = dispatcher::execute_topic(topic, *no_docs, *force) {
-                            eprintln!("Error: {}", e);
-                            std::process::exit(1);
-                        }
-                    } else {
-                        dispatcher::handle_unknown_topic("algo", topic_name);
-                        std::process::exit(1);
-                    }
-                }
-                None => {
-                    dispatcher::list_category("algo");
-                }
+        Some(Commands::Man { out_dir }) => {
+            if !dispatcher::man(command(), out_dir.as_deref()) {
+                std::process::exit(1);
             }
         }
     }